@@ -0,0 +1,79 @@
+use clap::{Parser, Subcommand};
+use solana_rpc_performance_rust::report::BenchmarkReport;
+use solana_rpc_performance_rust::tools::rpc_latency_test::{
+    AssertionFailed, ASSERTION_FAILED_EXIT_CODE,
+};
+use solana_rpc_performance_rust::tools::{
+    compare_reports, history, laserstream_benchmark, latency_calculator, racing_comparison,
+    rpc_latency_test, rpc_vs_laserstream_logger, signature_poll_latency,
+};
+use std::path::{Path, PathBuf};
+
+#[derive(Parser)]
+#[command(name = "bench")]
+#[command(
+    about = "Every latency tool behind one binary; each subcommand takes the flags of the tool it runs"
+)]
+struct Cli {
+    #[arg(
+        long,
+        global = true,
+        value_name = "JSON",
+        help = "Save the run's BenchmarkReport as JSON, for `bench compare` (rpc, grpc and websocket)"
+    )]
+    report: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// HTTP RPC slot detection latency (rpc_latency_test)
+    Rpc(rpc_latency_test::Args),
+    /// Laserstream block propagation benchmark (laserstream_benchmark)
+    Grpc(Box<laserstream_benchmark::Args>),
+    /// WebSocket blockSubscribe latency (latency_calculator --method websocket)
+    #[command(mut_arg("method", |arg| arg.required(false).default_value("websocket")))]
+    Websocket(latency_calculator::Args),
+    /// Laserstream vs WebSocket vs RPC race per slot (racing_comparison)
+    Race(racing_comparison::Args),
+    /// Transaction detection latency for a watched account (signature_poll_latency)
+    Tx(signature_poll_latency::Args),
+    /// RPC and Laserstream block feeds logged side by side (rpc_vs_laserstream_logger)
+    Feed(rpc_vs_laserstream_logger::Args),
+    /// Latency deltas between two saved reports, failing on a regression (compare_reports)
+    Compare(compare_reports::Args),
+    /// Daily latency percentiles from a --db history (history)
+    History(history::Args),
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    let report = match cli.command {
+        Command::Rpc(args) => match rpc_latency_test::run(args).await {
+            Ok(report) => report,
+            Err(e) if e.is::<AssertionFailed>() => std::process::exit(ASSERTION_FAILED_EXIT_CODE),
+            Err(e) => return Err(e),
+        },
+        Command::Grpc(args) => laserstream_benchmark::run(*args).await?,
+        Command::Websocket(args) => latency_calculator::run(args).await?,
+        Command::Race(args) => return racing_comparison::run(args).await,
+        Command::Tx(args) => return signature_poll_latency::run(args).await,
+        Command::Feed(args) => return rpc_vs_laserstream_logger::run(args).await,
+        Command::Compare(args) => return compare_reports::run(args).await,
+        Command::History(args) => return history::run(args).await,
+    };
+
+    if let Some(path) = &cli.report {
+        save_report(&report, path)?;
+    }
+    Ok(())
+}
+
+fn save_report(report: &BenchmarkReport, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::write(path, serde_json::to_string_pretty(report)?)?;
+    eprintln!("📄 Report written to {}", path.display());
+    Ok(())
+}
@@ -0,0 +1,218 @@
+use anyhow::Result;
+use clap::Parser;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::Instant;
+
+/// Tools a scenario may run, and whether they take `--duration` / `--endpoint`.
+const TOOLS: &[(&str, bool, bool)] = &[
+    ("laserstream_benchmark", true, true),
+    ("rpc_latency_test", true, false),
+    ("racing_comparison", true, true),
+    ("rpc_vs_laserstream_logger", true, true),
+    ("latency_calculator", true, true),
+    ("signature_poll_latency", true, false),
+];
+
+#[derive(Parser)]
+#[command(name = "scenario")]
+#[command(about = "Run a declared battery of benchmark tools and assemble one combined report")]
+struct Args {
+    #[arg(long, help = "Scenario file (TOML)")]
+    scenario: String,
+
+    #[arg(long, help = "Print the commands without running them")]
+    dry_run: bool,
+}
+
+#[derive(Deserialize)]
+struct Scenario {
+    name: String,
+    /// Combined JSON report path; printed to stdout when absent.
+    report: Option<String>,
+    #[serde(rename = "step")]
+    steps: Vec<Step>,
+}
+
+#[derive(Deserialize)]
+struct Step {
+    tool: String,
+    /// Minutes, passed as `--duration`.
+    duration: Option<u64>,
+    endpoint: Option<String>,
+    /// Where the tool's stdout is saved.
+    output: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+}
+
+impl Step {
+    fn command_args(&self) -> Result<Vec<String>> {
+        let &(_, takes_duration, takes_endpoint) = TOOLS
+            .iter()
+            .find(|(name, _, _)| *name == self.tool)
+            .ok_or_else(|| anyhow::anyhow!("Unknown tool in scenario: {}", self.tool))?;
+
+        let mut args = Vec::new();
+        if let Some(duration) = self.duration {
+            if !takes_duration {
+                return Err(anyhow::anyhow!("{} does not take a duration", self.tool));
+            }
+            args.extend(["--duration".to_string(), duration.to_string()]);
+        }
+        if let Some(endpoint) = &self.endpoint {
+            if !takes_endpoint {
+                return Err(anyhow::anyhow!("{} does not take an endpoint", self.tool));
+            }
+            args.extend(["--endpoint".to_string(), endpoint.clone()]);
+        }
+        args.extend(self.args.iter().cloned());
+
+        Ok(args)
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let scenario: Scenario = toml::from_str(&fs::read_to_string(&args.scenario)?)?;
+
+    // Validate every step before spending minutes on the first one
+    let commands = scenario
+        .steps
+        .iter()
+        .map(|step| step.command_args())
+        .collect::<Result<Vec<_>>>()?;
+
+    println!("🎬 Scenario: {}", scenario.name);
+    println!("Steps: {}", scenario.steps.len());
+    println!();
+
+    let bin_dir = std::env::current_exe()?
+        .parent()
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow::anyhow!("Cannot locate the tool binaries"))?;
+
+    let started_at = chrono::Utc::now();
+    let mut step_reports = Vec::new();
+    let mut failures = 0;
+
+    for (i, (step, command_args)) in scenario.steps.iter().zip(&commands).enumerate() {
+        println!(
+            "▶️  Step {}/{}: {} {}",
+            i + 1,
+            scenario.steps.len(),
+            step.tool,
+            command_args.join(" ")
+        );
+
+        if args.dry_run {
+            continue;
+        }
+
+        let start = Instant::now();
+        let output = Command::new(bin_dir.join(&step.tool))
+            .args(command_args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .output();
+
+        let report = match output {
+            Ok(output) => {
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                if let Some(path) = &step.output {
+                    fs::write(path, stdout.as_bytes())?;
+                }
+
+                let status = if output.status.success() {
+                    "ok"
+                } else {
+                    failures += 1;
+                    "failed"
+                };
+                println!(
+                    "{} {} in {:.1}s{}",
+                    if output.status.success() {
+                        "✅"
+                    } else {
+                        "❌"
+                    },
+                    status,
+                    start.elapsed().as_secs_f64(),
+                    step.output
+                        .as_ref()
+                        .map(|p| format!(" → {}", p))
+                        .unwrap_or_default()
+                );
+
+                json!({
+                    "tool": step.tool,
+                    "args": command_args,
+                    "status": status,
+                    "exit_code": output.status.code(),
+                    "elapsed_secs": start.elapsed().as_secs_f64(),
+                    "output": step.output,
+                    "result": trailing_json(&stdout)
+                })
+            }
+            Err(e) => {
+                failures += 1;
+                println!("❌ Could not start {}: {}", step.tool, e);
+
+                json!({
+                    "tool": step.tool,
+                    "args": command_args,
+                    "status": "failed",
+                    "error": e.to_string()
+                })
+            }
+        };
+
+        step_reports.push(report);
+        println!();
+    }
+
+    if args.dry_run {
+        return Ok(());
+    }
+
+    let report = json!({
+        "scenario": scenario.name,
+        "started_at": started_at.to_rfc3339(),
+        "finished_at": chrono::Utc::now().to_rfc3339(),
+        "failed_steps": failures,
+        "steps": step_reports
+    });
+    let rendered = serde_json::to_string_pretty(&report)?;
+
+    match &scenario.report {
+        Some(path) => {
+            fs::write(path, rendered)?;
+            println!("📄 Combined report written to {}", path);
+        }
+        None => println!("{}", rendered),
+    }
+
+    if failures > 0 {
+        return Err(anyhow::anyhow!("{} scenario steps failed", failures).into());
+    }
+
+    Ok(())
+}
+
+/// JSON document at the end of a tool's stdout (tools run with `--json`
+/// print a banner first), if any.
+fn trailing_json(stdout: &str) -> Value {
+    let start = if stdout.starts_with('{') {
+        Some(0)
+    } else {
+        stdout.rfind("\n{").map(|i| i + 1)
+    };
+
+    start
+        .and_then(|i| serde_json::from_str(stdout[i..].trim()).ok())
+        .unwrap_or(Value::Null)
+}
@@ -0,0 +1,7 @@
+use clap::Parser;
+use solana_rpc_performance_rust::tools::signature_poll_latency::{self, Args};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    signature_poll_latency::run(Args::parse()).await
+}
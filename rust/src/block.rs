@@ -0,0 +1,268 @@
+//! Helpers for reading `getBlock` / `blockNotification` JSON payloads.
+//!
+//! The transaction list comes back in several shapes depending on the
+//! `transactionDetails` and `maxSupportedTransactionVersion` parameters:
+//!
+//! * `"signatures"`: a top-level `signatures` array and no `transactions`.
+//! * `"full"` / `"accounts"`: a `transactions` array where each entry is
+//!   `{ "transaction": { "signatures": [...], ... }, "meta": ..., "version": ... }`.
+//!   `version` is the string `"legacy"` or the number `0` (address-lookup-table
+//!   transactions), and is absent when no max version was requested.
+//! * Binary encodings (`base64`, `base58`): `transaction` is `[data, encoding]`,
+//!   so signatures can't be read without decoding, but the entry still counts.
+//!
+//! [`SignatureScanner`] walks recent blocks with `transactionDetails:
+//! "signatures"`, the cheapest mode that still lists every transaction.
+
+use crate::rpc::{self, Commitment};
+use anyhow::Result;
+use reqwest::Client;
+use serde_json::{json, Value};
+
+/// Number of transactions in a block, regardless of `transactionDetails` mode.
+///
+/// Returns `None` when the block carries no transaction data at all
+/// (`transactionDetails: "none"`).
+pub fn transaction_count(block: &Value) -> Option<usize> {
+    if let Some(txs) = block.get("transactions").and_then(|t| t.as_array()) {
+        return Some(txs.len());
+    }
+
+    block
+        .get("signatures")
+        .and_then(|s| s.as_array())
+        .map(|sigs| sigs.len())
+}
+
+/// Slot of a `blockNotification`, given the notification's `result`
+/// (`{ "context": { "slot" }, "value": { "slot", "block" } }`).
+///
+/// Prefers `context.slot`, then `value.slot`. Only when both are missing is
+/// it inferred as `block.parentSlot + 1`, which is wrong after a skipped
+/// slot or on a fork; the flag is true in that case.
+pub fn notification_slot(result: &Value) -> Option<(u64, bool)> {
+    let reported = result
+        .pointer("/context/slot")
+        .or_else(|| result.pointer("/value/slot"))
+        .and_then(Value::as_u64);
+    if let Some(slot) = reported {
+        return Some((slot, false));
+    }
+
+    result
+        .pointer("/value/block/parentSlot")
+        .and_then(Value::as_u64)
+        .map(|parent| (parent + 1, true))
+}
+
+/// First (fee-payer) signature of every transaction in a block.
+///
+/// Handles the `"signatures"` detail mode as well as JSON-encoded legacy and
+/// v0 transactions. Binary-encoded transactions are skipped.
+pub fn extract_signatures_from_block(block: &Value) -> Vec<String> {
+    if let Some(txs) = block.get("transactions").and_then(|t| t.as_array()) {
+        return txs
+            .iter()
+            .filter_map(|tx| {
+                tx.get("transaction")
+                    .and_then(|t| t.get("signatures"))
+                    .and_then(|s| s.as_array())
+                    .and_then(|s| s.first())
+                    .and_then(|s| s.as_str())
+                    .map(|s| s.to_string())
+            })
+            .collect();
+    }
+
+    block
+        .get("signatures")
+        .and_then(|s| s.as_array())
+        .map(|sigs| {
+            sigs.iter()
+                .filter_map(|s| s.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Collects the signatures of recently produced blocks.
+///
+/// Each call scans back `limit` slots from the confirmed tip but only fetches
+/// slots newer than the last scan, so a signature is returned once across
+/// calls. Skipped slots (getBlock errors) are passed over.
+#[derive(Debug, Default)]
+pub struct SignatureScanner {
+    scanned_through: Option<u64>,
+}
+
+impl SignatureScanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn scan_recent_transactions(
+        &mut self,
+        client: &Client,
+        rpc_url: &str,
+        limit: u64,
+    ) -> Result<Vec<String>> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        // getBlock does not accept processed commitment
+        let tip = rpc::get_latest_slot(client, rpc_url, Commitment::Confirmed).await?;
+        let mut from = tip.saturating_sub(limit - 1);
+        if let Some(scanned) = self.scanned_through {
+            from = from.max(scanned + 1);
+        }
+
+        let mut signatures = Vec::new();
+        for slot in from..=tip {
+            let params = json!([
+                slot,
+                {
+                    "encoding": "json",
+                    "commitment": Commitment::Confirmed.as_str(),
+                    "maxSupportedTransactionVersion": 0,
+                    "rewards": false,
+                    "transactionDetails": "signatures"
+                }
+            ]);
+            let response = rpc::call(client, rpc_url, "getBlock", params).await?;
+            if let Some(block) = response.get("result").filter(|b| !b.is_null()) {
+                signatures.extend(extract_signatures_from_block(block));
+            }
+        }
+
+        self.scanned_through = Some(tip);
+        Ok(signatures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `getBlock` with `transactionDetails: "full"` and
+    /// `maxSupportedTransactionVersion: 0`: one legacy and one v0 entry.
+    fn full_block() -> Value {
+        json!({
+            "blockhash": "5VfydnLu4XwV2H2dLHPv22JxhLbYJruaM9YTaGY8pU3e",
+            "parentSlot": 299999999,
+            "transactions": [
+                {
+                    "meta": {"err": null, "fee": 5000},
+                    "transaction": {
+                        "message": {
+                            "accountKeys": ["11111111111111111111111111111111"],
+                            "instructions": []
+                        },
+                        "signatures": ["legacySig1"]
+                    },
+                    "version": "legacy"
+                },
+                {
+                    "meta": {"err": null, "fee": 10000},
+                    "transaction": {
+                        "message": {
+                            "accountKeys": ["11111111111111111111111111111111"],
+                            "addressTableLookups": [{
+                                "accountKey": "AddressLookupTab1e1111111111111111111111111",
+                                "readonlyIndexes": [0],
+                                "writableIndexes": []
+                            }],
+                            "instructions": []
+                        },
+                        "signatures": ["v0Sig1", "v0Sig2"]
+                    },
+                    "version": 0
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn counts_legacy_and_v0_transactions() {
+        assert_eq!(transaction_count(&full_block()), Some(2));
+    }
+
+    #[test]
+    fn extracts_fee_payer_signature_of_legacy_and_v0_transactions() {
+        assert_eq!(
+            extract_signatures_from_block(&full_block()),
+            vec!["legacySig1", "v0Sig1"]
+        );
+    }
+
+    #[test]
+    fn extracts_nested_transaction_signatures() {
+        let block = json!({
+            "transactions": [
+                {"transaction": {"signatures": ["sigA", "sigA2"], "message": {}}, "meta": null},
+                {"transaction": {"signatures": ["sigB"], "message": {}}, "meta": null},
+                // Binary encoding: counted, but no readable signature
+                {"transaction": ["AQID", "base64"], "meta": null}
+            ]
+        });
+        assert_eq!(extract_signatures_from_block(&block), vec!["sigA", "sigB"]);
+        assert_eq!(transaction_count(&block), Some(3));
+    }
+
+    #[test]
+    fn extracts_signatures_detail_mode() {
+        let block = json!({"signatures": ["sigA", "sigB", "sigC"]});
+        assert_eq!(
+            extract_signatures_from_block(&block),
+            vec!["sigA", "sigB", "sigC"]
+        );
+        assert_eq!(transaction_count(&block), Some(3));
+    }
+
+    /// `params.result` of a `blockNotification` as providers send it.
+    fn block_notification() -> Value {
+        json!({
+            "context": { "slot": 112301554 },
+            "value": {
+                "slot": 112301554,
+                "block": {
+                    "previousBlockhash": "GJp125YAN4ufCSUvZJVdCyWQJ7RPWMmwxoyUQySydZA",
+                    "blockhash": "6ojMHjctdqfB55JDpEpqfHnP96fiaHEcvzEQ2NNcxzHP",
+                    "parentSlot": 112301553,
+                    "transactions": [],
+                    "blockTime": 1639926816,
+                    "blockHeight": 101210751
+                },
+                "err": null
+            }
+        })
+    }
+
+    #[test]
+    fn notification_slot_prefers_context_slot() {
+        let mut result = block_notification();
+        result["value"]["slot"] = json!(112301999);
+        assert_eq!(notification_slot(&result), Some((112301554, false)));
+    }
+
+    #[test]
+    fn notification_slot_falls_back_to_value_slot() {
+        let mut result = block_notification();
+        result.as_object_mut().unwrap().remove("context");
+        assert_eq!(notification_slot(&result), Some((112301554, false)));
+    }
+
+    #[test]
+    fn notification_slot_infers_parent_slot_plus_one() {
+        let mut result = block_notification();
+        result.as_object_mut().unwrap().remove("context");
+        result["value"].as_object_mut().unwrap().remove("slot");
+        assert_eq!(notification_slot(&result), Some((112301554, true)));
+
+        result["value"]["block"]
+            .as_object_mut()
+            .unwrap()
+            .remove("parentSlot");
+        assert_eq!(notification_slot(&result), None);
+    }
+}
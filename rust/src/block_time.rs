@@ -0,0 +1,132 @@
+//! Sanity checks on `blockTime` before it becomes a latency sample.
+//!
+//! A provider serving a stale, cached or zero `blockTime` turns into absurd
+//! latencies. [`validate_block_time`] rejects such values with a reason, and
+//! [`BlockTimeRejections`] counts them so a summary can show what was
+//! dropped and why.
+
+use serde::Serialize;
+use std::fmt;
+
+/// How far a block time may be ahead of the local clock. Block times are
+/// whole seconds rounded down, so a correct one is never ahead; this only
+/// absorbs clock skew.
+pub const FUTURE_TOLERANCE_MS: i64 = 1_000;
+/// Older block times are stale: a live stream or poll sees a block within
+/// seconds, not minutes.
+pub const MAX_AGE_MS: i64 = 120_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectReason {
+    /// Zero or negative, i.e. not set.
+    Zero,
+    /// Ahead of the local clock by more than [`FUTURE_TOLERANCE_MS`].
+    Future,
+    /// Behind the local clock by more than [`MAX_AGE_MS`].
+    Stale,
+}
+
+impl RejectReason {
+    pub fn name(self) -> &'static str {
+        match self {
+            RejectReason::Zero => "zero",
+            RejectReason::Future => "future",
+            RejectReason::Stale => "stale",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct InvalidBlockTime {
+    pub slot: u64,
+    pub block_time: i64,
+    pub reason: RejectReason,
+}
+
+impl fmt::Display for InvalidBlockTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "slot {}: {} block time {}",
+            self.slot,
+            self.reason.name(),
+            self.block_time
+        )
+    }
+}
+
+impl std::error::Error for InvalidBlockTime {}
+
+/// Check a block time (unix seconds) against `now_ms`, the local receive
+/// time in unix ms.
+pub fn validate_block_time(
+    block_time: i64,
+    slot: u64,
+    now_ms: i64,
+) -> Result<(), InvalidBlockTime> {
+    let latency_ms = now_ms - block_time * 1000;
+    let reason = if block_time <= 0 {
+        RejectReason::Zero
+    } else if latency_ms < -FUTURE_TOLERANCE_MS {
+        RejectReason::Future
+    } else if latency_ms > MAX_AGE_MS {
+        RejectReason::Stale
+    } else {
+        return Ok(());
+    };
+
+    Err(InvalidBlockTime {
+        slot,
+        block_time,
+        reason,
+    })
+}
+
+/// Rejected block times by reason.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct BlockTimeRejections {
+    pub zero: u64,
+    pub future: u64,
+    pub stale: u64,
+}
+
+impl BlockTimeRejections {
+    /// Validate and count a rejection; true when the block time is usable.
+    pub fn accept(&mut self, block_time: i64, slot: u64, now_ms: i64) -> bool {
+        match validate_block_time(block_time, slot, now_ms) {
+            Ok(()) => true,
+            Err(invalid) => {
+                self.record(&invalid);
+                false
+            }
+        }
+    }
+
+    pub fn record(&mut self, invalid: &InvalidBlockTime) {
+        match invalid.reason {
+            RejectReason::Zero => self.zero += 1,
+            RejectReason::Future => self.future += 1,
+            RejectReason::Stale => self.stale += 1,
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.zero + self.future + self.stale
+    }
+
+    pub fn print(&self) {
+        if self.total() == 0 {
+            return;
+        }
+        println!(
+            "🕳️  {} block times rejected: {} zero, {} more than {}ms in the future, {} more than {}s old",
+            self.total(),
+            self.zero,
+            self.future,
+            FUTURE_TOLERANCE_MS,
+            self.stale,
+            MAX_AGE_MS / 1000
+        );
+    }
+}
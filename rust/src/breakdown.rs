@@ -0,0 +1,214 @@
+//! Split of end-to-end block latency into upstream and delivery legs.
+//!
+//! With the gRPC `created_at` timestamp the blended `received - block_time`
+//! number separates into:
+//!
+//! * upstream: `created_at - block_time * 1000` (validator → provider)
+//! * delivery: `received - created_at` (provider → this host)
+//! * total:    `received - block_time * 1000`
+//!
+//! Only samples carrying all three timestamps are recorded, so the three
+//! distributions describe the same blocks and upstream + delivery = total.
+//!
+//! Delivery is kept signed. A negative value means `created_at` is ahead of
+//! the local clock, which cannot happen with synchronised clocks; see
+//! [`ClockSkew`].
+
+use crate::stats::{self, LatencyStats};
+use serde::Serialize;
+
+/// Share of negative delivery samples above which the provider clock is
+/// reported as ahead of ours rather than as jitter.
+const CLOCK_SKEW_NEGATIVE_SHARE: f64 = 0.1;
+
+/// `created_at` consistently ahead of local receive time.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClockSkew {
+    pub negative: usize,
+    pub count: usize,
+    /// How far the provider clock is at least ahead of ours: the negated
+    /// P1 of `received - created_at`, since true delivery can't be negative.
+    pub estimated_offset_ms: i64,
+}
+
+impl ClockSkew {
+    /// Inspect signed `received - created_at` samples.
+    pub fn detect(delivery: &[i64]) -> Option<Self> {
+        let negative = delivery.iter().filter(|&&d| d < 0).count();
+        if delivery.is_empty()
+            || (negative as f64 / delivery.len() as f64) <= CLOCK_SKEW_NEGATIVE_SHARE
+        {
+            return None;
+        }
+
+        let mut sorted = delivery.to_vec();
+        sorted.sort();
+
+        Some(ClockSkew {
+            negative,
+            count: delivery.len(),
+            estimated_offset_ms: -stats::percentile(&sorted, 0.01)?,
+        })
+    }
+
+    pub fn print(&self) {
+        println!(
+            "⚠️  Clock skew: created_at was ahead of local receive time for {}/{} blocks",
+            self.negative, self.count
+        );
+        println!(
+            "   Provider clock is ~{}ms ahead of this host; delivery latency is understated by at least that much",
+            self.estimated_offset_ms
+        );
+    }
+}
+
+/// Samples where `block_time` is later than our receive time, by cause.
+///
+/// `block_time` is floored to the second, so a block stamped after we
+/// received it is a clock problem somewhere. The provider's `created_at`
+/// tells whose: if the provider also saw the block before its `block_time`,
+/// the validator's clock is ahead; otherwise ours is behind.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NegativeLatency {
+    /// `block_time` ahead of `created_at` too: future-dated by the validator.
+    pub future_block_time: u64,
+    /// `created_at` at or after `block_time`: the local clock is behind.
+    pub local_clock_behind: u64,
+    /// No `created_at` to tell the two apart.
+    pub unattributed: u64,
+}
+
+impl NegativeLatency {
+    /// `block_time` is unix seconds, `created_at_ms` and `received_ms` unix ms.
+    pub fn record(&mut self, block_time: i64, created_at_ms: Option<i64>, received_ms: i64) {
+        let block_time_ms = block_time * 1000;
+        if block_time_ms <= received_ms {
+            return;
+        }
+
+        match created_at_ms {
+            Some(created_at_ms) if created_at_ms < block_time_ms => self.future_block_time += 1,
+            Some(_) => self.local_clock_behind += 1,
+            None => self.unattributed += 1,
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.future_block_time + self.local_clock_behind + self.unattributed
+    }
+
+    pub fn print(&self) {
+        println!(
+            "⏪ Blocks ahead of wall clock (block_time > received): {}",
+            self.total()
+        );
+        println!(
+            "   • Future-dated block_time (validator clock ahead): {}",
+            self.future_block_time
+        );
+        println!(
+            "   • Local clock behind (created_at >= block_time): {}",
+            self.local_clock_behind
+        );
+        if self.unattributed > 0 {
+            println!("   • Unattributed (no created_at): {}", self.unattributed);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BreakdownSummary {
+    pub upstream: Option<LatencyStats>,
+    pub delivery: Option<LatencyStats>,
+    pub total: Option<LatencyStats>,
+    /// Blocks skipped because `block_time` or `created_at` was missing.
+    pub incomplete: u64,
+    pub clock_skew: Option<ClockSkew>,
+}
+
+#[derive(Debug, Default)]
+pub struct LatencyBreakdown {
+    upstream: Vec<i64>,
+    delivery: Vec<i64>,
+    total: Vec<i64>,
+    incomplete: u64,
+}
+
+impl LatencyBreakdown {
+    /// `block_time` is unix seconds, `created_at_ms` and `received_ms` unix ms.
+    pub fn record(
+        &mut self,
+        block_time: Option<i64>,
+        created_at_ms: Option<i64>,
+        received_ms: i64,
+    ) {
+        match (block_time, created_at_ms) {
+            (Some(block_time), Some(created_at_ms)) => {
+                let block_time_ms = block_time * 1000;
+                self.upstream.push(created_at_ms - block_time_ms);
+                self.delivery.push(received_ms - created_at_ms);
+                self.total.push(received_ms - block_time_ms);
+            }
+            _ => self.incomplete += 1,
+        }
+    }
+
+    pub fn summary(&self) -> BreakdownSummary {
+        BreakdownSummary {
+            upstream: LatencyStats::from_samples(&self.upstream),
+            delivery: LatencyStats::from_samples(&self.delivery),
+            total: LatencyStats::from_samples(&self.total),
+            incomplete: self.incomplete,
+            clock_skew: ClockSkew::detect(&self.delivery),
+        }
+    }
+
+    pub fn print(&self, source: &str) {
+        let summary = self.summary();
+
+        println!();
+        println!(
+            "🔬 Latency Breakdown ({}, {} blocks)",
+            source,
+            self.total.len()
+        );
+        println!("{}", "-".repeat(60));
+
+        if self.total.is_empty() {
+            println!("❌ No blocks carried both block_time and created_at");
+        } else {
+            println!(
+                "{:<9} | {:>8} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6}",
+                "Leg", "Avg", "Min", "P50", "P90", "P99", "Max"
+            );
+            for (leg, dist) in [
+                ("Upstream", &summary.upstream),
+                ("Delivery", &summary.delivery),
+                ("Total", &summary.total),
+            ] {
+                if let Some(d) = dist {
+                    println!(
+                        "{:<9} | {:>6.1}ms | {:>4}ms | {:>4}ms | {:>4}ms | {:>4}ms | {:>4}ms",
+                        leg, d.avg_ms, d.min_ms, d.p50_ms, d.p90_ms, d.p99_ms, d.max_ms
+                    );
+                }
+            }
+        }
+
+        if let Some(skew) = &summary.clock_skew {
+            skew.print();
+        }
+        if self.incomplete > 0 {
+            println!(
+                "⚠️  {} blocks without block_time or created_at were excluded",
+                self.incomplete
+            );
+        }
+        println!(
+            "• Upstream = created_at - block_time (validator → provider, 1s block_time resolution)"
+        );
+        println!("• Delivery = received - created_at (provider → this host)");
+        println!("• Total    = received - block_time");
+    }
+}
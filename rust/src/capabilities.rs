@@ -0,0 +1,450 @@
+//! Startup probe of what an RPC provider actually supports.
+//!
+//! Providers differ: most reject `getBlock` below confirmed, some reject
+//! `processed` everywhere, some do not accept
+//! `maxSupportedTransactionVersion` and some have no WebSocket
+//! `blockSubscribe`. Mid-run these only show up as failed polls or parse
+//! errors that look like missing blocks. [`probe_provider`] calls each
+//! method the tools depend on once per commitment, and
+//! [`ProviderCapabilities::require`] turns a missing one into a clear error
+//! before anything is timed.
+
+use crate::rpc::{self, Commitment};
+use anyhow::{anyhow, Result};
+use futures_util::{SinkExt, StreamExt};
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+use tracing::warn;
+
+/// Bound on each probe call, so an unresponsive provider delays startup by
+/// seconds rather than the client timeout.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// `getBlock` and `getBlockTime` are probed this many slots behind the tip,
+/// far enough back for the block to be available on any node.
+const PROBE_SLOT_LAG: u64 = 10;
+/// JSON-RPC errors for a slot without a block (cleaned up, not available,
+/// skipped): the method works, that slot just has nothing to return.
+const NO_BLOCK_ERROR_CODES: [i64; 4] = [-32001, -32004, -32007, -32009];
+
+/// Whether one method works at one commitment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "reason", rename_all = "snake_case")]
+pub enum Support {
+    Supported,
+    /// The provider answered with a JSON-RPC error or an unusable result.
+    Unsupported(String),
+    /// The probe itself failed (connection, timeout, HTTP 429 / 5xx), so
+    /// nothing is known either way.
+    Unknown(String),
+}
+
+impl Support {
+    fn label(&self) -> &'static str {
+        match self {
+            Support::Supported => "yes",
+            Support::Unsupported(_) => "NO",
+            Support::Unknown(_) => "unknown",
+        }
+    }
+
+    /// Not known to be unsupported.
+    pub fn usable(&self) -> bool {
+        !matches!(self, Support::Unsupported(_))
+    }
+}
+
+/// A method probed at each commitment.
+#[derive(Debug, Clone, Serialize)]
+pub struct PerCommitment {
+    pub processed: Support,
+    pub confirmed: Support,
+    pub finalized: Support,
+}
+
+impl PerCommitment {
+    fn all(support: Support) -> Self {
+        PerCommitment {
+            processed: support.clone(),
+            confirmed: support.clone(),
+            finalized: support,
+        }
+    }
+
+    pub fn get(&self, commitment: Commitment) -> &Support {
+        match commitment {
+            Commitment::Processed => &self.processed,
+            Commitment::Confirmed => &self.confirmed,
+            Commitment::Finalized => &self.finalized,
+        }
+    }
+
+    /// The weakest commitment not known to be unsupported.
+    pub fn weakest_usable(&self) -> Option<Commitment> {
+        Commitment::ALL
+            .into_iter()
+            .find(|&commitment| self.get(commitment).usable())
+    }
+}
+
+/// A method at the commitment a measurement needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    GetSlot(Commitment),
+    GetBlockTime,
+    /// With `maxSupportedTransactionVersion: 0`.
+    GetBlock(Commitment),
+    /// Over the provider's WebSocket endpoint.
+    BlockSubscribe(Commitment),
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Capability::GetSlot(commitment) => write!(f, "getSlot at {}", commitment),
+            Capability::GetBlockTime => f.write_str("getBlockTime"),
+            Capability::GetBlock(commitment) => write!(
+                f,
+                "getBlock at {} with maxSupportedTransactionVersion",
+                commitment
+            ),
+            Capability::BlockSubscribe(commitment) => {
+                write!(f, "WebSocket blockSubscribe at {}", commitment)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderCapabilities {
+    pub get_slot: PerCommitment,
+    pub get_block_time: Support,
+    pub get_block: PerCommitment,
+    pub block_subscribe: PerCommitment,
+}
+
+impl ProviderCapabilities {
+    pub fn support(&self, capability: Capability) -> &Support {
+        match capability {
+            Capability::GetSlot(commitment) => self.get_slot.get(commitment),
+            Capability::GetBlockTime => &self.get_block_time,
+            Capability::GetBlock(commitment) => self.get_block.get(commitment),
+            Capability::BlockSubscribe(commitment) => self.block_subscribe.get(commitment),
+        }
+    }
+
+    /// Fail with every unsupported capability in `needed`. One the probe
+    /// could not check is only warned about, so a flaky probe does not
+    /// block a run.
+    pub fn require(&self, needed: &[Capability]) -> Result<()> {
+        let mut missing = Vec::new();
+        for &capability in needed {
+            match self.support(capability) {
+                Support::Supported => {}
+                Support::Unsupported(reason) => {
+                    missing.push(format!("{} ({})", capability, reason))
+                }
+                Support::Unknown(reason) => {
+                    warn!("Could not probe {}: {}; running anyway", capability, reason)
+                }
+            }
+        }
+
+        if missing.is_empty() {
+            return Ok(());
+        }
+        Err(anyhow!(
+            "The provider does not support {}, which this method needs (--no-capability-probe skips this check)",
+            missing.join("; ")
+        ))
+    }
+
+    pub fn print(&self) {
+        let rows = [
+            ("getSlot", &self.get_slot),
+            ("getBlock", &self.get_block),
+            ("blockSubscribe", &self.block_subscribe),
+        ];
+
+        println!("🔎 Provider capabilities");
+        println!("{}", "-".repeat(50));
+        println!("Method         | processed | confirmed | finalized");
+        for (method, support) in rows {
+            println!(
+                "{:<14} | {:<9} | {:<9} | {}",
+                method,
+                support.processed.label(),
+                support.confirmed.label(),
+                support.finalized.label()
+            );
+        }
+        println!("{:<14} | {}", "getBlockTime", self.get_block_time.label());
+
+        let mut notes = Vec::new();
+        for (method, support) in rows {
+            for commitment in Commitment::ALL {
+                notes.push((
+                    format!("{} at {}", method, commitment),
+                    support.get(commitment),
+                ));
+            }
+        }
+        notes.push(("getBlockTime".to_string(), &self.get_block_time));
+        for (what, support) in notes {
+            match support {
+                Support::Supported => {}
+                Support::Unsupported(reason) => println!("❌ {}: {}", what, reason),
+                Support::Unknown(reason) => println!("❔ {}: not probed ({})", what, reason),
+            }
+        }
+        println!();
+    }
+}
+
+/// Probe the HTTP methods on `rpc_url` and `blockSubscribe` on its
+/// WebSocket counterpart.
+pub async fn probe_provider(client: &Client, rpc_url: &str) -> ProviderCapabilities {
+    probe(client, rpc_url, &rpc::websocket_url(rpc_url, None)).await
+}
+
+/// [`probe_provider`] with the WebSocket endpoint given separately.
+pub async fn probe(client: &Client, rpc_url: &str, websocket_url: &str) -> ProviderCapabilities {
+    let mut slots = HashMap::new();
+    let mut get_slot = Vec::new();
+    for commitment in Commitment::ALL {
+        let (support, response) = probe_call(
+            client,
+            rpc_url,
+            "getSlot",
+            json!([{"commitment": commitment.as_str()}]),
+            Value::is_u64,
+        )
+        .await;
+        if let Some(slot) = response.get("result").and_then(Value::as_u64) {
+            slots.insert(commitment, slot);
+        }
+        get_slot.push(support);
+    }
+    let [processed, confirmed, finalized] = get_slot.try_into().expect("one per commitment");
+    let get_slot = PerCommitment {
+        processed,
+        confirmed,
+        finalized,
+    };
+
+    // Any commitment's tip will do to pick a slot that has a block
+    let tip = Commitment::ALL
+        .into_iter()
+        .find_map(|commitment| slots.get(&commitment).copied());
+    let (get_block_time, get_block) = match tip {
+        Some(tip) => {
+            let slot = tip.saturating_sub(PROBE_SLOT_LAG);
+            let (get_block_time, _) =
+                probe_call(client, rpc_url, "getBlockTime", json!([slot]), |result| {
+                    result.is_i64() || result.is_null()
+                })
+                .await;
+            (get_block_time, probe_get_block(client, rpc_url, slot).await)
+        }
+        None => {
+            let no_slot = Support::Unknown("no slot to probe, getSlot failed".to_string());
+            (no_slot.clone(), PerCommitment::all(no_slot))
+        }
+    };
+
+    ProviderCapabilities {
+        get_slot,
+        get_block_time,
+        get_block,
+        block_subscribe: probe_block_subscribe(websocket_url).await,
+    }
+}
+
+async fn probe_get_block(client: &Client, rpc_url: &str, slot: u64) -> PerCommitment {
+    let mut supports = Vec::new();
+    for commitment in Commitment::ALL {
+        let params = json!([
+            slot,
+            {
+                "encoding": "json",
+                "commitment": commitment.as_str(),
+                "maxSupportedTransactionVersion": 0,
+                "rewards": false,
+                "transactionDetails": "none"
+            }
+        ]);
+        let (support, _) = probe_call(client, rpc_url, "getBlock", params, valid_block).await;
+        supports.push(support);
+    }
+    let [processed, confirmed, finalized] = supports.try_into().expect("one per commitment");
+    PerCommitment {
+        processed,
+        confirmed,
+        finalized,
+    }
+}
+
+/// A `getBlock` result: the block, or null for a slot without one.
+fn valid_block(result: &Value) -> bool {
+    result.is_null() || result.get("blockhash").is_some()
+}
+
+/// One bounded call, classified; the response envelope is `Null` when the
+/// call itself failed.
+async fn probe_call(
+    client: &Client,
+    rpc_url: &str,
+    method: &str,
+    params: Value,
+    valid: fn(&Value) -> bool,
+) -> (Support, Value) {
+    match rpc::with_timeout(PROBE_TIMEOUT, rpc::call(client, rpc_url, method, params)).await {
+        Ok(response) => (classify(&response, valid), response),
+        Err(e) => (Support::Unknown(e.to_string()), Value::Null),
+    }
+}
+
+/// Support shown by a JSON-RPC response envelope whose `result` must pass
+/// `valid`.
+fn classify(response: &Value, valid: fn(&Value) -> bool) -> Support {
+    if let Some(error) = response.get("error") {
+        let code = error.get("code").and_then(Value::as_i64);
+        if code.is_some_and(|code| NO_BLOCK_ERROR_CODES.contains(&code)) {
+            return Support::Supported;
+        }
+        let message = error
+            .get("message")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| error.to_string());
+        return Support::Unsupported(message);
+    }
+
+    match response.get("result") {
+        Some(result) if valid(result) => Support::Supported,
+        Some(_) => Support::Unsupported("unexpected result shape".to_string()),
+        None => Support::Unsupported("response has neither result nor error".to_string()),
+    }
+}
+
+async fn probe_block_subscribe(websocket_url: &str) -> PerCommitment {
+    match rpc::with_timeout(PROBE_TIMEOUT, block_subscribe_answers(websocket_url)).await {
+        Ok(mut answers) => {
+            let mut take = |commitment: Commitment| {
+                answers
+                    .remove(&commitment)
+                    .unwrap_or_else(|| Support::Unknown("no answer".to_string()))
+            };
+            PerCommitment {
+                processed: take(Commitment::Processed),
+                confirmed: take(Commitment::Confirmed),
+                finalized: take(Commitment::Finalized),
+            }
+        }
+        Err(e) => PerCommitment::all(Support::Unknown(e.to_string())),
+    }
+}
+
+/// Send one `blockSubscribe` per commitment on a single connection and
+/// collect the answers. Notifications that arrive meanwhile are skipped.
+async fn block_subscribe_answers(websocket_url: &str) -> Result<HashMap<Commitment, Support>> {
+    let (mut ws_stream, _) = connect_async(websocket_url).await?;
+
+    for (id, commitment) in Commitment::ALL.into_iter().enumerate() {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "blockSubscribe",
+            "params": [
+                "all",
+                {
+                    "commitment": commitment.as_str(),
+                    "encoding": "json",
+                    "transactionDetails": "none",
+                    "rewards": false
+                }
+            ]
+        });
+        ws_stream.send(Message::Text(request.to_string())).await?;
+    }
+
+    let mut answers = HashMap::new();
+    while answers.len() < Commitment::ALL.len() {
+        let text = match ws_stream.next().await {
+            Some(Ok(Message::Text(text))) => text,
+            Some(Ok(Message::Close(_))) | None => {
+                return Err(anyhow!("WebSocket closed before answering blockSubscribe"))
+            }
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => return Err(e.into()),
+        };
+        let Ok(value) = serde_json::from_str::<Value>(&text) else {
+            continue;
+        };
+        let Some(commitment) = value
+            .get("id")
+            .and_then(Value::as_u64)
+            .and_then(|id| Commitment::ALL.get(id as usize).copied())
+        else {
+            continue;
+        };
+        answers.insert(commitment, classify(&value, Value::is_u64));
+    }
+
+    let _ = ws_stream.close(None).await;
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_envelope_is_unsupported_with_its_message() {
+        let response = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": {"code": -32602, "message": "Invalid param: processed not supported"}
+        });
+        assert_eq!(
+            classify(&response, valid_block),
+            Support::Unsupported("Invalid param: processed not supported".to_string())
+        );
+    }
+
+    #[test]
+    fn no_block_error_codes_mean_supported() {
+        for code in NO_BLOCK_ERROR_CODES {
+            let response = json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": {"code": code, "message": "Block not available"}
+            });
+            assert_eq!(classify(&response, valid_block), Support::Supported);
+        }
+    }
+
+    #[test]
+    fn null_get_block_result_is_supported() {
+        let response = json!({"jsonrpc": "2.0", "id": 1, "result": null});
+        assert_eq!(classify(&response, valid_block), Support::Supported);
+    }
+
+    #[test]
+    fn malformed_result_is_unsupported() {
+        let response = json!({"jsonrpc": "2.0", "id": 1, "result": {"slot": 300_000_000}});
+        assert_eq!(
+            classify(&response, valid_block),
+            Support::Unsupported("unexpected result shape".to_string())
+        );
+        let response = json!({"jsonrpc": "2.0", "id": 1});
+        assert_eq!(
+            classify(&response, Value::is_u64),
+            Support::Unsupported("response has neither result nor error".to_string())
+        );
+    }
+}
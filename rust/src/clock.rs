@@ -0,0 +1,248 @@
+//! Wall-clock source for the absolute latency math.
+//!
+//! Latency is `received - block_time`, so any error in the local clock lands
+//! directly in the result. By default the tools read CLOCK_REALTIME
+//! (`SystemTime::now`). Hosts with a PTP/GPS-disciplined reference can pass
+//! `--time-source file:<path>` instead, where the file holds the current
+//! offset of that reference from CLOCK_REALTIME in milliseconds:
+//!
+//! ```text
+//! # reference - CLOCK_REALTIME, ms (fractional allowed)
+//! -0.183
+//! ```
+//!
+//! Blank lines and lines starting with `#` are ignored. The file is re-read
+//! at most once per second, so whatever keeps it current (e.g. a script
+//! around `pmc` or `chronyc tracking`) can rewrite it during a run. If a
+//! refresh fails, the last good offset stays in use.
+//!
+//! Hosts without such a reference can pass `--time-source ntp[:server]`: one
+//! SNTP query at startup measures the offset from that server, and it is
+//! applied for the rest of the run. If the query fails the run continues on
+//! uncorrected CLOCK_REALTIME with a warning, since a skewed clock is still
+//! better than no measurement.
+//!
+//! Comparing two arrivals with each other needs no absolute time at all, and
+//! CLOCK_REALTIME can step backwards when NTP adjusts it. [`monotonic_ms`]
+//! reads the monotonic clock instead, for ordering and diffing arrivals from
+//! different sources.
+
+use anyhow::{anyhow, Result};
+use std::fmt;
+use std::net::UdpSocket;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+pub const DEFAULT_NTP_SERVER: &str = "pool.ntp.org";
+const NTP_PORT: u16 = 123;
+const NTP_TIMEOUT: Duration = Duration::from_secs(2);
+/// Seconds between the NTP epoch (1900) and the unix epoch.
+const NTP_UNIX_OFFSET_SECS: f64 = 2_208_988_800.0;
+
+#[derive(Debug, Clone, Default)]
+pub enum TimeSource {
+    #[default]
+    ClockRealtime,
+    File(PathBuf),
+    /// `host` or `host:port` of an NTP server.
+    Ntp(String),
+}
+
+impl FromStr for TimeSource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "clock_realtime" => Ok(TimeSource::ClockRealtime),
+            "ntp" => Ok(TimeSource::Ntp(DEFAULT_NTP_SERVER.to_string())),
+            _ if s.starts_with("ntp:") => s
+                .strip_prefix("ntp:")
+                .filter(|server| !server.is_empty())
+                .map(|server| TimeSource::Ntp(server.to_string()))
+                .ok_or_else(|| anyhow!("Invalid time source '{}', expected ntp:<server>", s)),
+            _ => s
+                .strip_prefix("file:")
+                .filter(|path| !path.is_empty())
+                .map(|path| TimeSource::File(PathBuf::from(path)))
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Invalid time source '{}', expected clock_realtime, ntp[:server] or file:<path>",
+                        s
+                    )
+                }),
+        }
+    }
+}
+
+impl fmt::Display for TimeSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeSource::ClockRealtime => write!(f, "clock_realtime"),
+            TimeSource::File(path) => write!(f, "file:{}", path.display()),
+            TimeSource::Ntp(server) => write!(f, "ntp:{}", server),
+        }
+    }
+}
+
+struct FileOffset {
+    path: PathBuf,
+    offset_ms: f64,
+    read_at: Instant,
+}
+
+static FILE_OFFSET: OnceLock<Mutex<FileOffset>> = OnceLock::new();
+static NTP_OFFSET: OnceLock<f64> = OnceLock::new();
+static MONOTONIC_START: OnceLock<Instant> = OnceLock::new();
+
+/// Select the time source for this process.
+///
+/// A file source is read once up front, so a missing or malformed file fails
+/// the run instead of silently falling back to CLOCK_REALTIME. An NTP source
+/// that cannot be reached only warns.
+pub fn init(source: &TimeSource) -> Result<()> {
+    let path = match source {
+        TimeSource::ClockRealtime => return Ok(()),
+        TimeSource::File(path) => path,
+        TimeSource::Ntp(server) => {
+            match query_ntp_offset(server) {
+                Ok(offset_ms) => {
+                    // stderr, so JSON on stdout (--json, --jsonl) stays parseable
+                    eprintln!(
+                        "🕰️  NTP offset from {}: {:+.1}ms (server - local clock, applied to every timestamp)",
+                        server, offset_ms
+                    );
+                    NTP_OFFSET
+                        .set(offset_ms)
+                        .map_err(|_| anyhow!("Time source already initialised"))?;
+                }
+                Err(e) => eprintln!(
+                    "⚠️  NTP query to {} failed ({}); using uncorrected CLOCK_REALTIME",
+                    server, e
+                ),
+            }
+            return Ok(());
+        }
+    };
+
+    let offset_ms = read_offset(path)?;
+    FILE_OFFSET
+        .set(Mutex::new(FileOffset {
+            path: path.clone(),
+            offset_ms,
+            read_at: Instant::now(),
+        }))
+        .map_err(|_| anyhow!("Time source already initialised"))
+}
+
+/// Current unix time in milliseconds from the selected source.
+pub fn now_ms() -> i64 {
+    let realtime_ms = realtime_ms();
+
+    match offset_ms() {
+        Some(offset) => (realtime_ms + offset) as i64,
+        None => realtime_ms as i64,
+    }
+}
+
+/// Milliseconds on the monotonic clock since its first read in this
+/// process. Never jumps, so only differences between two readings mean
+/// anything; use [`now_ms`] for latency against a block time.
+pub fn monotonic_ms() -> i64 {
+    MONOTONIC_START
+        .get_or_init(Instant::now)
+        .elapsed()
+        .as_millis() as i64
+}
+
+/// Offset currently applied on top of CLOCK_REALTIME, if a file or NTP
+/// source is active. Refreshes the file when the last read is stale.
+pub fn offset_ms() -> Option<f64> {
+    if let Some(&offset) = NTP_OFFSET.get() {
+        return Some(offset);
+    }
+
+    let mut state = FILE_OFFSET.get()?.lock().unwrap();
+
+    if state.read_at.elapsed() >= REFRESH_INTERVAL {
+        if let Ok(offset) = read_offset(&state.path) {
+            state.offset_ms = offset;
+        }
+        state.read_at = Instant::now();
+    }
+
+    Some(state.offset_ms)
+}
+
+fn read_offset(path: &Path) -> Result<f64> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Cannot read time source {}: {}", path.display(), e))?;
+
+    let value = content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .ok_or_else(|| anyhow!("Time source {} has no offset line", path.display()))?;
+
+    value
+        .parse::<f64>()
+        .map_err(|e| anyhow!("Invalid offset '{}' in {}: {}", value, path.display(), e))
+}
+
+/// One SNTP exchange; returns the server's offset from CLOCK_REALTIME in ms
+/// using the usual `((t2 - t1) + (t3 - t4)) / 2`, which cancels a symmetric
+/// network delay.
+fn query_ntp_offset(server: &str) -> Result<f64> {
+    let address = if server.contains(':') {
+        server.to_string()
+    } else {
+        format!("{}:{}", server, NTP_PORT)
+    };
+
+    let socket = UdpSocket::bind("0.0.0.0:0")?;
+    socket.set_read_timeout(Some(NTP_TIMEOUT))?;
+    socket.connect(&address)?;
+
+    // LI 0, version 4, mode 3 (client)
+    let mut packet = [0u8; 48];
+    packet[0] = 0x23;
+
+    let t1 = realtime_ms();
+    socket.send(&packet)?;
+    let received = socket.recv(&mut packet)?;
+    let t4 = realtime_ms();
+
+    if received < 48 {
+        return Err(anyhow!("short NTP response ({} bytes)", received));
+    }
+    if packet[0] & 0x07 != 4 {
+        return Err(anyhow!("not an NTP server response"));
+    }
+
+    let t2 = ntp_timestamp_ms(&packet[32..40]);
+    let t3 = ntp_timestamp_ms(&packet[40..48]);
+    if t3 <= 0.0 {
+        return Err(anyhow!(
+            "NTP server sent no transmit time (unsynchronised?)"
+        ));
+    }
+
+    Ok(((t2 - t1) + (t3 - t4)) / 2.0)
+}
+
+/// 64-bit NTP timestamp (32.32 fixed point since 1900) as unix ms.
+fn ntp_timestamp_ms(bytes: &[u8]) -> f64 {
+    let seconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64;
+    let fraction = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as f64;
+
+    (seconds - NTP_UNIX_OFFSET_SECS + fraction / 4_294_967_296.0) * 1000.0
+}
+
+fn realtime_ms() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64() * 1000.0)
+        .unwrap_or(0.0)
+}
@@ -0,0 +1,255 @@
+//! Per-provider latency side by side, written as a CSV or JSON report.
+//!
+//! Providers are ranked by [`composite_score`] rather than by average, so
+//! one that is fast but drops blocks does not come out on top.
+
+use crate::report::SCHEMA_VERSION;
+use crate::stats::LatencyStats;
+use anyhow::Result;
+use serde::Serialize;
+use std::io::Write;
+use std::path::Path;
+
+/// One provider's row of the comparison.
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedLatencyStats {
+    /// [`SCHEMA_VERSION`], on every row so a lone row is self-describing.
+    pub schema_version: u32,
+    pub provider: String,
+    pub samples: usize,
+    pub avg_ms: f64,
+    pub min_ms: i64,
+    pub max_ms: i64,
+    pub p50_ms: i64,
+    pub p95_ms: i64,
+    /// Share of the expected blocks this provider delivered, `0.0..=1.0`.
+    pub success_rate: f64,
+    /// [`composite_score`], `0..=100`, higher is better.
+    pub score: f64,
+}
+
+/// Latency at or above which the median and P95 terms of
+/// [`composite_score`] contribute nothing.
+pub const SCORE_LATENCY_CEILING_MS: f64 = 5000.0;
+
+/// Relative weights of the [`composite_score`] terms, parsed from
+/// `median=0.4,p95=0.3,success=0.3`. Terms left out weigh zero.
+#[derive(Debug, Clone, Copy)]
+pub struct ScoreWeights {
+    pub median: f64,
+    pub p95: f64,
+    pub success: f64,
+}
+
+impl std::str::FromStr for ScoreWeights {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut weights = ScoreWeights {
+            median: 0.0,
+            p95: 0.0,
+            success: 0.0,
+        };
+        for term in s.split(',') {
+            let (name, value) = term.split_once('=').ok_or_else(|| {
+                anyhow::anyhow!("Invalid score weight '{}', expected NAME=WEIGHT", term)
+            })?;
+            let value: f64 = value
+                .trim()
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Invalid score weight '{}': {}", term, e))?;
+            if !value.is_finite() || value < 0.0 {
+                return Err(anyhow::anyhow!(
+                    "Score weights must not be negative, got '{}'",
+                    term
+                ));
+            }
+            match name.trim() {
+                "median" => weights.median = value,
+                "p95" => weights.p95 = value,
+                "success" => weights.success = value,
+                other => {
+                    return Err(anyhow::anyhow!(
+                        "Unknown score term '{}', expected median, p95 or success",
+                        other
+                    ))
+                }
+            }
+        }
+
+        if weights.median + weights.p95 + weights.success <= 0.0 {
+            return Err(anyhow::anyhow!(
+                "At least one score weight must be positive, got '{}'",
+                s
+            ));
+        }
+        Ok(weights)
+    }
+}
+
+/// Weighted mean of three terms, each normalized to `0.0..=1.0` against
+/// fixed references rather than the other providers, so a score means the
+/// same thing in every run:
+///
+/// - median and P95: `1 - latency / SCORE_LATENCY_CEILING_MS`, clamped, so
+///   0ms (or a negative latency from clock skew) scores 1 and anything at or
+///   above the ceiling scores 0;
+/// - success: [`FeedLatencyStats::success_rate`] as is.
+///
+/// The mean is divided by the sum of the weights and scaled to `0..=100`.
+pub fn composite_score(stats: &FeedLatencyStats, weights: &ScoreWeights) -> f64 {
+    let latency_term = |ms: i64| (1.0 - ms as f64 / SCORE_LATENCY_CEILING_MS).clamp(0.0, 1.0);
+    let total = weights.median + weights.p95 + weights.success;
+    if total <= 0.0 {
+        return 0.0;
+    }
+
+    let weighted = weights.median * latency_term(stats.p50_ms)
+        + weights.p95 * latency_term(stats.p95_ms)
+        + weights.success * stats.success_rate.clamp(0.0, 1.0);
+    weighted / total * 100.0
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    Csv,
+    Json,
+}
+
+/// Stats of each provider's latency samples, best [`composite_score`] first.
+/// `expected` is the number of blocks a provider delivering everything would
+/// have sampled; the success rate is taken against it. Providers without
+/// samples are left out.
+pub fn compare_feed_latencies(
+    feeds: &[(&str, Vec<i64>)],
+    expected: usize,
+    weights: &ScoreWeights,
+) -> Vec<FeedLatencyStats> {
+    let mut stats: Vec<FeedLatencyStats> = feeds
+        .iter()
+        .filter_map(|(provider, samples)| {
+            LatencyStats::from_samples(samples).map(|s| FeedLatencyStats {
+                schema_version: SCHEMA_VERSION,
+                provider: provider.to_string(),
+                samples: s.count,
+                avg_ms: s.avg_ms,
+                min_ms: s.min_ms,
+                max_ms: s.max_ms,
+                p50_ms: s.p50_ms,
+                p95_ms: s.p95_ms,
+                success_rate: s.count as f64 / expected.max(s.count) as f64,
+                score: 0.0,
+            })
+        })
+        .collect();
+    for row in stats.iter_mut() {
+        row.score = composite_score(row, weights);
+    }
+    stats.sort_by(|a, b| b.score.total_cmp(&a.score));
+    stats
+}
+
+/// Print `stats` as a ranking table in the order given.
+pub fn print_ranking(stats: &[FeedLatencyStats]) {
+    println!();
+    println!("🏆 Provider ranking (composite score):");
+    println!("Provider       | Score | Success | P50      | P95      | Avg");
+    println!("{}", "-".repeat(68));
+    for row in stats {
+        println!(
+            "{:<14} | {:>5.1} | {:>6.1}% | {:>6}ms | {:>6}ms | {:.1}ms",
+            row.provider,
+            row.score,
+            row.success_rate * 100.0,
+            row.p50_ms,
+            row.p95_ms,
+            row.avg_ms
+        );
+    }
+}
+
+/// Write `stats` in the order given, as CSV (`provider,samples,avg_ms,...`)
+/// or as a JSON array.
+pub fn write_comparison_report(
+    stats: &[FeedLatencyStats],
+    format: ReportFormat,
+    path: &Path,
+) -> Result<()> {
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+    match format {
+        ReportFormat::Json => serde_json::to_writer_pretty(&mut writer, stats)?,
+        ReportFormat::Csv => {
+            writeln!(
+                writer,
+                "provider,samples,avg_ms,min_ms,max_ms,p50_ms,p95_ms,success_rate,score,schema_version"
+            )?;
+            for row in stats {
+                writeln!(
+                    writer,
+                    "{},{},{:.1},{},{},{},{},{:.4},{:.1},{}",
+                    row.provider,
+                    row.samples,
+                    row.avg_ms,
+                    row.min_ms,
+                    row.max_ms,
+                    row.p50_ms,
+                    row.p95_ms,
+                    row.success_rate,
+                    row.score,
+                    row.schema_version
+                )?;
+            }
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(provider: &str, p50_ms: i64) -> FeedLatencyStats {
+        FeedLatencyStats {
+            schema_version: SCHEMA_VERSION,
+            provider: provider.to_string(),
+            samples: 100,
+            avg_ms: p50_ms as f64 + 0.5,
+            min_ms: p50_ms - 50,
+            max_ms: p50_ms + 400,
+            p50_ms,
+            p95_ms: p50_ms + 200,
+            success_rate: 0.98,
+            score: 87.3,
+        }
+    }
+
+    #[test]
+    fn writes_csv_header_and_one_row_per_provider() {
+        let stats = vec![
+            row("laserstream", 380),
+            row("websocket", 420),
+            row("rpc", 610),
+        ];
+        let path = std::env::temp_dir().join(format!("comparison-{}.csv", std::process::id()));
+
+        write_comparison_report(&stats, ReportFormat::Csv, &path).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "provider,samples,avg_ms,min_ms,max_ms,p50_ms,p95_ms,success_rate,score,schema_version"
+        );
+        assert_eq!(lines.len(), stats.len() + 1);
+        assert_eq!(
+            lines[1],
+            format!(
+                "laserstream,100,380.5,330,780,380,580,0.9800,87.3,{}",
+                SCHEMA_VERSION
+            )
+        );
+        assert!(lines[3].starts_with("rpc,"));
+    }
+}
@@ -0,0 +1,97 @@
+//! Processed → confirmed delay measured on a single stream.
+//!
+//! Comparing a processed subscription against a confirmed one on a second
+//! connection mixes commitment cost with connection-to-connection variance.
+//! A Yellowstone request carries one commitment for all its filters, but a
+//! slot filter with `filter_by_commitment: false` streams every status
+//! transition of a slot over the same connection, so the gap between the
+//! processed and confirmed updates for a slot is the commitment cost alone.
+
+use crate::stats::LatencyStats;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Processed timestamps older than this many slots without a confirmed
+/// update (forks, dropped updates) are discarded.
+const MAX_PENDING_SLOTS: u64 = 150;
+/// Deltas at or below this are treated as "arrived together".
+const SAME_TIME_MS: i64 = 1;
+/// Share of same-time pairs above which the endpoint is assumed to not
+/// stream per-commitment statuses.
+const SAME_TIME_SHARE: f64 = 0.9;
+
+#[derive(Debug, Default)]
+pub struct ConfirmationDelay {
+    processed_at: HashMap<u64, i64>,
+    deltas: Vec<i64>,
+    /// Confirmed updates with no processed update to pair with.
+    pub unpaired: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfirmationSummary {
+    pub delay: Option<LatencyStats>,
+    pub unpaired: usize,
+    /// Nearly every pair arrived at the same moment, which means the
+    /// endpoint is not reporting commitment levels separately.
+    pub statuses_indistinct: bool,
+}
+
+impl ConfirmationDelay {
+    pub fn processed(&mut self, slot: u64, received_ms: i64) {
+        self.processed_at.entry(slot).or_insert(received_ms);
+        self.processed_at
+            .retain(|&pending, _| pending + MAX_PENDING_SLOTS >= slot);
+    }
+
+    /// Record the confirmed update for `slot`, returning its delay when the
+    /// processed update was seen.
+    pub fn confirmed(&mut self, slot: u64, received_ms: i64) -> Option<i64> {
+        match self.processed_at.remove(&slot) {
+            Some(processed_ms) => {
+                let delta = received_ms - processed_ms;
+                self.deltas.push(delta);
+                Some(delta)
+            }
+            None => {
+                self.unpaired += 1;
+                None
+            }
+        }
+    }
+
+    pub fn summary(&self) -> ConfirmationSummary {
+        let same_time = self.deltas.iter().filter(|&&d| d <= SAME_TIME_MS).count();
+
+        ConfirmationSummary {
+            delay: LatencyStats::from_samples(&self.deltas),
+            unpaired: self.unpaired,
+            statuses_indistinct: !self.deltas.is_empty()
+                && same_time as f64 / self.deltas.len() as f64 > SAME_TIME_SHARE,
+        }
+    }
+
+    pub fn print(&self) {
+        let summary = self.summary();
+
+        println!("🔐 Processed → confirmed delay (same connection):");
+        match &summary.delay {
+            Some(stats) => println!(
+                "Slots: {} | Avg: {:.1}ms | P50: {}ms | P95: {}ms | Max: {}ms",
+                stats.count, stats.avg_ms, stats.p50_ms, stats.p95_ms, stats.max_ms
+            ),
+            None => println!("No slot reached confirmed with a processed update seen"),
+        }
+        if summary.unpaired > 0 {
+            println!(
+                "{} confirmed updates had no processed update to pair with",
+                summary.unpaired
+            );
+        }
+        if summary.statuses_indistinct {
+            println!(
+                "⚠️  Processed and confirmed arrived together for almost every slot; the endpoint likely ignores per-commitment slot statuses"
+            );
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Slot continuity of a block stream.
+//!
+//! A jump of more than one slot between consecutive blocks is not always a
+//! problem: leaders skip slots, and the next block then names the previous
+//! one as its parent. The block's `parent_slot` separates the cases:
+//!
+//! - parent == previous block: leader-skipped slots, nothing missing
+//! - parent > previous block: blocks in between never reached us (dropped)
+//! - parent < previous block: the previous block was on an abandoned fork
+//!
+//! A block at or below the previous slot is a regression.
+
+use serde::Serialize;
+
+/// What a block's slot says about the stream since the previous block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotStep {
+    /// First block, or the next slot.
+    Contiguous,
+    /// `missing` slots were skipped by their leaders.
+    Skipped { missing: u64 },
+    /// `missing` slots passed and at least one of their blocks never arrived.
+    Dropped { missing: u64 },
+    /// `missing` slots passed and the chain continued from an earlier parent.
+    Forked { missing: u64 },
+    /// The slot went backward (or repeated) by `by` slots.
+    Regression { by: u64 },
+}
+
+impl SlotStep {
+    /// Number of slots between this block and the previous one, for gaps.
+    pub fn missing(self) -> Option<u64> {
+        match self {
+            SlotStep::Skipped { missing }
+            | SlotStep::Dropped { missing }
+            | SlotStep::Forked { missing } => Some(missing),
+            SlotStep::Contiguous | SlotStep::Regression { .. } => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SlotStep::Contiguous => "contiguous",
+            SlotStep::Skipped { .. } => "skipped",
+            SlotStep::Dropped { .. } => "dropped",
+            SlotStep::Forked { .. } => "forked",
+            SlotStep::Regression { .. } => "regression",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SlotContinuity {
+    previous: Option<u64>,
+    summary: ContinuitySummary,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ContinuitySummary {
+    /// Blocks more than one slot after the previous block.
+    pub gaps: u64,
+    /// Blocks at or below the previous block's slot.
+    pub regressions: u64,
+    /// Gaps explained by leader-skipped slots.
+    pub skipped: u64,
+    /// Gaps where blocks were missed by the stream.
+    pub dropped: u64,
+    /// Gaps where the previous block was abandoned by a fork.
+    pub forked: u64,
+    /// Largest gap seen, in slots.
+    pub max_gap_slots: u64,
+}
+
+impl SlotContinuity {
+    pub fn record(&mut self, slot: u64, parent_slot: u64) -> SlotStep {
+        let step = match self.previous {
+            None => SlotStep::Contiguous,
+            Some(previous) if slot <= previous => SlotStep::Regression {
+                by: previous - slot,
+            },
+            Some(previous) if slot == previous + 1 => SlotStep::Contiguous,
+            Some(previous) => {
+                let missing = slot - previous - 1;
+                if parent_slot == previous {
+                    SlotStep::Skipped { missing }
+                } else if parent_slot > previous {
+                    SlotStep::Dropped { missing }
+                } else {
+                    SlotStep::Forked { missing }
+                }
+            }
+        };
+
+        let summary = &mut self.summary;
+        match step {
+            SlotStep::Contiguous => {}
+            SlotStep::Regression { .. } => summary.regressions += 1,
+            SlotStep::Skipped { .. } => summary.skipped += 1,
+            SlotStep::Dropped { .. } => summary.dropped += 1,
+            SlotStep::Forked { .. } => summary.forked += 1,
+        }
+        if let Some(missing) = step.missing() {
+            summary.gaps += 1;
+            summary.max_gap_slots = summary.max_gap_slots.max(missing);
+        }
+        // A regression keeps the later slot, so one stale block is not
+        // followed by a spurious gap back to the tip.
+        if !matches!(step, SlotStep::Regression { .. }) {
+            self.previous = Some(slot);
+        }
+        step
+    }
+
+    pub fn summary(&self) -> &ContinuitySummary {
+        &self.summary
+    }
+
+    pub fn print(&self) {
+        let summary = &self.summary;
+
+        println!("🧵 Slot continuity:");
+        println!(
+            "Gaps: {} (skipped by leader: {}, dropped: {}, forked: {}) | Largest: {} slots",
+            summary.gaps, summary.skipped, summary.dropped, summary.forked, summary.max_gap_slots
+        );
+        println!("Regressions: {}", summary.regressions);
+    }
+}
@@ -0,0 +1,114 @@
+//! Capture of the measurement host's context for a run.
+//!
+//! A slow run is often the host's fault rather than the provider's, so the
+//! summaries record where the numbers were taken from: hostname, OS, a coarse
+//! public-IP location and a TCP connect RTT to the endpoint under test.
+
+use reqwest::{Client, Url};
+use serde::Serialize;
+use serde_json::Value;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+
+const PUBLIC_IP_LOOKUP_URL: &str = "https://ipinfo.io/json";
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RunEnvironment {
+    pub hostname: String,
+    pub os: String,
+    pub arch: String,
+    pub public_ip: Option<String>,
+    /// "city, region, country" as reported by the public-IP lookup.
+    pub location: Option<String>,
+    /// TCP connect time to the endpoint host, roughly one network round trip.
+    pub endpoint_rtt_ms: Option<f64>,
+}
+
+impl RunEnvironment {
+    /// Gather host details. The public-IP lookup is best-effort and skipped
+    /// entirely when `public_ip_lookup` is false.
+    pub async fn capture(endpoint: &str, public_ip_lookup: bool) -> Self {
+        let (public_ip, location) = if public_ip_lookup {
+            lookup_public_ip().await.unwrap_or((None, None))
+        } else {
+            (None, None)
+        };
+
+        RunEnvironment {
+            hostname: hostname(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            public_ip,
+            location,
+            endpoint_rtt_ms: tcp_connect_rtt_ms(endpoint).await,
+        }
+    }
+
+    pub fn print(&self) {
+        println!();
+        println!("🖥️  Run Environment");
+        println!("{}", "-".repeat(50));
+        println!("Hostname:            {}", self.hostname);
+        println!("OS:                  {} ({})", self.os, self.arch);
+        println!(
+            "Public IP:           {}",
+            self.public_ip.as_deref().unwrap_or("not probed")
+        );
+        println!(
+            "Location:            {}",
+            self.location.as_deref().unwrap_or("unknown")
+        );
+        match self.endpoint_rtt_ms {
+            Some(rtt) => println!("Endpoint RTT:        {:.1}ms (TCP connect)", rtt),
+            None => println!("Endpoint RTT:        unavailable"),
+        }
+    }
+}
+
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+async fn lookup_public_ip() -> Option<(Option<String>, Option<String>)> {
+    let client = Client::builder().timeout(PROBE_TIMEOUT).build().ok()?;
+    let info: Value = client
+        .get(PUBLIC_IP_LOOKUP_URL)
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+
+    let ip = info.get("ip").and_then(|v| v.as_str()).map(String::from);
+    let location: Vec<&str> = ["city", "region", "country"]
+        .iter()
+        .filter_map(|key| info.get(*key).and_then(|v| v.as_str()))
+        .collect();
+    let location = (!location.is_empty()).then(|| location.join(", "));
+
+    Some((ip, location))
+}
+
+async fn tcp_connect_rtt_ms(endpoint: &str) -> Option<f64> {
+    let url = Url::parse(endpoint).ok()?;
+    let host = url.host_str()?;
+    let port = url.port_or_known_default()?;
+
+    // Resolve first so DNS time doesn't inflate the RTT anchor
+    let addr = tokio::net::lookup_host((host, port)).await.ok()?.next()?;
+
+    let start = Instant::now();
+    tokio::time::timeout(PROBE_TIMEOUT, TcpStream::connect(addr))
+        .await
+        .ok()?
+        .ok()?;
+
+    Some(start.elapsed().as_secs_f64() * 1000.0)
+}
@@ -0,0 +1,279 @@
+//! Block feed latency of the same slots at processed, confirmed and finalized.
+//!
+//! Every new processed slot is fetched with `getBlock` at all three
+//! commitments. A block that is not yet visible at a commitment stays
+//! pending and is retried once that commitment's tip has reached the slot,
+//! so the difference between the per-commitment latencies is what each
+//! commitment costs on this RPC.
+
+use crate::clock;
+use crate::rpc::{self, Commitment};
+use crate::stats::LatencyStats;
+use anyhow::Result;
+use futures::future;
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+
+/// Slots still pending at a commitment after this long are given up on.
+const PENDING_TIMEOUT_MS: i64 = 60_000;
+/// "Block not available for slot": produced but not at this commitment yet.
+const BLOCK_NOT_AVAILABLE: i64 = -32004;
+/// "Slot was skipped" / "missing in long-term storage".
+const SLOT_SKIPPED: [i64; 2] = [-32007, -32009];
+
+/// Outcome of one `getBlock` at one commitment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockFeedLatency {
+    /// The block was returned; `None` when it carries no block time.
+    Available { latency_ms: Option<i64> },
+    /// Not visible at this commitment yet.
+    Pending,
+    /// The slot has no block.
+    Skipped,
+    /// The call failed (some RPCs reject `getBlock` below confirmed).
+    Failed(String),
+}
+
+#[derive(Debug, Default)]
+struct CommitmentSamples {
+    latencies: Vec<i64>,
+    skipped: usize,
+    failed: usize,
+    timed_out: usize,
+    last_error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct CommitmentFeed {
+    /// Slot → (first seen ms, commitments still pending).
+    pending: BTreeMap<u64, (i64, Vec<Commitment>)>,
+    samples: HashMap<Commitment, CommitmentSamples>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitmentFeedSummary {
+    pub commitment: &'static str,
+    pub latency: Option<LatencyStats>,
+    pub skipped: usize,
+    pub failed: usize,
+    pub timed_out: usize,
+    /// Slots still pending when the run ended.
+    pub pending: usize,
+    pub last_error: Option<String>,
+}
+
+/// Fetch `slot` at every commitment concurrently.
+pub async fn measure_all_commitments(
+    client: &Client,
+    rpc_url: &str,
+    slot: u64,
+) -> HashMap<Commitment, BlockFeedLatency> {
+    let fetches = Commitment::ALL.map(|commitment| async move {
+        (
+            commitment,
+            fetch_block(client, rpc_url, slot, commitment).await,
+        )
+    });
+    future::join_all(fetches).await.into_iter().collect()
+}
+
+async fn fetch_block(
+    client: &Client,
+    rpc_url: &str,
+    slot: u64,
+    commitment: Commitment,
+) -> BlockFeedLatency {
+    let params = json!([
+        slot,
+        {
+            "encoding": "json",
+            "commitment": commitment.as_str(),
+            "maxSupportedTransactionVersion": 0,
+            "rewards": false,
+            "transactionDetails": "none"
+        }
+    ]);
+    let response = match rpc::call(client, rpc_url, "getBlock", params).await {
+        Ok(response) => response,
+        Err(e) => return BlockFeedLatency::Failed(e.to_string()),
+    };
+    let received_time = clock::now_ms();
+
+    if let Some(error) = response.get("error") {
+        return match error.get("code").and_then(Value::as_i64) {
+            Some(BLOCK_NOT_AVAILABLE) => BlockFeedLatency::Pending,
+            Some(code) if SLOT_SKIPPED.contains(&code) => BlockFeedLatency::Skipped,
+            _ => BlockFeedLatency::Failed(error.to_string()),
+        };
+    }
+
+    match response.get("result") {
+        Some(block) if !block.is_null() => BlockFeedLatency::Available {
+            latency_ms: block
+                .get("blockTime")
+                .and_then(Value::as_i64)
+                .map(|bt| received_time - bt * 1000),
+        },
+        _ => BlockFeedLatency::Pending,
+    }
+}
+
+impl CommitmentFeed {
+    /// Measure a newly processed slot at every commitment.
+    pub async fn track(&mut self, client: &Client, rpc_url: &str, slot: u64) {
+        let first_seen = clock::now_ms();
+        let results = measure_all_commitments(client, rpc_url, slot).await;
+
+        let mut pending = Vec::new();
+        for commitment in Commitment::ALL {
+            if let Some(result) = results.get(&commitment) {
+                if !self.record(commitment, result) {
+                    pending.push(commitment);
+                }
+            }
+        }
+        if !pending.is_empty() {
+            self.pending.insert(slot, (first_seen, pending));
+        }
+    }
+
+    /// Retry pending slots that each commitment's tip has reached, and give
+    /// up on the ones pending for too long.
+    pub async fn retry_pending(&mut self, client: &Client, rpc_url: &str) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        let mut tips = HashMap::new();
+        for commitment in Commitment::ALL {
+            tips.insert(
+                commitment,
+                rpc::get_latest_slot(client, rpc_url, commitment).await?,
+            );
+        }
+
+        let now = clock::now_ms();
+        let slots: Vec<u64> = self.pending.keys().copied().collect();
+        for slot in slots {
+            let Some((first_seen, commitments)) = self.pending.remove(&slot) else {
+                continue;
+            };
+
+            let mut still_pending = Vec::new();
+            for commitment in commitments {
+                if tips.get(&commitment).is_some_and(|&tip| tip < slot) {
+                    still_pending.push(commitment);
+                    continue;
+                }
+                let result = fetch_block(client, rpc_url, slot, commitment).await;
+                if !self.record(commitment, &result) {
+                    still_pending.push(commitment);
+                }
+            }
+
+            if still_pending.is_empty() {
+                continue;
+            }
+            if now - first_seen > PENDING_TIMEOUT_MS {
+                for commitment in still_pending {
+                    self.samples.entry(commitment).or_default().timed_out += 1;
+                }
+            } else {
+                self.pending.insert(slot, (first_seen, still_pending));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns false while the block is still pending at `commitment`.
+    fn record(&mut self, commitment: Commitment, result: &BlockFeedLatency) -> bool {
+        let samples = self.samples.entry(commitment).or_default();
+        match result {
+            BlockFeedLatency::Available { latency_ms } => {
+                samples.latencies.extend(*latency_ms);
+                true
+            }
+            BlockFeedLatency::Pending => false,
+            BlockFeedLatency::Skipped => {
+                samples.skipped += 1;
+                true
+            }
+            BlockFeedLatency::Failed(error) => {
+                samples.failed += 1;
+                samples.last_error = Some(error.clone());
+                true
+            }
+        }
+    }
+
+    pub fn summary(&self) -> Vec<CommitmentFeedSummary> {
+        Commitment::ALL
+            .iter()
+            .map(|commitment| {
+                let samples = self.samples.get(commitment);
+                CommitmentFeedSummary {
+                    commitment: commitment.as_str(),
+                    latency: samples.and_then(|s| LatencyStats::from_samples(&s.latencies)),
+                    skipped: samples.map_or(0, |s| s.skipped),
+                    failed: samples.map_or(0, |s| s.failed),
+                    timed_out: samples.map_or(0, |s| s.timed_out),
+                    pending: self
+                        .pending
+                        .values()
+                        .filter(|(_, pending)| pending.contains(commitment))
+                        .count(),
+                    last_error: samples.and_then(|s| s.last_error.clone()),
+                }
+            })
+            .collect()
+    }
+
+    pub fn print(&self) {
+        let summary = self.summary();
+        let processed_avg = summary
+            .first()
+            .and_then(|s| s.latency.as_ref())
+            .map(|stats| stats.avg_ms);
+
+        println!();
+        println!("🔐 Block feed latency by commitment (same slots):");
+        for entry in &summary {
+            match &entry.latency {
+                Some(stats) => {
+                    let cost = match processed_avg {
+                        Some(base) if entry.commitment != Commitment::Processed.as_str() => {
+                            format!(" | +{:.0}ms vs processed", stats.avg_ms - base)
+                        }
+                        _ => String::new(),
+                    };
+                    println!(
+                        "{:<10} Blocks: {} | Avg: {:.1}ms | P50: {}ms | P95: {}ms{}",
+                        entry.commitment,
+                        stats.count,
+                        stats.avg_ms,
+                        stats.p50_ms,
+                        stats.p95_ms,
+                        cost
+                    );
+                }
+                None => println!("{:<10} No blocks measured", entry.commitment),
+            }
+            if entry.skipped + entry.failed + entry.timed_out + entry.pending > 0 {
+                println!(
+                    "           skipped: {} | failed: {} | gave up after {}s: {} | still pending: {}",
+                    entry.skipped,
+                    entry.failed,
+                    PENDING_TIMEOUT_MS / 1000,
+                    entry.timed_out,
+                    entry.pending
+                );
+            }
+            if let Some(error) = &entry.last_error {
+                println!("           last error: {}", error);
+            }
+        }
+    }
+}
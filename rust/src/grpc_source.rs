@@ -0,0 +1,127 @@
+//! Geyser gRPC subscriptions from Helius Laserstream or a plain Yellowstone
+//! endpoint.
+//!
+//! Both speak the Yellowstone `SubscribeRequest` protobuf; they differ in
+//! how the connection is set up. Laserstream goes through the Helius client
+//! (API key, reconnects with replay), while a vanilla Yellowstone endpoint
+//! (e.g. Triton) is a tonic `GeyserClient` authenticated by an `x-token`
+//! metadata header. A reconnect there is not replayed, so a dropped
+//! Yellowstone stream ends the subscription.
+
+use crate::subscription;
+use anyhow::{anyhow, Result};
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use helius_laserstream::grpc::{SubscribeRequest, SubscribeUpdate};
+use helius_laserstream::LaserstreamConfig;
+use yellowstone_grpc_proto::geyser::geyser_client::GeyserClient;
+use yellowstone_grpc_proto::tonic::metadata::AsciiMetadataValue;
+use yellowstone_grpc_proto::tonic::transport::{ClientTlsConfig, Endpoint};
+use yellowstone_grpc_proto::tonic::{Request, Status};
+
+/// Stream of updates of one subscription; errors are per update.
+pub type UpdateStream = BoxStream<'static, Result<SubscribeUpdate>>;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum GrpcKind {
+    /// Helius Laserstream (API key)
+    #[default]
+    Helius,
+    /// Vanilla Yellowstone gRPC (`x-token` header)
+    Yellowstone,
+}
+
+pub trait GrpcSource: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Open a subscription. Connection errors surface as the first item.
+    fn subscribe(&self, request: SubscribeRequest) -> UpdateStream;
+}
+
+/// Build the source of `kind` for `endpoint`. `token` is the Helius API key
+/// (required) or the Yellowstone `x-token` (optional, some endpoints are
+/// open or authenticate by IP).
+pub fn source(
+    kind: GrpcKind,
+    endpoint: &str,
+    token: Option<String>,
+) -> Result<Box<dyn GrpcSource>> {
+    match kind {
+        GrpcKind::Helius => {
+            let api_key =
+                token.ok_or_else(|| anyhow!("API key required for Helius Laserstream"))?;
+            Ok(Box::new(HeliusSource {
+                config: LaserstreamConfig {
+                    api_key,
+                    endpoint: endpoint.parse()?,
+                    ..Default::default()
+                },
+            }))
+        }
+        GrpcKind::Yellowstone => Ok(Box::new(YellowstoneSource {
+            endpoint: endpoint.to_string(),
+            x_token: token.map(|t| t.parse()).transpose()?,
+        })),
+    }
+}
+
+pub struct HeliusSource {
+    config: LaserstreamConfig,
+}
+
+impl GrpcSource for HeliusSource {
+    fn name(&self) -> &'static str {
+        "Helius Laserstream"
+    }
+
+    fn subscribe(&self, request: SubscribeRequest) -> UpdateStream {
+        // Pings are answered and reconnects logged by the wrapper
+        let (updates, _subscription) = subscription::subscribe(self.config.clone(), request);
+        updates
+            .map(|update| update.map_err(|e| anyhow!("{}", e)))
+            .boxed()
+    }
+}
+
+pub struct YellowstoneSource {
+    endpoint: String,
+    x_token: Option<AsciiMetadataValue>,
+}
+
+impl YellowstoneSource {
+    async fn connect(
+        endpoint: String,
+        x_token: Option<AsciiMetadataValue>,
+        request: SubscribeRequest,
+    ) -> Result<UpdateStream> {
+        let mut channel = Endpoint::from_shared(endpoint.clone())?;
+        if endpoint.starts_with("https://") {
+            channel = channel.tls_config(ClientTlsConfig::new().with_native_roots())?;
+        }
+        let channel = channel.connect().await?;
+
+        let mut client = GeyserClient::with_interceptor(channel, move |mut req: Request<()>| {
+            if let Some(token) = &x_token {
+                req.metadata_mut().insert("x-token", token.clone());
+            }
+            Ok::<_, Status>(req)
+        });
+
+        // Keep the request side open: some servers end the subscription
+        // when the client stops sending
+        let requests = stream::iter([request]).chain(stream::pending());
+        let updates = client.subscribe(requests).await?.into_inner();
+
+        Ok(updates.map_err(anyhow::Error::from).boxed())
+    }
+}
+
+impl GrpcSource for YellowstoneSource {
+    fn name(&self) -> &'static str {
+        "Yellowstone gRPC"
+    }
+
+    fn subscribe(&self, request: SubscribeRequest) -> UpdateStream {
+        let connect = Self::connect(self.endpoint.clone(), self.x_token.clone(), request);
+        stream::once(connect).try_flatten().boxed()
+    }
+}
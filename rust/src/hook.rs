@@ -0,0 +1,65 @@
+//! User commands run on measured samples (`--on-sample` / `--on-alert`).
+//!
+//! The command goes through `sh -c` with the sample in its environment:
+//! `SLOT`, `LATENCY_MS`, `SOURCE` and `STATUS`. Children are spawned detached
+//! (tokio reaps them) and rate-limited, so a slow or chatty command never
+//! blocks the receive loop.
+
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+
+pub struct SampleHook {
+    command: String,
+    min_interval: Duration,
+    last_fired: Option<Instant>,
+    fired: u64,
+    skipped: u64,
+}
+
+impl SampleHook {
+    pub fn new(command: String, min_interval: Duration) -> Self {
+        SampleHook {
+            command,
+            min_interval,
+            last_fired: None,
+            fired: 0,
+            skipped: 0,
+        }
+    }
+
+    /// Spawn the command for one sample unless it ran less than
+    /// `min_interval` ago.
+    pub fn fire(&mut self, source: &str, slot: u64, latency_ms: i64, status: &str) {
+        if let Some(last) = self.last_fired {
+            if last.elapsed() < self.min_interval {
+                self.skipped += 1;
+                return;
+            }
+        }
+        self.last_fired = Some(Instant::now());
+
+        let spawned = Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .env("SLOT", slot.to_string())
+            .env("LATENCY_MS", latency_ms.to_string())
+            .env("SOURCE", source)
+            .env("STATUS", status)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .spawn();
+
+        match spawned {
+            Ok(_) => self.fired += 1,
+            Err(e) => eprintln!("❌ Hook command failed to start: {}", e),
+        }
+    }
+
+    pub fn print_summary(&self, name: &str) {
+        println!(
+            "🪝 {}: ran {} times, {} samples skipped by rate limit",
+            name, self.fired, self.skipped
+        );
+    }
+}
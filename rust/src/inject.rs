@@ -0,0 +1,119 @@
+//! Synthetic latency for exercising status thresholds, hooks and verdicts
+//! without waiting for a real bad day (`--inject-latency`, hidden).
+//!
+//! The spec `<ms>@<percent>` (e.g. `2000ms@5%`) adds `ms` to that share of
+//! samples. Selection uses a seeded SplitMix64 stream, so the same `--seed`
+//! perturbs the same sample positions every run. Anything printed from an
+//! injected run carries [`LatencyInjector::banner`] so it cannot be mistaken
+//! for a real measurement.
+
+use anyhow::{anyhow, Result};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Copy)]
+pub struct InjectSpec {
+    pub add_ms: i64,
+    /// Share of samples perturbed, `0..=100`.
+    pub percent: f64,
+}
+
+impl FromStr for InjectSpec {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            anyhow!(
+                "Invalid latency injection '{}', expected <ms>@<percent> (e.g. 2000ms@5%)",
+                s
+            )
+        };
+
+        let (ms, percent) = s.split_once('@').ok_or_else(invalid)?;
+        let add_ms = ms
+            .trim()
+            .trim_end_matches("ms")
+            .parse()
+            .map_err(|_| invalid())?;
+        let percent: f64 = percent
+            .trim()
+            .trim_end_matches('%')
+            .parse()
+            .map_err(|_| invalid())?;
+
+        if !(0.0..=100.0).contains(&percent) {
+            return Err(invalid());
+        }
+
+        Ok(InjectSpec { add_ms, percent })
+    }
+}
+
+impl fmt::Display for InjectSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "+{}ms on {}% of samples", self.add_ms, self.percent)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LatencyInjector {
+    spec: InjectSpec,
+    seed: u64,
+    state: u64,
+    /// Samples perturbed so far.
+    pub injected: u64,
+}
+
+impl LatencyInjector {
+    /// Without a seed one is taken from the clock; it is shown in the banner
+    /// so the run can be replayed.
+    pub fn new(spec: InjectSpec, seed: Option<u64>) -> Self {
+        let seed = seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or(0)
+        });
+
+        LatencyInjector {
+            spec,
+            seed,
+            state: seed,
+            injected: 0,
+        }
+    }
+
+    /// Latency as the rest of the pipeline should see it.
+    pub fn apply(&mut self, latency_ms: i64) -> i64 {
+        if self.next_f64() * 100.0 < self.spec.percent {
+            self.injected += 1;
+            latency_ms + self.spec.add_ms
+        } else {
+            latency_ms
+        }
+    }
+
+    pub fn banner(&self) -> String {
+        format!(
+            "🧪 SYNTHETIC LATENCY INJECTED ({}, seed {}) - not a real measurement",
+            self.spec, self.seed
+        )
+    }
+
+    pub fn print_summary(&self) {
+        println!("{}", self.banner());
+        println!("🧪 {} samples were perturbed", self.injected);
+    }
+
+    /// SplitMix64, mapped to `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
@@ -0,0 +1,72 @@
+//! WebSocket pings for long runs.
+//!
+//! Some providers drop a connection that carries no frames for a while, which
+//! happens during quiet periods of a subscription. [`Keepalive`] paces pings
+//! from the read loop's `select!` and notices when a pong is overdue.
+
+use std::time::Duration;
+use tokio::time::{self, Instant};
+
+/// A ping unanswered for this long is reported as a missed pong.
+pub const PONG_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// What the read loop should do when [`Keepalive::next`] resolves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeepaliveEvent {
+    /// Send a `Message::Ping`.
+    Ping,
+    /// The last ping got no pong within [`PONG_TIMEOUT`].
+    PongOverdue,
+}
+
+#[derive(Debug)]
+pub struct Keepalive {
+    /// `None` disables pings.
+    interval: Option<Duration>,
+    next_ping: Instant,
+    awaiting_pong_since: Option<Instant>,
+    /// Pings that went unanswered for longer than [`PONG_TIMEOUT`].
+    pub missed_pongs: u64,
+}
+
+impl Keepalive {
+    /// Ping every `interval_secs`; 0 never pings.
+    pub fn new(interval_secs: u64) -> Self {
+        let interval = (interval_secs > 0).then(|| Duration::from_secs(interval_secs));
+        Keepalive {
+            next_ping: Instant::now() + interval.unwrap_or_default(),
+            interval,
+            awaiting_pong_since: None,
+            missed_pongs: 0,
+        }
+    }
+
+    /// Wait for the next ping or pong deadline. Cancel-safe: state only
+    /// changes once the wait completes, so it can sit in a `select!` loop.
+    pub async fn next(&mut self) -> KeepaliveEvent {
+        let Some(interval) = self.interval else {
+            return std::future::pending().await;
+        };
+
+        let pong_deadline = self.awaiting_pong_since.map(|sent| sent + PONG_TIMEOUT);
+        match pong_deadline {
+            Some(deadline) if deadline <= self.next_ping => {
+                time::sleep_until(deadline).await;
+                self.awaiting_pong_since = None;
+                self.missed_pongs += 1;
+                KeepaliveEvent::PongOverdue
+            }
+            _ => {
+                time::sleep_until(self.next_ping).await;
+                self.next_ping = Instant::now() + interval;
+                self.awaiting_pong_since.get_or_insert_with(Instant::now);
+                KeepaliveEvent::Ping
+            }
+        }
+    }
+
+    /// A pong arrived; the outstanding ping is answered.
+    pub fn pong(&mut self) {
+        self.awaiting_pong_since = None;
+    }
+}
@@ -0,0 +1,435 @@
+//! Landing rate of transactions submitted elsewhere.
+//!
+//! Given their signatures, [`benchmark_landing_rate`] polls
+//! `getSignatureStatuses` with exponential backoff until every transaction
+//! is finalized or failed, or the deadline passes. It reports what fraction
+//! landed and how long each took to reach confirmed.
+//!
+//! [`benchmark_fee_landing`] does the same for transactions sent at varying
+//! priority fees and groups the time to confirm by fee. A fee the caller
+//! does not know is taken from the landed transaction: `getTransaction`'s
+//! `meta.fee` minus the 5000 lamport base fee per signature. That is the
+//! priority fee actually paid, which can be below the compute unit price
+//! times the requested limit when fewer units were requested than priced.
+
+use crate::rpc;
+use crate::stats::{self, LatencyStats};
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::time::{self, Instant};
+
+/// getSignatureStatuses accepts at most this many signatures per call.
+const MAX_SIGNATURES_PER_CALL: usize = 256;
+/// Base fee per signature, subtracted from `meta.fee` to get the priority fee.
+const LAMPORTS_PER_SIGNATURE: u64 = 5000;
+/// First wait between polls; doubles after every poll.
+const INITIAL_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(4);
+/// Poll interval of [`track_transaction_speed`].
+const TRACK_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Where a signature stands at the last poll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LandingStatus {
+    /// Confirmed, not yet finalized.
+    Confirmed,
+    Finalized,
+    /// Included at confirmed or above with an execution error.
+    Failed,
+    /// Unknown to the RPC, or only processed.
+    NotFound,
+}
+
+impl LandingStatus {
+    /// Finalized and failed transactions need no further polling.
+    fn is_final(self) -> bool {
+        matches!(self, LandingStatus::Finalized | LandingStatus::Failed)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LandingStats {
+    pub submitted: usize,
+    /// Still only confirmed when the deadline passed.
+    pub confirmed: usize,
+    pub finalized: usize,
+    pub failed: usize,
+    /// Not seen at confirmed by the deadline.
+    pub not_found: usize,
+    /// Confirmed or finalized without an error, out of `submitted`.
+    pub landing_percent: f64,
+    /// Ms from the start of the benchmark to the first poll that saw each
+    /// landed transaction at confirmed, so it is only as fine as the poll
+    /// interval. `None` when nothing landed.
+    pub time_to_confirm: Option<LatencyStats>,
+    pub polls: u64,
+}
+
+/// A transaction handed to [`benchmark_fee_landing`].
+#[derive(Debug, Clone)]
+pub struct Submitted {
+    pub signature: String,
+    /// `None` to read it from the landed transaction.
+    pub priority_fee_lamports: Option<u64>,
+}
+
+/// Landing and time to confirm of the transactions whose priority fee falls
+/// in `[min_lamports, max_lamports)`.
+#[derive(Debug, Clone, Serialize)]
+pub struct FeeBucket {
+    pub min_lamports: u64,
+    /// `None` for the open-ended top bucket.
+    pub max_lamports: Option<u64>,
+    pub submitted: usize,
+    pub landed: usize,
+    pub median_confirm_ms: Option<i64>,
+}
+
+struct Tracked {
+    signature: String,
+    status: LandingStatus,
+    confirmed_after_ms: Option<i64>,
+    priority_fee: Option<u64>,
+}
+
+impl Tracked {
+    fn landed(&self) -> bool {
+        matches!(
+            self.status,
+            LandingStatus::Confirmed | LandingStatus::Finalized
+        )
+    }
+}
+
+/// Poll the final status of `signatures` for up to `deadline`. Call it right
+/// after submitting them so `time_to_confirm` covers the whole wait.
+pub async fn benchmark_landing_rate(
+    client: &Client,
+    rpc_url: &str,
+    signatures: Vec<String>,
+    deadline: Duration,
+) -> Result<LandingStats> {
+    let mut tracked: Vec<Tracked> = signatures
+        .into_iter()
+        .map(|signature| Tracked {
+            signature,
+            status: LandingStatus::NotFound,
+            confirmed_after_ms: None,
+            priority_fee: None,
+        })
+        .collect();
+    let polls = poll_statuses(client, rpc_url, &mut tracked, deadline).await?;
+    Ok(landing_stats(&tracked, polls))
+}
+
+/// [`benchmark_landing_rate`] plus the median time to confirm per power-of-ten
+/// priority fee bucket (0, 1-9, 10-99, ... lamports).
+pub async fn benchmark_fee_landing(
+    client: &Client,
+    rpc_url: &str,
+    submitted: Vec<Submitted>,
+    deadline: Duration,
+) -> Result<(LandingStats, Vec<FeeBucket>)> {
+    let mut tracked: Vec<Tracked> = submitted
+        .into_iter()
+        .map(|s| Tracked {
+            signature: s.signature,
+            status: LandingStatus::NotFound,
+            confirmed_after_ms: None,
+            priority_fee: s.priority_fee_lamports,
+        })
+        .collect();
+    let polls = poll_statuses(client, rpc_url, &mut tracked, deadline).await?;
+
+    for entry in tracked.iter_mut() {
+        if entry.priority_fee.is_none() && entry.landed() {
+            entry.priority_fee = get_priority_fee(client, rpc_url, &entry.signature).await?;
+        }
+    }
+
+    Ok((landing_stats(&tracked, polls), fee_buckets(&tracked)))
+}
+
+/// Poll one signature every [`TRACK_POLL_INTERVAL`] until it reaches
+/// confirmed or fails, or `deadline` passes. Returns its last status and,
+/// once landed, the ms from the call to the poll that saw it confirmed. The
+/// fixed short interval keeps that figure close to the actual confirmation,
+/// unlike the backoff of [`benchmark_landing_rate`].
+pub async fn track_transaction_speed(
+    client: &Client,
+    rpc_url: &str,
+    signature: &str,
+    deadline: Duration,
+) -> Result<(LandingStatus, Option<i64>)> {
+    let start = Instant::now();
+
+    loop {
+        let status = get_signature_statuses(client, rpc_url, &[signature])
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or(LandingStatus::NotFound);
+        let elapsed_ms = start.elapsed().as_millis() as i64;
+
+        match status {
+            LandingStatus::Confirmed | LandingStatus::Finalized => {
+                return Ok((status, Some(elapsed_ms)))
+            }
+            LandingStatus::Failed => return Ok((status, None)),
+            LandingStatus::NotFound => {}
+        }
+
+        let Some(remaining) = deadline.checked_sub(start.elapsed()) else {
+            return Ok((status, None));
+        };
+        time::sleep(TRACK_POLL_INTERVAL.min(remaining)).await;
+    }
+}
+
+/// Poll until every transaction is final or `deadline` passes; returns the
+/// number of polls.
+async fn poll_statuses(
+    client: &Client,
+    rpc_url: &str,
+    tracked: &mut [Tracked],
+    deadline: Duration,
+) -> Result<u64> {
+    let start = Instant::now();
+    let mut interval = INITIAL_POLL_INTERVAL;
+    let mut polls = 0;
+
+    loop {
+        let open: Vec<usize> = (0..tracked.len())
+            .filter(|&i| !tracked[i].status.is_final())
+            .collect();
+        if open.is_empty() {
+            break;
+        }
+
+        for chunk in open.chunks(MAX_SIGNATURES_PER_CALL) {
+            let batch: Vec<&str> = chunk
+                .iter()
+                .map(|&i| tracked[i].signature.as_str())
+                .collect();
+            let statuses = get_signature_statuses(client, rpc_url, &batch).await?;
+            let elapsed_ms = start.elapsed().as_millis() as i64;
+
+            for (&i, status) in chunk.iter().zip(statuses) {
+                let entry = &mut tracked[i];
+                entry.status = status;
+                if matches!(status, LandingStatus::Confirmed | LandingStatus::Finalized) {
+                    entry.confirmed_after_ms.get_or_insert(elapsed_ms);
+                }
+            }
+        }
+        polls += 1;
+
+        let Some(remaining) = deadline.checked_sub(start.elapsed()) else {
+            break;
+        };
+        time::sleep(interval.min(remaining)).await;
+        interval = (interval * 2).min(MAX_POLL_INTERVAL);
+    }
+
+    Ok(polls)
+}
+
+fn landing_stats(tracked: &[Tracked], polls: u64) -> LandingStats {
+    let count = |status| tracked.iter().filter(|t| t.status == status).count();
+    let confirmed = count(LandingStatus::Confirmed);
+    let finalized = count(LandingStatus::Finalized);
+    let confirm_times: Vec<i64> = tracked
+        .iter()
+        .filter(|t| t.landed())
+        .filter_map(|t| t.confirmed_after_ms)
+        .collect();
+
+    LandingStats {
+        submitted: tracked.len(),
+        confirmed,
+        finalized,
+        failed: count(LandingStatus::Failed),
+        not_found: count(LandingStatus::NotFound),
+        landing_percent: if tracked.is_empty() {
+            0.0
+        } else {
+            (confirmed + finalized) as f64 / tracked.len() as f64 * 100.0
+        },
+        time_to_confirm: LatencyStats::from_samples(&confirm_times),
+        polls,
+    }
+}
+
+/// Power-of-ten buckets up to the highest fee seen. Transactions whose fee
+/// is still unknown (not landed, no fee given) are left out.
+fn fee_buckets(tracked: &[Tracked]) -> Vec<FeeBucket> {
+    let Some(max_fee) = tracked.iter().filter_map(|t| t.priority_fee).max() else {
+        return Vec::new();
+    };
+
+    let mut bounds = vec![(0, Some(1))];
+    let mut lower = 1u64;
+    while lower <= max_fee {
+        let upper = lower.checked_mul(10);
+        bounds.push((lower, upper));
+        match upper {
+            Some(upper) => lower = upper,
+            None => break,
+        }
+    }
+
+    bounds
+        .into_iter()
+        .filter_map(|(min_lamports, max_lamports)| {
+            let in_bucket: Vec<&Tracked> = tracked
+                .iter()
+                .filter(|t| {
+                    t.priority_fee.is_some_and(|fee| {
+                        fee >= min_lamports && max_lamports.is_none_or(|max| fee < max)
+                    })
+                })
+                .collect();
+            if in_bucket.is_empty() {
+                return None;
+            }
+
+            let mut confirm_times: Vec<i64> = in_bucket
+                .iter()
+                .filter(|t| t.landed())
+                .filter_map(|t| t.confirmed_after_ms)
+                .collect();
+            confirm_times.sort();
+            Some(FeeBucket {
+                min_lamports,
+                max_lamports,
+                submitted: in_bucket.len(),
+                landed: in_bucket.iter().filter(|t| t.landed()).count(),
+                median_confirm_ms: stats::percentile(&confirm_times, 0.5),
+            })
+        })
+        .collect()
+}
+
+/// Priority fee paid by a landed transaction: `meta.fee` minus the base fee
+/// of its signatures. `None` when the transaction is not available.
+async fn get_priority_fee(client: &Client, rpc_url: &str, signature: &str) -> Result<Option<u64>> {
+    let json_value = rpc::call(
+        client,
+        rpc_url,
+        "getTransaction",
+        json!([signature, {
+            "encoding": "json",
+            "commitment": "confirmed",
+            "maxSupportedTransactionVersion": 0
+        }]),
+    )
+    .await?;
+
+    if let Some(error) = json_value.get("error") {
+        return Err(anyhow!("getTransaction error: {}", error));
+    }
+
+    let Some(result) = json_value.get("result").filter(|r| !r.is_null()) else {
+        return Ok(None);
+    };
+    let fee = result.pointer("/meta/fee").and_then(Value::as_u64);
+    let signatures = result
+        .pointer("/transaction/signatures")
+        .and_then(Value::as_array)
+        .map_or(1, |s| s.len() as u64);
+
+    Ok(fee.map(|fee| fee.saturating_sub(signatures * LAMPORTS_PER_SIGNATURE)))
+}
+
+/// One status per signature, in order. A JSON-RPC `error` is returned as `Err`.
+async fn get_signature_statuses(
+    client: &Client,
+    rpc_url: &str,
+    signatures: &[&str],
+) -> Result<Vec<LandingStatus>> {
+    let json_value = rpc::call(
+        client,
+        rpc_url,
+        "getSignatureStatuses",
+        json!([signatures, {"searchTransactionHistory": true}]),
+    )
+    .await?;
+
+    if let Some(error) = json_value.get("error") {
+        return Err(anyhow!("getSignatureStatuses error: {}", error));
+    }
+
+    let values = json_value
+        .pointer("/result/value")
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow!("Failed to get signature statuses"))?;
+
+    Ok(values.iter().map(parse_status).collect())
+}
+
+fn parse_status(value: &Value) -> LandingStatus {
+    let level = value.get("confirmationStatus").and_then(Value::as_str);
+    let errored = value.get("err").is_some_and(|err| !err.is_null());
+
+    match level {
+        Some("confirmed" | "finalized") if errored => LandingStatus::Failed,
+        Some("finalized") => LandingStatus::Finalized,
+        Some("confirmed") => LandingStatus::Confirmed,
+        _ => LandingStatus::NotFound,
+    }
+}
+
+impl LandingStats {
+    pub fn print(&self) {
+        println!();
+        println!("🛬 Transaction Landing Rate");
+        println!("{}", "=".repeat(50));
+        println!("Submitted:            {}", self.submitted);
+        println!("Finalized:            {}", self.finalized);
+        println!("Confirmed only:       {}", self.confirmed);
+        println!("Failed:               {}", self.failed);
+        println!("Not found:            {}", self.not_found);
+        println!("Landing rate:         {:.1}%", self.landing_percent);
+        println!("Status polls:         {}", self.polls);
+
+        if let Some(stats) = &self.time_to_confirm {
+            println!();
+            println!("⏱️  Time to confirm (from start of polling):");
+            println!("Average:              {:.1}ms", stats.avg_ms);
+            println!("Min:                  {}ms", stats.min_ms);
+            println!("Max:                  {}ms", stats.max_ms);
+            println!("Median:               {}ms", stats.p50_ms);
+            println!("95th percentile:      {}ms", stats.p95_ms);
+        }
+    }
+}
+
+pub fn print_fee_buckets(buckets: &[FeeBucket]) {
+    println!();
+    println!("💸 Time to confirm by priority fee:");
+    if buckets.is_empty() {
+        println!("No transaction with a known priority fee");
+        return;
+    }
+    println!("Fee (lamports)       | Sent   | Landed | Median confirm");
+    println!("{}", "-".repeat(58));
+    for bucket in buckets {
+        let range = match bucket.max_lamports {
+            Some(max) if max == bucket.min_lamports + 1 => bucket.min_lamports.to_string(),
+            Some(max) => format!("{}-{}", bucket.min_lamports, max - 1),
+            None => format!("{}+", bucket.min_lamports),
+        };
+        let median = bucket
+            .median_confirm_ms
+            .map(|ms| format!("{}ms", ms))
+            .unwrap_or_else(|| "n/a".to_string());
+        println!(
+            "{:<20} | {:>6} | {:>6} | {}",
+            range, bucket.submitted, bucket.landed, median
+        );
+    }
+}
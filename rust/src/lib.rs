@@ -0,0 +1,52 @@
+//! Solana block and slot latency benchmarks.
+//!
+//! The binaries are thin wrappers over [`tools`], so the same runs can be
+//! embedded. Each config is the tool's clap `Args`; build it with the
+//! binary's flags, e.g. `RpcBenchConfig::parse_from(["rpc", "--duration", "1"])`
+//! (`clap::Parser` in scope), and inspect the returned [`BenchmarkReport`].
+
+pub mod block;
+pub mod block_time;
+pub mod breakdown;
+pub mod capabilities;
+pub mod clock;
+pub mod comparison;
+pub mod confirmation;
+pub mod continuity;
+pub mod environment;
+pub mod feed;
+pub mod grpc_source;
+pub mod hook;
+pub mod inject;
+pub mod keepalive;
+pub mod landing;
+pub mod logging;
+pub mod methodology;
+pub mod multistream;
+pub mod output;
+pub mod poll;
+pub mod prewarm;
+pub mod pubkey;
+pub mod ratelimit;
+pub mod report;
+pub mod rpc;
+pub mod shutdown;
+pub mod slot_rate;
+pub mod stall;
+pub mod stats;
+pub mod status;
+pub mod stop;
+pub mod storage;
+pub mod submit;
+pub mod subscription;
+pub mod tail;
+pub mod tools;
+pub mod types;
+pub mod warmup;
+
+pub use report::{BenchmarkReport, Measurement};
+pub use tools::laserstream_benchmark::{
+    run as run_laserstream_benchmark, Args as LaserstreamBenchConfig,
+};
+pub use tools::latency_calculator::{run as run_websocket_benchmark, Args as WebsocketBenchConfig};
+pub use tools::rpc_latency_test::{run as run_rpc_benchmark, Args as RpcBenchConfig};
@@ -0,0 +1,44 @@
+//! Diagnostics on stderr through `tracing`.
+//!
+//! Result tables, live sample lines and `--jsonl` records stay plain stdout.
+//! Everything else a tool used to print only with `--verbose` (stream
+//! errors, skipped slots, subscription confirmations) is a `debug!` event.
+//! `RUST_LOG` picks the levels, e.g. `RUST_LOG=solana_rpc_performance_rust=trace`;
+//! without it warnings are shown, and `--verbose` raises this crate to DEBUG.
+
+use crate::rpc::TRACE_TARGET;
+use tracing_subscriber::EnvFilter;
+
+const CRATE_TARGET: &str = "solana_rpc_performance_rust";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per event
+    Json,
+}
+
+/// Install the global subscriber. `rpc_trace` adds the `--trace-rpc`
+/// request/response events. A second call (e.g. when tools share a
+/// process) keeps the first subscriber.
+pub fn init(format: LogFormat, verbose: bool, rpc_trace: bool) {
+    let mut filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        let level = if verbose { "debug" } else { "warn" };
+        EnvFilter::new(format!("warn,{}={}", CRATE_TARGET, level))
+    });
+    if rpc_trace {
+        if let Ok(directive) = format!("{}=debug", TRACE_TARGET).parse() {
+            filter = filter.add_directive(directive);
+        }
+    }
+
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr);
+    let _ = match format {
+        LogFormat::Text => builder.try_init(),
+        LogFormat::Json => builder.json().try_init(),
+    };
+}
@@ -0,0 +1,54 @@
+//! Self-describing record of the methodology constants behind a run.
+//!
+//! Verdicts depend on poll intervals, filter windows and status thresholds
+//! that differ between tools; printing them alongside the results makes two
+//! runs comparable (or shows why they aren't).
+
+use crate::status::StatusThresholds;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Methodology {
+    pub tool: &'static str,
+    /// How blocks are observed, e.g. "gRPC block stream" or "HTTP polling".
+    pub source: &'static str,
+    pub latency_formula: &'static str,
+    /// Wall clock behind `received_time`, e.g. "clock_realtime".
+    pub time_source: String,
+    pub commitment: &'static str,
+    /// Sleep between polls; `None` for push-based sources. The lower bound
+    /// when the interval adapts.
+    pub poll_interval_ms: Option<u64>,
+    /// Upper bound of an adaptive poll interval; `None` when it is fixed.
+    pub poll_interval_max_ms: Option<u64>,
+    /// Samples outside this window are dropped before statistics.
+    pub latency_filter: Option<String>,
+    /// Number of leading samples excluded from statistics.
+    pub warmup_samples: u64,
+    pub percentile_definition: &'static str,
+    pub status_thresholds: StatusThresholds,
+}
+
+impl Methodology {
+    pub fn print(&self) {
+        println!();
+        println!("📐 Methodology ({})", self.tool);
+        println!("{}", "-".repeat(50));
+        println!("Source:              {}", self.source);
+        println!("Latency formula:     {}", self.latency_formula);
+        println!("Time source:         {}", self.time_source);
+        println!("Commitment:          {}", self.commitment);
+        match (self.poll_interval_ms, self.poll_interval_max_ms) {
+            (Some(min), Some(max)) => println!("Poll interval:       {}-{}ms (adaptive)", min, max),
+            (Some(ms), None) => println!("Poll interval:       {}ms", ms),
+            (None, _) => println!("Poll interval:       n/a (push)"),
+        }
+        println!(
+            "Latency filter:      {}",
+            self.latency_filter.as_deref().unwrap_or("none")
+        );
+        println!("Warmup samples:      {}", self.warmup_samples);
+        println!("Percentiles:         {}", self.percentile_definition);
+        println!("Status thresholds:   {}", self.status_thresholds);
+    }
+}
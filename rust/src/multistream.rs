@@ -0,0 +1,302 @@
+//! Per-connection variance across K identical subscriptions.
+//!
+//! The same block stream is opened K times from this host. For each slot the
+//! earliest arrival across the streams is the best any connection achieved,
+//! so a stream's penalty (its arrival minus that minimum) is what that one
+//! connection added on top of the latency all of them share.
+//!
+//! A stream can deliver a slot twice, e.g. when the client replays after an
+//! internal reconnect. Only a stream's first arrival of a slot counts; the
+//! repeats are counted as duplicates so a late copy can neither replace the
+//! real arrival nor lower the slot's minimum. A slot is remembered for
+//! 2 × [`MAX_PENDING_SLOTS`] slots; an arrival of an older one cannot be
+//! told apart from a duplicate and is counted as late instead.
+
+use crate::stats::LatencyStats;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// A slot still missing streams this many slots after the newest one is
+/// scored with the streams that did deliver it. Scored slots are remembered
+/// for as long again to recognise duplicates; arrivals of older slots are
+/// counted as late and otherwise ignored.
+const MAX_PENDING_SLOTS: u64 = 150;
+
+#[derive(Debug)]
+pub struct MultiStream {
+    /// Slot → arrival ms per stream.
+    pending: BTreeMap<u64, Vec<Option<i64>>>,
+    /// Arrivals of the recently scored slots, to tell a duplicate from a
+    /// stream's late first delivery.
+    scored: BTreeMap<u64, Vec<Option<i64>>>,
+    /// `(slot, earliest arrival ms)` scored since the last
+    /// [`MultiStream::take_scored`].
+    newly_scored: Vec<(u64, i64)>,
+    /// Slots below this are forgotten.
+    forget_before: u64,
+    /// Slots scored so far.
+    scored_slots: u64,
+    penalties: Vec<Vec<i64>>,
+    /// Slots each stream delivered first (ties count for every tied stream).
+    first: Vec<u64>,
+    /// Scored slots each stream never delivered.
+    missed: Vec<u64>,
+    /// Repeat deliveries of a slot by the same stream, ignored.
+    duplicates: Vec<u64>,
+    /// Arrivals of slots already forgotten, ignored.
+    late: Vec<u64>,
+    /// Slots every stream delivered.
+    complete: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamPenalty {
+    pub stream: usize,
+    /// Arrival minus the earliest arrival of the same slot, in ms.
+    pub penalty: Option<LatencyStats>,
+    pub first: u64,
+    pub missed: u64,
+    pub duplicates: u64,
+    /// Arrivals of slots too old to tell a duplicate from a late delivery.
+    pub late: u64,
+}
+
+impl MultiStream {
+    pub fn new(streams: usize) -> Self {
+        MultiStream {
+            pending: BTreeMap::new(),
+            scored: BTreeMap::new(),
+            newly_scored: Vec::new(),
+            forget_before: 0,
+            scored_slots: 0,
+            penalties: vec![Vec::new(); streams],
+            first: vec![0; streams],
+            missed: vec![0; streams],
+            duplicates: vec![0; streams],
+            late: vec![0; streams],
+            complete: 0,
+        }
+    }
+
+    fn streams(&self) -> usize {
+        self.penalties.len()
+    }
+
+    /// Record that `stream` received `slot`. Only its first arrival counts;
+    /// repeats are counted as duplicates and otherwise ignored.
+    pub fn record(&mut self, stream: usize, slot: u64, received_ms: i64) {
+        if slot < self.forget_before {
+            self.late[stream] += 1;
+            return;
+        }
+        if let Some(arrivals) = self.scored.get(&slot) {
+            if arrivals[stream].is_some() {
+                self.duplicates[stream] += 1;
+            }
+            return;
+        }
+        let streams = self.streams();
+        let arrivals = self
+            .pending
+            .entry(slot)
+            .or_insert_with(|| vec![None; streams]);
+        if arrivals[stream].is_some() {
+            self.duplicates[stream] += 1;
+            return;
+        }
+        arrivals[stream] = Some(received_ms);
+
+        if arrivals.iter().all(Option::is_some) {
+            if let Some(arrivals) = self.pending.remove(&slot) {
+                self.complete += 1;
+                self.score(slot, arrivals);
+            }
+        }
+
+        let stale: Vec<u64> = self
+            .pending
+            .range(..slot.saturating_sub(MAX_PENDING_SLOTS))
+            .map(|(&slot, _)| slot)
+            .collect();
+        for slot in stale {
+            if let Some(arrivals) = self.pending.remove(&slot) {
+                self.score(slot, arrivals);
+            }
+        }
+
+        self.forget_before = self
+            .forget_before
+            .max(slot.saturating_sub(2 * MAX_PENDING_SLOTS));
+        self.scored = self.scored.split_off(&self.forget_before);
+    }
+
+    /// Score the slots still waiting on a stream at the end of the run.
+    pub fn finish(&mut self) {
+        for (slot, arrivals) in std::mem::take(&mut self.pending) {
+            self.score(slot, arrivals);
+        }
+    }
+
+    fn score(&mut self, slot: u64, arrivals: Vec<Option<i64>>) {
+        let Some(earliest) = arrivals.iter().flatten().copied().min() else {
+            return;
+        };
+        self.newly_scored.push((slot, earliest));
+        self.scored_slots += 1;
+        for (stream, arrival) in arrivals.iter().enumerate() {
+            match *arrival {
+                Some(received_ms) => {
+                    let penalty = received_ms - earliest;
+                    if penalty == 0 {
+                        self.first[stream] += 1;
+                    }
+                    self.penalties[stream].push(penalty);
+                }
+                None => self.missed[stream] += 1,
+            }
+        }
+        self.scored.insert(slot, arrivals);
+    }
+
+    /// Slots every stream delivered.
+    pub fn complete(&self) -> u64 {
+        self.complete
+    }
+
+    /// Slots scored so far.
+    pub fn scored_slots(&self) -> u64 {
+        self.scored_slots
+    }
+
+    /// `(slot, earliest arrival ms)` of the slots scored since the last
+    /// call, in the order they were scored.
+    pub fn take_scored(&mut self) -> Vec<(u64, i64)> {
+        std::mem::take(&mut self.newly_scored)
+    }
+
+    /// Slots below this are forgotten, so per-slot data kept next to this
+    /// can be dropped below it too.
+    pub fn forget_before(&self) -> u64 {
+        self.forget_before
+    }
+
+    pub fn summary(&self) -> Vec<StreamPenalty> {
+        (0..self.streams())
+            .map(|stream| StreamPenalty {
+                stream,
+                penalty: LatencyStats::from_samples(&self.penalties[stream]),
+                first: self.first[stream],
+                missed: self.missed[stream],
+                duplicates: self.duplicates[stream],
+                late: self.late[stream],
+            })
+            .collect()
+    }
+
+    pub fn print(&self) {
+        println!(
+            "🔀 Per-stream penalty vs the earliest of {} streams ({} slots scored, {} delivered by all):",
+            self.streams(),
+            self.scored_slots,
+            self.complete
+        );
+        println!("Stream | First  | Missed | Dups   | Avg penalty | P50    | P95    | Max");
+        println!("{}", "-".repeat(75));
+        for stream in self.summary() {
+            match &stream.penalty {
+                Some(penalty) => println!(
+                    "{:<6} | {:>6} | {:>6} | {:>6} | {:>9.1}ms | {:>4}ms | {:>4}ms | {}ms",
+                    stream.stream + 1,
+                    stream.first,
+                    stream.missed,
+                    stream.duplicates,
+                    penalty.avg_ms,
+                    penalty.p50_ms,
+                    penalty.p95_ms,
+                    penalty.max_ms
+                ),
+                None => println!(
+                    "{:<6} | {:>6} | {:>6} | {:>6} | no slots delivered",
+                    stream.stream + 1,
+                    stream.first,
+                    stream.missed,
+                    stream.duplicates
+                ),
+            }
+        }
+        self.print_late();
+    }
+
+    /// Footnote for the arrivals of forgotten slots, if there were any.
+    pub fn print_late(&self) {
+        let late: u64 = self.late.iter().sum();
+        if late > 0 {
+            println!(
+                "{} arrivals of slots more than {} slots old were ignored",
+                late,
+                2 * MAX_PENDING_SLOTS
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SLOT: u64 = 300_000_000;
+
+    #[test]
+    fn same_stream_repeat_is_a_duplicate() {
+        let mut multi = MultiStream::new(2);
+        multi.record(0, SLOT, 1_000);
+        // Pending: the repeat must not replace the first arrival
+        multi.record(0, SLOT, 900);
+        multi.record(1, SLOT, 1_040);
+        // Scored: the repeat must not add a penalty
+        multi.record(1, SLOT, 1_500);
+
+        let summary = multi.summary();
+        assert_eq!(multi.scored_slots(), 1);
+        assert_eq!(multi.take_scored(), vec![(SLOT, 1_000)]);
+        assert_eq!(summary[0].penalty.as_ref().unwrap().count, 1);
+        assert_eq!(summary[0].penalty.as_ref().unwrap().max_ms, 0);
+        assert_eq!(summary[1].penalty.as_ref().unwrap().count, 1);
+        assert_eq!(summary[1].penalty.as_ref().unwrap().max_ms, 40);
+        assert_eq!(summary[0].duplicates, 1);
+        assert_eq!(summary[1].duplicates, 1);
+    }
+
+    #[test]
+    fn late_first_delivery_after_stale_scoring_is_not_a_duplicate() {
+        let mut multi = MultiStream::new(2);
+        multi.record(0, SLOT, 1_000);
+        // Stream 1 has not delivered SLOT yet when it goes stale
+        multi.record(0, SLOT + MAX_PENDING_SLOTS + 1, 61_000);
+        assert_eq!(multi.take_scored(), vec![(SLOT, 1_000)]);
+
+        multi.record(1, SLOT, 61_100);
+
+        let summary = multi.summary();
+        assert_eq!(summary[1].missed, 1);
+        assert_eq!(summary[1].duplicates, 0);
+        assert_eq!(summary[1].late, 0);
+        assert!(summary[1].penalty.is_none());
+    }
+
+    #[test]
+    fn arrival_of_a_forgotten_slot_is_late() {
+        let mut multi = MultiStream::new(2);
+        multi.record(0, SLOT, 1_000);
+        multi.record(1, SLOT, 1_010);
+        multi.record(0, SLOT + 2 * MAX_PENDING_SLOTS + 1, 121_000);
+        assert!(multi.forget_before() > SLOT);
+
+        multi.record(0, SLOT, 121_100);
+
+        let summary = multi.summary();
+        assert_eq!(summary[0].late, 1);
+        assert_eq!(summary[0].duplicates, 0);
+        assert_eq!(summary[0].penalty.as_ref().unwrap().count, 1);
+    }
+}
@@ -0,0 +1,431 @@
+//! Result sinks selected with `--output`.
+//!
+//! `--output` takes a comma-separated list of sinks, each with an optional
+//! target: `json[:PATH]`, `csv:PATH`, `prometheus:PORT`, `influx:URL|PATH`.
+//! The JSON sink is the end-of-run summary the tool already builds; every
+//! other sink consumes individual samples. Samples are published once on a
+//! broadcast channel and each sink runs as its own task subscribed to it, so
+//! adding a sink never touches the receive loop.
+//!
+//! The Prometheus sink keeps one series per source, all labelled with the
+//! run's region, plus a stream error counter bumped through
+//! [`Outputs::stream_error`] (errors are not samples, so they bypass the
+//! channel).
+
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+
+/// Samples buffered per sink before a slow one starts losing them.
+const CHANNEL_CAPACITY: usize = 4096;
+/// Influx lines sent per write request (or appended per file write).
+const INFLUX_BATCH: usize = 500;
+/// `le` bounds of the Prometheus latency histogram, in ms.
+const PROMETHEUS_BUCKETS_MS: &[i64] = &[250, 500, 750, 1000, 1250, 1500, 2000, 3000, 5000];
+/// Measurement / metric name prefix shared by the sample sinks.
+const METRIC: &str = "block_latency";
+
+#[derive(Debug, Clone)]
+pub struct Sample {
+    pub source: &'static str,
+    pub slot: u64,
+    pub received_ms: i64,
+    pub latency_ms: i64,
+    /// Server send → local receive, when the source reports a send time.
+    pub network_latency_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Sink {
+    /// End-of-run summary, to stdout when no path is given.
+    Json(Option<PathBuf>),
+    /// One `source,slot,received_ms,latency_ms` row per sample.
+    Csv(PathBuf),
+    /// `/metrics` endpoint on this port for the duration of the run.
+    Prometheus(u16),
+    /// Line protocol, POSTed to an `http(s)://` write URL or appended to a file.
+    Influx(String),
+}
+
+impl FromStr for Sink {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (kind, target) = match s.split_once(':') {
+            Some((kind, target)) => (kind, Some(target).filter(|t| !t.is_empty())),
+            None => (s, None),
+        };
+        let required = |what: &str| {
+            target.ok_or_else(|| anyhow!("Output '{}' needs a target: {}:<{}>", kind, kind, what))
+        };
+
+        match kind {
+            "json" => Ok(Sink::Json(target.map(PathBuf::from))),
+            "csv" => Ok(Sink::Csv(PathBuf::from(required("path")?))),
+            "prometheus" => {
+                let port = required("port")?;
+                port.parse()
+                    .map(Sink::Prometheus)
+                    .map_err(|e| anyhow!("Invalid prometheus port '{}': {}", port, e))
+            }
+            "influx" => Ok(Sink::Influx(required("url|path")?.to_string())),
+            _ => Err(anyhow!(
+                "Unknown output '{}', expected json, csv, prometheus or influx",
+                kind
+            )),
+        }
+    }
+}
+
+/// Parsed `--output` list.
+#[derive(Debug, Clone, Default)]
+pub struct OutputSpec(pub Vec<Sink>);
+
+impl FromStr for OutputSpec {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        s.split(',')
+            .map(|sink| sink.trim().parse())
+            .collect::<Result<Vec<_>>>()
+            .map(OutputSpec)
+    }
+}
+
+impl OutputSpec {
+    /// Where the JSON summary goes: `Some(None)` for stdout.
+    pub fn json(&self) -> Option<Option<PathBuf>> {
+        self.0.iter().find_map(|sink| match sink {
+            Sink::Json(path) => Some(path.clone()),
+            _ => None,
+        })
+    }
+}
+
+/// Running sample sinks. Publish with [`Outputs::send`], then [`Outputs::finish`]
+/// to flush them once the run is over.
+pub struct Outputs {
+    sender: broadcast::Sender<Sample>,
+    writers: Vec<(String, JoinHandle<Result<u64>>)>,
+    servers: Vec<JoinHandle<Result<()>>>,
+    prometheus: Vec<Arc<Mutex<PrometheusState>>>,
+}
+
+impl Outputs {
+    /// Spawn a task per sample sink. Must be called inside the runtime.
+    /// `region` labels the Prometheus series.
+    pub fn start(spec: &OutputSpec, region: &str) -> Result<Self> {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        let mut writers = Vec::new();
+        let mut servers = Vec::new();
+        let mut prometheus = Vec::new();
+
+        for sink in &spec.0 {
+            let rx = sender.subscribe();
+            match sink {
+                Sink::Json(_) => {}
+                Sink::Csv(path) => {
+                    let file = File::create(path)
+                        .map_err(|e| anyhow!("Cannot create {}: {}", path.display(), e))?;
+                    writers.push((
+                        format!("csv:{}", path.display()),
+                        tokio::spawn(write_csv(BufWriter::new(file), rx)),
+                    ));
+                }
+                Sink::Prometheus(port) => {
+                    // Bind up front so a taken port fails the run instead of a background task
+                    let listener = std::net::TcpListener::bind(("0.0.0.0", *port))
+                        .map_err(|e| anyhow!("Cannot bind prometheus port {}: {}", port, e))?;
+                    listener.set_nonblocking(true)?;
+                    let listener = TcpListener::from_std(listener)?;
+
+                    let state = Arc::new(Mutex::new(PrometheusState::new(region)));
+                    prometheus.push(state.clone());
+                    servers.push(tokio::spawn(serve_prometheus(listener, state.clone())));
+                    writers.push((
+                        format!("prometheus:{}", port),
+                        tokio::spawn(collect_prometheus(state, rx)),
+                    ));
+                    println!("📈 Prometheus metrics on http://0.0.0.0:{}/metrics", port);
+                }
+                Sink::Influx(target) => {
+                    writers.push((
+                        format!("influx:{}", target),
+                        tokio::spawn(write_influx(target.clone(), rx)),
+                    ));
+                }
+            }
+        }
+
+        Ok(Outputs {
+            sender,
+            writers,
+            servers,
+            prometheus,
+        })
+    }
+
+    pub fn send(&self, sample: Sample) {
+        // No receivers just means no sample sinks were selected
+        let _ = self.sender.send(sample);
+    }
+
+    /// Count a stream/transport error against `source`.
+    pub fn stream_error(&self, source: &'static str) {
+        for state in &self.prometheus {
+            *state.lock().unwrap().errors.entry(source).or_default() += 1;
+        }
+    }
+
+    /// Close the channel, wait for every sink to drain and stop the servers.
+    pub async fn finish(self) {
+        drop(self.sender);
+
+        for (name, handle) in self.writers {
+            match handle.await {
+                Ok(Ok(0)) => {}
+                Ok(Ok(lagged)) => eprintln!(
+                    "⚠️  Output {} fell behind and dropped {} samples",
+                    name, lagged
+                ),
+                Ok(Err(e)) => eprintln!("❌ Output {} failed: {}", name, e),
+                Err(e) => eprintln!("❌ Output {} task failed: {}", name, e),
+            }
+        }
+        for server in self.servers {
+            server.abort();
+        }
+    }
+}
+
+/// Next sample, counting any the sink lagged past. `None` once the run ends.
+async fn next_sample(rx: &mut broadcast::Receiver<Sample>, lagged: &mut u64) -> Option<Sample> {
+    loop {
+        match rx.recv().await {
+            Ok(sample) => return Some(sample),
+            Err(RecvError::Lagged(n)) => *lagged += n,
+            Err(RecvError::Closed) => return None,
+        }
+    }
+}
+
+async fn write_csv(
+    mut writer: BufWriter<File>,
+    mut rx: broadcast::Receiver<Sample>,
+) -> Result<u64> {
+    let mut lagged = 0;
+    writeln!(writer, "source,slot,received_ms,latency_ms")?;
+
+    while let Some(sample) = next_sample(&mut rx, &mut lagged).await {
+        writeln!(
+            writer,
+            "{},{},{},{}",
+            sample.source, sample.slot, sample.received_ms, sample.latency_ms
+        )?;
+    }
+
+    writer.flush()?;
+    Ok(lagged)
+}
+
+async fn write_influx(target: String, mut rx: broadcast::Receiver<Sample>) -> Result<u64> {
+    let client = Client::new();
+    let mut lagged = 0;
+    let mut lines = Vec::new();
+
+    while let Some(sample) = next_sample(&mut rx, &mut lagged).await {
+        lines.push(format!(
+            "{},source={} slot={}i,latency_ms={}i {}",
+            METRIC,
+            sample.source,
+            sample.slot,
+            sample.latency_ms,
+            sample.received_ms * 1_000_000
+        ));
+        if lines.len() >= INFLUX_BATCH {
+            flush_influx(&client, &target, &mut lines).await?;
+        }
+    }
+
+    flush_influx(&client, &target, &mut lines).await?;
+    Ok(lagged)
+}
+
+async fn flush_influx(client: &Client, target: &str, lines: &mut Vec<String>) -> Result<()> {
+    if lines.is_empty() {
+        return Ok(());
+    }
+    let body = lines.join("\n") + "\n";
+    lines.clear();
+
+    if target.starts_with("http://") || target.starts_with("https://") {
+        let response = client.post(target).body(body).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("Influx write returned {}", response.status()));
+        }
+    } else {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(target)?
+            .write_all(body.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// One latency histogram, cumulative per [`PROMETHEUS_BUCKETS_MS`] bound.
+#[derive(Debug, Default)]
+struct PrometheusHistogram {
+    count: u64,
+    sum_ms: i64,
+    buckets: [u64; PROMETHEUS_BUCKETS_MS.len()],
+}
+
+impl PrometheusHistogram {
+    fn observe(&mut self, ms: i64) {
+        self.count += 1;
+        self.sum_ms += ms;
+        for (bucket, &le) in self.buckets.iter_mut().zip(PROMETHEUS_BUCKETS_MS) {
+            if ms <= le {
+                *bucket += 1;
+            }
+        }
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        for (count, le) in self.buckets.iter().zip(PROMETHEUS_BUCKETS_MS) {
+            let _ = writeln!(out, "{}_bucket{{{},le=\"{}\"}} {}", name, labels, le, count);
+        }
+        let _ = writeln!(
+            out,
+            "{}_bucket{{{},le=\"+Inf\"}} {}",
+            name, labels, self.count
+        );
+        let _ = writeln!(out, "{}_sum{{{}}} {}", name, labels, self.sum_ms);
+        let _ = writeln!(out, "{}_count{{{}}} {}", name, labels, self.count);
+    }
+}
+
+#[derive(Debug, Default)]
+struct SourceMetrics {
+    last_ms: i64,
+    latency: PrometheusHistogram,
+    network: PrometheusHistogram,
+}
+
+#[derive(Debug, Default)]
+struct PrometheusState {
+    region: String,
+    /// Sorted so scrapes list series in a stable order.
+    sources: BTreeMap<&'static str, SourceMetrics>,
+    errors: BTreeMap<&'static str, u64>,
+}
+
+impl PrometheusState {
+    fn new(region: &str) -> Self {
+        PrometheusState {
+            region: region.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn record(&mut self, sample: &Sample) {
+        let source = self.sources.entry(sample.source).or_default();
+        source.last_ms = sample.latency_ms;
+        source.latency.observe(sample.latency_ms);
+        if let Some(network_ms) = sample.network_latency_ms {
+            source.network.observe(network_ms);
+        }
+    }
+
+    fn render(&self) -> String {
+        let labels = |source: &str| format!("source=\"{}\",region=\"{}\"", source, self.region);
+        let mut out = String::new();
+
+        let _ = writeln!(out, "# TYPE {}_last_ms gauge", METRIC);
+        for (source, metrics) in &self.sources {
+            let _ = writeln!(
+                out,
+                "{}_last_ms{{{}}} {}",
+                METRIC,
+                labels(source),
+                metrics.last_ms
+            );
+        }
+        let _ = writeln!(out, "# TYPE {}_ms histogram", METRIC);
+        for (source, metrics) in &self.sources {
+            metrics
+                .latency
+                .render(&mut out, &format!("{}_ms", METRIC), &labels(source));
+        }
+        let _ = writeln!(out, "# TYPE {}_network_ms histogram", METRIC);
+        for (source, metrics) in &self.sources {
+            metrics
+                .network
+                .render(&mut out, &format!("{}_network_ms", METRIC), &labels(source));
+        }
+        let _ = writeln!(out, "# TYPE {}_blocks_received_total counter", METRIC);
+        for (source, metrics) in &self.sources {
+            let _ = writeln!(
+                out,
+                "{}_blocks_received_total{{{}}} {}",
+                METRIC,
+                labels(source),
+                metrics.latency.count
+            );
+        }
+        let _ = writeln!(out, "# TYPE {}_stream_errors_total counter", METRIC);
+        for (source, errors) in &self.errors {
+            let _ = writeln!(
+                out,
+                "{}_stream_errors_total{{{}}} {}",
+                METRIC,
+                labels(source),
+                errors
+            );
+        }
+
+        out
+    }
+}
+
+async fn collect_prometheus(
+    state: Arc<Mutex<PrometheusState>>,
+    mut rx: broadcast::Receiver<Sample>,
+) -> Result<u64> {
+    let mut lagged = 0;
+    while let Some(sample) = next_sample(&mut rx, &mut lagged).await {
+        state.lock().unwrap().record(&sample);
+    }
+    Ok(lagged)
+}
+
+/// Minimal HTTP responder: every request gets the current metrics.
+async fn serve_prometheus(listener: TcpListener, state: Arc<Mutex<PrometheusState>>) -> Result<()> {
+    loop {
+        let (mut socket, _) = listener.accept().await?;
+        let body = state.lock().unwrap().render();
+
+        tokio::spawn(async move {
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        });
+    }
+}
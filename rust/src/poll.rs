@@ -0,0 +1,184 @@
+//! Slot-tip polling for the HTTP pollers.
+//!
+//! The pollers sample `getSlot` every few hundred ms. A single failed call
+//! used to cost the whole cycle, so the next observed slot was often two
+//! ahead and its latency was measured for a block produced while nobody was
+//! looking. [`SlotTracker::poll`] retries briefly inside the cycle and counts
+//! the transitions that were still skipped, so a run can be judged on how
+//! faithfully it followed the tip.
+//!
+//! [`AdaptiveInterval`] picks the sleep between those polls from the slot
+//! time actually observed, instead of a fixed interval that is too eager
+//! while the cluster is slow and too lazy while it is fast.
+
+use anyhow::Result;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// Extra attempts after a failed poll before the cycle is given up.
+pub const RETRIES: u32 = 2;
+/// Pause between attempts; short enough to stay well inside a poll interval.
+pub const RETRY_DELAY: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone)]
+pub struct SlotTracker {
+    last_slot: u64,
+    /// Tip advances observed.
+    pub transitions: u64,
+    /// Slots the tip moved past without being observed (jumps of more than
+    /// one). Leader-skipped slots land here too, hence "likely" missed.
+    pub missed: u64,
+    /// Attempts that failed but were recovered by a retry.
+    pub retries: u64,
+    /// Cycles where every attempt failed.
+    pub failed_cycles: u64,
+}
+
+impl SlotTracker {
+    pub fn new(start_slot: u64) -> Self {
+        SlotTracker {
+            last_slot: start_slot,
+            transitions: 0,
+            missed: 0,
+            retries: 0,
+            failed_cycles: 0,
+        }
+    }
+
+    /// Fetch the tip with up to [`RETRIES`] retries. Returns the new slot
+    /// when it advanced, `None` when it did not.
+    pub async fn poll<F, Fut>(&mut self, mut fetch: F) -> Result<Option<u64>>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<u64>>,
+    {
+        let mut attempt = 0;
+        let slot = loop {
+            match fetch().await {
+                Ok(slot) => break slot,
+                Err(_) if attempt < RETRIES => {
+                    attempt += 1;
+                    self.retries += 1;
+                    tokio::time::sleep(RETRY_DELAY).await;
+                }
+                Err(e) => {
+                    self.failed_cycles += 1;
+                    return Err(e);
+                }
+            }
+        };
+
+        if slot <= self.last_slot {
+            return Ok(None);
+        }
+
+        self.transitions += 1;
+        self.missed += slot - self.last_slot - 1;
+        self.last_slot = slot;
+
+        Ok(Some(slot))
+    }
+
+    pub fn last_slot(&self) -> u64 {
+        self.last_slot
+    }
+
+    pub fn print_summary(&self) {
+        println!(
+            "🎯 Slot polling: {} transitions, {} slots likely missed (tip jumped by >1), {} getSlot retries, {} failed cycles",
+            self.transitions, self.missed, self.retries, self.failed_cycles
+        );
+    }
+}
+
+/// Default bounds of [`AdaptiveInterval`], as flag defaults.
+pub const DEFAULT_MIN_POLL_MS: u64 = 100;
+pub const DEFAULT_MAX_POLL_MS: u64 = 1000;
+/// Solana's target slot time, assumed until advances have been observed.
+const TARGET_SLOT_MS: f64 = 400.0;
+/// Weight of the newest advance in the smoothed slot time.
+const SLOT_MS_SMOOTHING: f64 = 0.2;
+/// Polls are aimed at this fraction of a slot, so a slot is seen on average
+/// a quarter slot after it starts.
+const SLOT_FRACTION: f64 = 0.5;
+/// Multiplier applied to the interval when a poll skipped a slot.
+const SHRINK: f64 = 0.75;
+/// Multiplier applied after [`RELAX_AFTER`] advances in a row without a skip.
+const RELAX: f64 = 1.1;
+const RELAX_AFTER: u32 = 10;
+/// Bounds of the skip/relax adjustment relative to half the slot time.
+const MIN_ADJUSTMENT: f64 = 0.25;
+const MAX_ADJUSTMENT: f64 = 1.5;
+
+/// Sleep between `getSlot` polls: half the recent slot time, shortened
+/// while polls skip slots and lengthened again once every slot is caught,
+/// always within `[min, max]`.
+#[derive(Debug, Clone)]
+pub struct AdaptiveInterval {
+    min: Duration,
+    max: Duration,
+    /// Smoothed ms per slot between observed advances.
+    slot_ms: f64,
+    adjustment: f64,
+    clean_advances: u32,
+    last_advance: Option<(u64, Instant)>,
+    /// Polls that skipped a slot, i.e. times the interval was shortened.
+    pub shrinks: u64,
+}
+
+impl AdaptiveInterval {
+    /// Bounds given the wrong way round are swapped.
+    pub fn new(min_ms: u64, max_ms: u64) -> Self {
+        let (min_ms, max_ms) = (min_ms.min(max_ms), min_ms.max(max_ms));
+        AdaptiveInterval {
+            min: Duration::from_millis(min_ms),
+            max: Duration::from_millis(max_ms),
+            slot_ms: TARGET_SLOT_MS,
+            adjustment: 1.0,
+            clean_advances: 0,
+            last_advance: None,
+            shrinks: 0,
+        }
+    }
+
+    /// Record that the tip advanced from `previous` to `current`.
+    pub fn advanced(&mut self, previous: u64, current: u64) {
+        let now = Instant::now();
+        if let Some((last_slot, at)) = self.last_advance.replace((current, now)) {
+            if current > last_slot {
+                let per_slot =
+                    now.duration_since(at).as_secs_f64() * 1000.0 / (current - last_slot) as f64;
+                self.slot_ms += SLOT_MS_SMOOTHING * (per_slot - self.slot_ms);
+            }
+        }
+
+        if current > previous + 1 {
+            self.adjustment = (self.adjustment * SHRINK).max(MIN_ADJUSTMENT);
+            self.clean_advances = 0;
+            self.shrinks += 1;
+        } else {
+            self.clean_advances += 1;
+            if self.clean_advances >= RELAX_AFTER {
+                self.adjustment = (self.adjustment * RELAX).min(MAX_ADJUSTMENT);
+                self.clean_advances = 0;
+            }
+        }
+    }
+
+    /// How long to sleep before the next poll.
+    pub fn next(&self) -> Duration {
+        let ms = self.slot_ms * SLOT_FRACTION * self.adjustment;
+        Duration::from_secs_f64(ms.max(0.0) / 1000.0).clamp(self.min, self.max)
+    }
+
+    pub fn print_summary(&self) {
+        println!(
+            "⏱️  Adaptive polling: {:.0}ms/slot observed, ending at {}ms between polls ({}-{}ms bounds), shortened {} times after a skipped slot",
+            self.slot_ms,
+            self.next().as_millis(),
+            self.min.as_millis(),
+            self.max.as_millis(),
+            self.shrinks
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Connection warmup before the measured loop starts.
+//!
+//! The first request on a fresh client pays for DNS, TCP and TLS on top of
+//! the round trip, which inflates whatever sample it belongs to. With
+//! `--prewarm-dns` the tools pay that cost up front, report it separately and
+//! only then start timing.
+
+use crate::rpc;
+use reqwest::{Client, Url};
+use serde::Serialize;
+use serde_json::json;
+use std::time::Instant;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Prewarm {
+    /// Time to resolve the endpoint host.
+    pub dns_ms: Option<f64>,
+    /// Time for the throwaway request (connect + TLS + one round trip), or
+    /// until the first stream update for push sources.
+    pub connect_ms: Option<f64>,
+}
+
+impl Prewarm {
+    pub fn print(&self) {
+        let fmt = |ms: Option<f64>| {
+            ms.map(|ms| format!("{:.1}ms", ms))
+                .unwrap_or_else(|| "failed".to_string())
+        };
+        println!(
+            "🔥 Prewarm: DNS {}, connection {} (excluded from samples)",
+            fmt(self.dns_ms),
+            fmt(self.connect_ms)
+        );
+    }
+}
+
+/// Resolve the endpoint's host, returning how long the lookup took.
+pub async fn resolve_ms(endpoint: &str) -> Option<f64> {
+    let url = Url::parse(endpoint).ok()?;
+    let host = url.host_str()?;
+    let port = url.port_or_known_default()?;
+
+    let start = Instant::now();
+    tokio::net::lookup_host((host, port)).await.ok()?.next()?;
+
+    Some(start.elapsed().as_secs_f64() * 1000.0)
+}
+
+/// Resolve the RPC host and issue one throwaway `getSlot` so `client` holds
+/// an open pooled connection before timing starts.
+pub async fn rpc(client: &Client, rpc_url: &str) -> Prewarm {
+    let dns_ms = resolve_ms(rpc_url).await;
+
+    let start = Instant::now();
+    let connect_ms = rpc::call(client, rpc_url, "getSlot", json!([]))
+        .await
+        .ok()
+        .map(|_| start.elapsed().as_secs_f64() * 1000.0);
+
+    Prewarm { dns_ms, connect_ms }
+}
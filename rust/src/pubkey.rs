@@ -0,0 +1,29 @@
+//! Validation for account / program pubkey inputs.
+//!
+//! A mistyped address otherwise reaches the RPC or gRPC endpoint unchanged
+//! and comes back as an opaque server error or, worse, an empty result that
+//! looks like a quiet account.
+
+use anyhow::{anyhow, Result};
+
+const PUBKEY_BYTES: usize = 32;
+
+/// Check that `s` is base58 decoding to exactly 32 bytes and return it
+/// trimmed. Usable directly as a clap `value_parser`.
+pub fn parse_pubkey(s: &str) -> Result<String> {
+    let s = s.trim();
+    let invalid = |reason: String| anyhow!("invalid pubkey '{}': {}", s, reason);
+
+    let bytes = bs58::decode(s)
+        .into_vec()
+        .map_err(|e| invalid(format!("expected 32-byte base58 ({})", e)))?;
+
+    if bytes.len() != PUBKEY_BYTES {
+        return Err(invalid(format!(
+            "expected 32-byte base58, decoded to {} bytes",
+            bytes.len()
+        )));
+    }
+
+    Ok(s.to_string())
+}
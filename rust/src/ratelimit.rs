@@ -0,0 +1,118 @@
+//! Client-side pacing of RPC requests.
+//!
+//! Fetching every slot of a burst one after another is slow, and firing them
+//! all at once trips provider rate limits. [`RequestLimiter`] runs requests
+//! concurrently up to a cap, spaces them with a token bucket at the
+//! provider's requests/sec, and retries HTTP 429 with backoff.
+
+use crate::rpc::RateLimited;
+use anyhow::Result;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::{Mutex, Semaphore};
+use tokio::time::{self, Instant};
+
+/// Retries of a single request answered with 429 before giving up.
+const MAX_RATE_LIMIT_RETRIES: u32 = 5;
+/// First backoff when the provider sends no `Retry-After`; doubles per retry.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Refills `rate` tokens per second up to one second's worth of burst.
+pub struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    pub fn new(rate: f64) -> Self {
+        let capacity = rate.max(1.0);
+        TokenBucket {
+            rate,
+            capacity,
+            state: Mutex::new((capacity, Instant::now())),
+        }
+    }
+
+    /// Wait until a token is available and take it.
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().await;
+                let (tokens, last) = &mut *state;
+                let now = Instant::now();
+                *tokens = (*tokens + now.duration_since(*last).as_secs_f64() * self.rate)
+                    .min(self.capacity);
+                *last = now;
+
+                if *tokens >= 1.0 {
+                    *tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - *tokens) / self.rate)
+            };
+            time::sleep(wait).await;
+        }
+    }
+}
+
+pub struct RequestLimiter {
+    permits: Semaphore,
+    bucket: Option<TokenBucket>,
+    rate_limited: AtomicUsize,
+}
+
+impl RequestLimiter {
+    /// `requests_per_second` of `None` leaves only the concurrency cap.
+    pub fn new(max_concurrency: usize, requests_per_second: Option<f64>) -> Self {
+        RequestLimiter {
+            permits: Semaphore::new(max_concurrency.max(1)),
+            bucket: requests_per_second.map(TokenBucket::new),
+            rate_limited: AtomicUsize::new(0),
+        }
+    }
+
+    /// Run `request` once a concurrency permit and a rate token are
+    /// available. A [`RateLimited`] error is retried with backoff (honouring
+    /// `Retry-After`); any other result is returned as is.
+    pub async fn run<T, F, Fut>(&self, mut request: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        // Held through the backoff so a throttled provider also sees fewer
+        // requests in flight
+        let _permit = self.permits.acquire().await?;
+        let mut backoff = INITIAL_BACKOFF;
+
+        let mut attempt = 0;
+        loop {
+            if let Some(bucket) = &self.bucket {
+                bucket.acquire().await;
+            }
+
+            let result = request().await;
+            let limited = match &result {
+                Err(e) => e.downcast_ref::<RateLimited>().copied(),
+                Ok(_) => None,
+            };
+            let Some(limited) = limited else {
+                return result;
+            };
+
+            self.rate_limited.fetch_add(1, Ordering::Relaxed);
+            if attempt == MAX_RATE_LIMIT_RETRIES {
+                return result;
+            }
+            time::sleep(limited.retry_after.unwrap_or(backoff)).await;
+            backoff *= 2;
+            attempt += 1;
+        }
+    }
+
+    /// 429 responses seen so far, retried or not.
+    pub fn rate_limited(&self) -> usize {
+        self.rate_limited.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,90 @@
+//! What an embedded benchmark run returns.
+//!
+//! The binaries print their results and drop the report; library callers
+//! get the accepted measurements and the statistics computed from them.
+
+use crate::breakdown::BreakdownSummary;
+use crate::stall::StallStats;
+use crate::stats::LatencyStats;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Written as `schema_version` on every serialized report: this
+/// [`BenchmarkReport`], the comparison rows, `laserstream_benchmark --json`
+/// and each `--jsonl` block. Bump it whenever a field is renamed, removed or
+/// changes meaning, so tooling reading older files can tell.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Accept a record read back in only if it carries the current
+/// [`SCHEMA_VERSION`].
+pub fn check_schema_version(record: &Value) -> Result<()> {
+    match record.get("schema_version").and_then(Value::as_u64) {
+        Some(version) if version == u64::from(SCHEMA_VERSION) => Ok(()),
+        Some(version) => Err(anyhow!(
+            "schema_version {}, this build reads version {}",
+            version,
+            SCHEMA_VERSION
+        )),
+        None => Err(anyhow!(
+            "no schema_version; written by an older build, re-capture it with this one"
+        )),
+    }
+}
+
+/// One accepted latency sample.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Measurement {
+    pub slot: u64,
+    /// Unix ms at which the block (or slot) was observed.
+    pub received_ms: i64,
+    pub latency_ms: i64,
+    /// Unix ms at which the provider processed the block (gRPC `created_at`),
+    /// splitting `latency_ms` into upstream and delivery. `None` for sources
+    /// without it and for updates that did not carry one.
+    pub created_at_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchmarkReport {
+    /// [`SCHEMA_VERSION`].
+    pub schema_version: u32,
+    pub tool: &'static str,
+    pub endpoint: String,
+    /// Accepted samples in arrival order; warmup and filtered samples are not included.
+    pub measurements: Vec<Measurement>,
+    /// `None` when nothing was measured.
+    pub stats: Option<LatencyStats>,
+    /// Samples dropped by the accepted latency window.
+    pub excluded: u64,
+    /// Leading samples discarded by `--warmup`.
+    pub warmup_discarded: u64,
+    /// Feed stalls, for runs that watch for them.
+    pub stalls: Option<StallStats>,
+    /// Upstream (block_time → created_at) and delivery (created_at →
+    /// received) distributions, for runs whose source reports `created_at`.
+    pub breakdown: Option<BreakdownSummary>,
+}
+
+impl BenchmarkReport {
+    pub fn new(
+        tool: &'static str,
+        endpoint: &str,
+        measurements: Vec<Measurement>,
+        excluded: u64,
+        warmup_discarded: u64,
+    ) -> Self {
+        let latencies: Vec<i64> = measurements.iter().map(|m| m.latency_ms).collect();
+        BenchmarkReport {
+            schema_version: SCHEMA_VERSION,
+            tool,
+            endpoint: endpoint.to_string(),
+            stats: LatencyStats::from_samples(&latencies),
+            measurements,
+            excluded,
+            warmup_discarded,
+            stalls: None,
+            breakdown: None,
+        }
+    }
+}
@@ -0,0 +1,622 @@
+//! JSON-RPC transport and the common Solana calls shared by the benchmark
+//! binaries, plus the shared `config.json` loader.
+//!
+//! Every call gets a unique `id`; with `--trace-rpc` the request, the raw
+//! (truncated) response and the round trip are logged at DEBUG under the
+//! `rpc_trace` target so a misbehaving provider can be inspected.
+//!
+//! [`call_measured`] can also ask for a gzip response. The client has no
+//! transparent decompression, so the compressed wire size and the time spent
+//! inflating it stay visible in the returned [`Transfer`].
+//!
+//! An HTTP 429 comes back as a [`RateLimited`] error so callers can back off
+//! instead of mistaking it for a missing block. [`with_timeout`] bounds a
+//! call well below the client timeout so one stalled request cannot hold up
+//! a polling cycle. An HTTP 5xx is a [`ServerError`]. [`retry`] retries
+//! these transport-level failures with jittered backoff, but never a
+//! JSON-RPC `error`, which is the provider's actual answer.
+
+use crate::status::StatusThresholds;
+use anyhow::{anyhow, Result};
+use flate2::read::GzDecoder;
+use reqwest::header::{ACCEPT_ENCODING, CONTENT_ENCODING, RETRY_AFTER};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::io::Read;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+pub const TRACE_TARGET: &str = "rpc_trace";
+/// Responses (a full getBlock can be megabytes) are cut to this many bytes in traces.
+const TRACE_BODY_LIMIT: usize = 512;
+
+/// Cadence of the HTTP slot pollers, shared so their samples stay comparable.
+pub const SLOT_POLL_INTERVAL_MS: u64 = 400;
+/// [`retry`] settings for calls inside a poll cycle: three attempts whose
+/// backoff (at most 50 + 100ms) stays inside one interval.
+pub const POLL_RETRY_ATTEMPTS: u32 = 3;
+pub const POLL_RETRY_BASE_DELAY: Duration = Duration::from_millis(50);
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Commitment {
+    #[default]
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+impl Commitment {
+    /// Weakest to strongest.
+    pub const ALL: [Commitment; 3] = [
+        Commitment::Processed,
+        Commitment::Confirmed,
+        Commitment::Finalized,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Commitment::Processed => "processed",
+            Commitment::Confirmed => "confirmed",
+            Commitment::Finalized => "finalized",
+        }
+    }
+}
+
+impl FromStr for Commitment {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "processed" => Ok(Commitment::Processed),
+            "confirmed" => Ok(Commitment::Confirmed),
+            "finalized" => Ok(Commitment::Finalized),
+            _ => Err(anyhow!(
+                "Invalid commitment '{}', expected processed, confirmed or finalized",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Commitment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// `../shared/config.json`, shared with the Node and Go benchmarks.
+#[derive(Deserialize)]
+pub struct Config {
+    pub rpcs: HashMap<String, RPCConfig>,
+    /// Only read by rpc_latency_test.
+    #[serde(default)]
+    pub status_thresholds: Option<StatusThresholds>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct RPCConfig {
+    pub name: String,
+    pub url: String,
+    pub provider: String,
+    #[serde(default)]
+    pub status: String,
+    /// Provider limit as written in the config, e.g. `"100 req/s"`.
+    #[serde(default, rename = "rateLimit")]
+    pub rate_limit: Option<String>,
+}
+
+impl RPCConfig {
+    /// The leading number of `rateLimit`, if it parses.
+    pub fn requests_per_second(&self) -> Option<f64> {
+        self.rate_limit
+            .as_deref()?
+            .split_whitespace()
+            .next()?
+            .parse()
+            .ok()
+            .filter(|&rate: &f64| rate > 0.0)
+    }
+}
+
+/// The provider answered HTTP 429.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimited {
+    /// `Retry-After` in seconds, when the provider sent one.
+    pub retry_after: Option<Duration>,
+}
+
+impl fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.retry_after {
+            Some(after) => write!(
+                f,
+                "rate limited (HTTP 429, retry after {}s)",
+                after.as_secs()
+            ),
+            None => f.write_str("rate limited (HTTP 429)"),
+        }
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+/// The provider answered with an HTTP 5xx.
+#[derive(Debug, Clone, Copy)]
+pub struct ServerError {
+    pub status: u16,
+}
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "server error (HTTP {})", self.status)
+    }
+}
+
+impl std::error::Error for ServerError {}
+
+/// A call abandoned by [`with_timeout`].
+#[derive(Debug, Clone, Copy)]
+pub struct TimedOut {
+    pub after: Duration,
+}
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no response within {}ms", self.after.as_millis())
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+/// Await `call` for at most `limit`; past that it is dropped and a
+/// [`TimedOut`] error returned.
+pub async fn with_timeout<T>(limit: Duration, call: impl Future<Output = Result<T>>) -> Result<T> {
+    tokio::time::timeout(limit, call)
+        .await
+        .map_err(|_| TimedOut { after: limit })?
+}
+
+/// Connection failures, timeouts, HTTP 5xx and 429. Everything else,
+/// notably a JSON-RPC `error` turned into `Err` by a caller, is final.
+pub fn is_transient(error: &anyhow::Error) -> bool {
+    if let Some(e) = error.downcast_ref::<reqwest::Error>() {
+        return e.is_connect() || e.is_timeout() || e.is_request();
+    }
+    error.is::<TimedOut>() || error.is::<ServerError>() || error.is::<RateLimited>()
+}
+
+/// Run `op` up to `max_attempts` times while it fails with a transient
+/// error ([`is_transient`]), sleeping `base_delay * 2^n` between attempts,
+/// scaled by a random 50-100% so parallel pollers do not retry in lockstep.
+///
+/// A `Retry-After` from a 429 is honoured instead when it fits within the
+/// longest of those backoffs (`base_delay * 2^(max_attempts - 2)`). A longer
+/// one ends the retries with the 429, so a poller skips the cycle rather
+/// than stalling past its interval.
+pub async fn retry<T, F, Fut>(mut op: F, max_attempts: u32, base_delay: Duration) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let max_backoff = base_delay.saturating_mul(1 << max_attempts.saturating_sub(2).min(31));
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Err(e) if attempt < max_attempts && is_transient(&e) => {
+                let retry_after = e.downcast_ref::<RateLimited>().and_then(|r| r.retry_after);
+                let delay = match retry_after {
+                    Some(after) if after > max_backoff => return Err(e),
+                    Some(after) => after,
+                    None => {
+                        base_delay.mul_f64(2f64.powi(attempt as i32 - 1) * (0.5 + jitter() / 2.0))
+                    }
+                };
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Pseudo-random fraction in `0.0..1.0` from the clock's sub-second nanos,
+/// plenty to spread retries without a RNG dependency.
+fn jitter() -> f64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    (nanos % 1_000_000) as f64 / 1_000_000.0
+}
+
+pub fn load_config(config_path: &str) -> Result<Config> {
+    let content = std::fs::read_to_string(config_path)?;
+    let config: Config = serde_json::from_str(&content)?;
+    Ok(config)
+}
+
+/// Size and timing of one [`call_measured`] request.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Transfer {
+    /// Whether the server actually answered with gzip.
+    pub compressed: bool,
+    /// Body bytes as received on the wire.
+    pub wire_bytes: usize,
+    /// Body bytes after decompression.
+    pub body_bytes: usize,
+    /// Request sent until response headers arrived: connection, server
+    /// processing and first byte, before any of the body.
+    pub headers_ms: f64,
+    /// Reading the (possibly compressed) body after the headers.
+    pub download_ms: f64,
+    /// Inflating a gzip body; zero otherwise.
+    pub decode_ms: f64,
+    /// Parsing the body as JSON. Grows with the payload, so a provider
+    /// returning huge blocks shows here rather than in `headers_ms`.
+    pub parse_ms: f64,
+    /// The whole call, up to the decoded JSON.
+    pub total_ms: f64,
+}
+
+/// POST a JSON-RPC request and return the decoded response envelope.
+///
+/// The caller inspects `result` / `error`; only transport and JSON decoding
+/// failures are returned as `Err`.
+pub async fn call(client: &Client, url: &str, method: &str, params: Value) -> Result<Value> {
+    call_measured(client, url, method, params, false)
+        .await
+        .map(|(value, _)| value)
+}
+
+/// [`call`], optionally sending `Accept-Encoding: gzip`, with the transfer
+/// figures of the response.
+pub async fn call_measured(
+    client: &Client,
+    url: &str,
+    method: &str,
+    params: Value,
+    gzip: bool,
+) -> Result<(Value, Transfer)> {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": method,
+        "params": params
+    });
+
+    debug!(target: TRACE_TARGET, id, method, request = %request, "request");
+
+    let mut builder = client.post(url).json(&request);
+    if gzip {
+        builder = builder.header(ACCEPT_ENCODING, "gzip");
+    }
+
+    let start = Instant::now();
+    let response = builder.send().await?;
+    let headers_ms = start.elapsed().as_secs_f64() * 1000.0;
+    let status = response.status().as_u16();
+    if status == 429 {
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse().ok())
+            .map(Duration::from_secs);
+        debug!(target: TRACE_TARGET, id, method, status, "rate limited");
+        return Err(RateLimited { retry_after }.into());
+    }
+    if status >= 500 {
+        debug!(target: TRACE_TARGET, id, method, status, "server error");
+        return Err(ServerError { status }.into());
+    }
+    let compressed = response
+        .headers()
+        .get(CONTENT_ENCODING)
+        .is_some_and(|encoding| encoding.as_bytes().eq_ignore_ascii_case(b"gzip"));
+
+    let wire = response.bytes().await?;
+    let downloaded_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    let body = if compressed {
+        let mut body = String::new();
+        GzDecoder::new(wire.as_ref()).read_to_string(&mut body)?;
+        body
+    } else {
+        String::from_utf8(wire.to_vec())?
+    };
+    let rtt_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    debug!(
+        target: TRACE_TARGET,
+        id,
+        method,
+        status,
+        rtt_ms,
+        bytes = body.len(),
+        wire_bytes = wire.len(),
+        response = truncate(&body, TRACE_BODY_LIMIT),
+        "response"
+    );
+
+    let parse_start = Instant::now();
+    let value: Value = serde_json::from_str(&body)?;
+    let parse_ms = parse_start.elapsed().as_secs_f64() * 1000.0;
+
+    if value.get("id").and_then(|v| v.as_u64()) != Some(id) {
+        warn!(target: TRACE_TARGET, id, method, "response id does not match request id");
+    }
+
+    let transfer = Transfer {
+        compressed,
+        wire_bytes: wire.len(),
+        body_bytes: body.len(),
+        headers_ms,
+        download_ms: downloaded_ms - headers_ms,
+        decode_ms: if compressed {
+            rtt_ms - downloaded_ms
+        } else {
+            0.0
+        },
+        parse_ms,
+        total_ms: start.elapsed().as_secs_f64() * 1000.0,
+    };
+
+    Ok((value, transfer))
+}
+
+/// Current slot at `commitment`. A JSON-RPC `error` is returned as `Err`.
+pub async fn get_latest_slot(
+    client: &Client,
+    rpc_url: &str,
+    commitment: Commitment,
+) -> Result<u64> {
+    let json_value = call(
+        client,
+        rpc_url,
+        "getSlot",
+        json!([{"commitment": commitment.as_str()}]),
+    )
+    .await?;
+
+    parse_slot(&json_value)
+}
+
+/// Slot of a `getSlot` response envelope; a JSON-RPC `error` or a missing
+/// or null `result` is an `Err`.
+fn parse_slot(json_value: &Value) -> Result<u64> {
+    if let Some(slot) = json_value.get("result").and_then(|v| v.as_u64()) {
+        Ok(slot)
+    } else if let Some(error) = json_value.get("error") {
+        Err(anyhow!("getSlot error: {}", error))
+    } else {
+        Err(anyhow!("Failed to get slot"))
+    }
+}
+
+/// Block time of `slot` in unix seconds, `None` while it is not available.
+///
+/// getBlockTime routinely errors for very recent slots, so a JSON-RPC
+/// `error` is treated as "not available yet" rather than a failure.
+pub async fn get_block_time(client: &Client, rpc_url: &str, slot: u64) -> Result<Option<i64>> {
+    let json_value = call(client, rpc_url, "getBlockTime", json!([slot])).await?;
+
+    Ok(json_value.get("result").and_then(|v| v.as_i64()))
+}
+
+/// The WebSocket URL for an HTTP(S) or scheme-less endpoint. Only the scheme
+/// changes, so the path and query (e.g. an existing `?api-key=`) are kept.
+pub fn websocket_url(endpoint: &str, api_key: Option<&str>) -> String {
+    let url = if let Some(rest) = endpoint.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = endpoint.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else if !endpoint.starts_with("ws://") && !endpoint.starts_with("wss://") {
+        format!("wss://{}", endpoint)
+    } else {
+        endpoint.to_string()
+    };
+    with_api_key(url, api_key)
+}
+
+/// The HTTP URL for a WebSocket or scheme-less endpoint, like [`websocket_url`].
+pub fn http_url(endpoint: &str, api_key: Option<&str>) -> String {
+    let url = if let Some(rest) = endpoint.strip_prefix("wss://") {
+        format!("https://{}", rest)
+    } else if let Some(rest) = endpoint.strip_prefix("ws://") {
+        format!("http://{}", rest)
+    } else if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+        format!("https://{}", endpoint)
+    } else {
+        endpoint.to_string()
+    };
+    with_api_key(url, api_key)
+}
+
+/// Append `api-key=<key>` to the query unless the URL already has one.
+fn with_api_key(url: String, api_key: Option<&str>) -> String {
+    let Some(api_key) = api_key else {
+        return url;
+    };
+    let query = url
+        .split_once('?')
+        .map(|(_, query)| query)
+        .unwrap_or_default();
+    if query.split('&').any(|param| param.starts_with("api-key=")) {
+        return url;
+    }
+
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{}{}api-key={}", url, separator, api_key)
+}
+
+fn truncate(s: &str, limit: usize) -> &str {
+    if s.len() <= limit {
+        return s;
+    }
+    let mut end = limit;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_slot_from_result() {
+        let response = json!({"jsonrpc": "2.0", "id": 1, "result": 312_456_789u64});
+        assert_eq!(parse_slot(&response).unwrap(), 312_456_789);
+    }
+
+    #[test]
+    fn null_slot_result_is_an_error() {
+        let response = json!({"jsonrpc": "2.0", "id": 1, "result": null});
+        assert!(parse_slot(&response).is_err());
+    }
+
+    #[test]
+    fn slot_error_object_is_reported() {
+        let response = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": {"code": -32602, "message": "Invalid params: unknown commitment"}
+        });
+        let error = parse_slot(&response).unwrap_err().to_string();
+        assert!(error.starts_with("getSlot error:"), "{}", error);
+        assert!(error.contains("-32602"), "{}", error);
+    }
+
+    #[test]
+    fn api_key_is_appended_once() {
+        assert_eq!(
+            websocket_url("https://mainnet.helius-rpc.com", Some("KEY")),
+            "wss://mainnet.helius-rpc.com?api-key=KEY"
+        );
+        assert_eq!(
+            websocket_url("https://mainnet.helius-rpc.com/?api-key=OLD", Some("KEY")),
+            "wss://mainnet.helius-rpc.com/?api-key=OLD"
+        );
+        assert_eq!(
+            http_url("wss://mainnet.helius-rpc.com/?api-key=OLD", Some("KEY")),
+            "https://mainnet.helius-rpc.com/?api-key=OLD"
+        );
+    }
+
+    #[test]
+    fn api_key_joins_other_query_parameters() {
+        assert_eq!(
+            websocket_url("https://rpc.example.com/v1?region=fra", Some("KEY")),
+            "wss://rpc.example.com/v1?region=fra&api-key=KEY"
+        );
+        assert_eq!(
+            http_url("rpc.example.com/v1?region=fra&api-key=OLD", Some("KEY")),
+            "https://rpc.example.com/v1?region=fra&api-key=OLD"
+        );
+        // Only a whole `api-key` parameter counts as already present
+        assert_eq!(
+            http_url("http://localhost:8899/?x-api-key=abc", Some("KEY")),
+            "http://localhost:8899/?x-api-key=abc&api-key=KEY"
+        );
+    }
+
+    #[test]
+    fn no_api_key_leaves_the_url_alone() {
+        assert_eq!(
+            websocket_url("http://localhost:8899/?region=fra", None),
+            "ws://localhost:8899/?region=fra"
+        );
+    }
+
+    /// Calls `retry` with an op that fails with `error` until attempt
+    /// `succeed_on`; returns the result and the number of attempts made.
+    async fn run_retry(
+        error: fn() -> anyhow::Error,
+        succeed_on: u32,
+        max_attempts: u32,
+    ) -> (Result<u32>, u32) {
+        let mut attempts = 0;
+        let result = retry(
+            || {
+                attempts += 1;
+                let attempt = attempts;
+                async move {
+                    if attempt < succeed_on {
+                        Err(error())
+                    } else {
+                        Ok(attempt)
+                    }
+                }
+            },
+            max_attempts,
+            Duration::from_millis(1),
+        )
+        .await;
+        (result, attempts)
+    }
+
+    fn timed_out() -> anyhow::Error {
+        TimedOut {
+            after: Duration::from_millis(1),
+        }
+        .into()
+    }
+
+    #[tokio::test]
+    async fn retry_succeeds_after_transient_failures() {
+        let (result, attempts) = run_retry(timed_out, 3, 3).await;
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn retry_gives_up_when_attempts_run_out() {
+        let (result, attempts) = run_retry(timed_out, 10, 3).await;
+        assert!(result.unwrap_err().is::<TimedOut>());
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn retry_does_not_repeat_a_json_rpc_error() {
+        let (result, attempts) = run_retry(|| anyhow!("getSlot error: {{}}"), 2, 3).await;
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn retry_honours_a_short_retry_after() {
+        let rate_limited = || {
+            RateLimited {
+                retry_after: Some(Duration::from_millis(1)),
+            }
+            .into()
+        };
+        let (result, attempts) = run_retry(rate_limited, 2, 3).await;
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn retry_gives_up_on_a_retry_after_beyond_the_backoff() {
+        let rate_limited = || {
+            RateLimited {
+                retry_after: Some(Duration::from_secs(30)),
+            }
+            .into()
+        };
+        let started = Instant::now();
+        let (result, attempts) = run_retry(rate_limited, 2, 3).await;
+        assert!(result.unwrap_err().is::<RateLimited>());
+        assert_eq!(attempts, 1);
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,51 @@
+//! Ctrl-C handling for the measurement loops.
+//!
+//! The first Ctrl-C asks every loop to stop at its next await point, so the
+//! run ends the same way as when its duration elapses and the summary covers
+//! whatever was collected. A second Ctrl-C aborts immediately, for when a
+//! loop is stuck in a long call.
+
+use tokio::sync::watch;
+
+/// Exit status of a forced abort (128 + SIGINT, as shells report it).
+const FORCED_EXIT_CODE: i32 = 130;
+
+/// Cloneable handle on the shutdown request; one per task.
+#[derive(Debug, Clone)]
+pub struct Shutdown(watch::Receiver<bool>);
+
+impl Shutdown {
+    /// Start listening for Ctrl-C. Must be called inside the runtime.
+    pub fn install() -> Self {
+        let (sender, receiver) = watch::channel(false);
+
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_err() {
+                return;
+            }
+            eprintln!();
+            eprintln!("🛑 Ctrl-C received, finishing with the data collected so far (Ctrl-C again to abort)");
+            let _ = sender.send(true);
+
+            if tokio::signal::ctrl_c().await.is_ok() {
+                std::process::exit(FORCED_EXIT_CODE);
+            }
+            // Keep the sender alive so waiters do not see a closed channel
+            std::future::pending::<()>().await;
+        });
+
+        Shutdown(receiver)
+    }
+
+    pub fn requested(&self) -> bool {
+        *self.0.borrow()
+    }
+
+    /// Resolves once shutdown was requested; never if the signal handler
+    /// could not be installed.
+    pub async fn wait(&mut self) {
+        if self.0.wait_for(|&requested| requested).await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Observed slot production rate of a block stream.
+//!
+//! Solana targets 400ms slots (2.5 slots/sec), but the real rate drifts with
+//! validator performance. A run whose latency rose while slots also slowed
+//! down is looking at the cluster, not the network. The rate is taken from
+//! consecutive `(slot, received_ms)` pairs of the stream, so a stalled
+//! delivery after a long gap does not read as a slow cluster.
+
+use serde::Serialize;
+
+/// 400ms per slot.
+pub const IDEAL_SLOTS_PER_SEC: f64 = 2.5;
+/// Pairs further apart than this (dropped blocks, reconnects) are not
+/// counted; the next pair starts from the later block.
+const MAX_PAIR_GAP_SLOTS: u64 = 8;
+/// Length of each sliding window whose rate feeds the standard deviation.
+const WINDOW_MS: i64 = 10_000;
+
+#[derive(Debug, Default)]
+pub struct SlotRate {
+    previous: Option<(u64, i64)>,
+    total_slots: u64,
+    total_ms: i64,
+    window_slots: u64,
+    window_ms: i64,
+    window_rates: Vec<f64>,
+    /// Pairs left out for a gap or a regression.
+    ignored_pairs: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SlotRateSummary {
+    /// Slots per second over every counted pair.
+    pub observed_slot_rate: Option<f64>,
+    /// Standard deviation of the per-window rates.
+    pub slot_rate_stddev: Option<f64>,
+    /// `observed_slot_rate` relative to 2.5 slots/sec, in percent.
+    pub deviation_percent: Option<f64>,
+    pub windows: usize,
+    pub ignored_pairs: u64,
+}
+
+impl SlotRate {
+    pub fn record(&mut self, slot: u64, received_ms: i64) {
+        let previous = self.previous.replace((slot, received_ms));
+        let Some((previous_slot, previous_ms)) = previous else {
+            return;
+        };
+        if slot <= previous_slot || slot - previous_slot > MAX_PAIR_GAP_SLOTS {
+            self.ignored_pairs += 1;
+            // A regression keeps the later slot as the reference
+            if slot <= previous_slot {
+                self.previous = previous;
+            }
+            return;
+        }
+
+        let (slots, elapsed_ms) = (slot - previous_slot, (received_ms - previous_ms).max(0));
+        self.total_slots += slots;
+        self.total_ms += elapsed_ms;
+        self.window_slots += slots;
+        self.window_ms += elapsed_ms;
+
+        if self.window_ms >= WINDOW_MS {
+            self.window_rates
+                .push(self.window_slots as f64 * 1000.0 / self.window_ms as f64);
+            self.window_slots = 0;
+            self.window_ms = 0;
+        }
+    }
+
+    pub fn summary(&self) -> SlotRateSummary {
+        let observed_slot_rate =
+            (self.total_ms > 0).then(|| self.total_slots as f64 * 1000.0 / self.total_ms as f64);
+
+        let windows = self.window_rates.len();
+        let slot_rate_stddev = (windows > 1).then(|| {
+            let mean = self.window_rates.iter().sum::<f64>() / windows as f64;
+            let variance = self
+                .window_rates
+                .iter()
+                .map(|r| (r - mean).powi(2))
+                .sum::<f64>()
+                / windows as f64;
+            variance.sqrt()
+        });
+
+        SlotRateSummary {
+            observed_slot_rate,
+            slot_rate_stddev,
+            deviation_percent: observed_slot_rate
+                .map(|rate| (rate / IDEAL_SLOTS_PER_SEC - 1.0) * 100.0),
+            windows,
+            ignored_pairs: self.ignored_pairs,
+        }
+    }
+
+    pub fn print(&self) {
+        let summary = self.summary();
+
+        println!(
+            "⏲️  Slot rate (stream arrival, {}s windows):",
+            WINDOW_MS / 1000
+        );
+        match (summary.observed_slot_rate, summary.deviation_percent) {
+            (Some(rate), Some(deviation)) => println!(
+                "Observed: {:.3} slots/sec ({:+.1}% vs {} ideal, {:.0}ms/slot)",
+                rate,
+                deviation,
+                IDEAL_SLOTS_PER_SEC,
+                1000.0 / rate
+            ),
+            _ => println!("Observed: n/a (not enough consecutive blocks)"),
+        }
+        if let Some(stddev) = summary.slot_rate_stddev {
+            println!(
+                "Std deviation across {} windows: {:.3} slots/sec",
+                summary.windows, stddev
+            );
+        }
+        if summary.ignored_pairs > 0 {
+            println!(
+                "{} block pairs more than {} slots apart or out of order were not counted",
+                summary.ignored_pairs, MAX_PAIR_GAP_SLOTS
+            );
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Silent periods of a block feed.
+//!
+//! A provider can stop sending without closing the connection, which leaves
+//! a read loop waiting forever. [`StallDetector`] tracks the time since the
+//! last block; a gap longer than the threshold is a stall. The block that
+//! ends a stall may have been held back by it, so callers keep it out of the
+//! latency statistics.
+
+use serde::Serialize;
+use std::time::Duration;
+use tokio::time::{self, Instant};
+
+#[derive(Debug)]
+pub struct StallDetector {
+    threshold: Duration,
+    /// `None` until the first block, so connecting is not a stall.
+    last_block: Option<Instant>,
+    /// A stall has been reported and not yet ended by a block.
+    stalled: bool,
+    stalls: Vec<Duration>,
+}
+
+/// Stalls of one run.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct StallStats {
+    pub threshold_ms: u64,
+    pub count: u64,
+    pub total_ms: u64,
+    pub longest_ms: u64,
+}
+
+impl StallDetector {
+    pub fn new(threshold_ms: u64) -> Self {
+        StallDetector {
+            threshold: Duration::from_millis(threshold_ms),
+            last_block: None,
+            stalled: false,
+            stalls: Vec::new(),
+        }
+    }
+
+    /// Wait until the feed has been silent for the threshold. Resolves once
+    /// per stall; pending before the first block and during a stall.
+    pub async fn wait(&self) {
+        match self.last_block {
+            Some(last) if !self.stalled => time::sleep_until(last + self.threshold).await,
+            _ => std::future::pending().await,
+        }
+    }
+
+    /// Mark the stall [`wait`](Self::wait) reported; returns how long the
+    /// feed has been silent.
+    pub fn begin(&mut self) -> Duration {
+        self.stalled = true;
+        self.last_block
+            .map_or(Duration::ZERO, |last| last.elapsed())
+    }
+
+    /// Record a block arrival. Returns the gap when it ends a stall.
+    pub fn block(&mut self) -> Option<Duration> {
+        let now = Instant::now();
+        let gap = self.last_block.map(|last| now - last);
+        self.last_block = Some(now);
+        self.stalled = false;
+
+        let gap = gap.filter(|gap| *gap > self.threshold)?;
+        self.stalls.push(gap);
+        Some(gap)
+    }
+
+    /// Count a stall still running at the end of the run.
+    pub fn finish(&mut self) {
+        if self.stalled {
+            if let Some(last) = self.last_block {
+                self.stalls.push(last.elapsed());
+            }
+            self.stalled = false;
+        }
+    }
+
+    pub fn stats(&self) -> StallStats {
+        StallStats {
+            threshold_ms: self.threshold.as_millis() as u64,
+            count: self.stalls.len() as u64,
+            total_ms: self.stalls.iter().sum::<Duration>().as_millis() as u64,
+            longest_ms: self
+                .stalls
+                .iter()
+                .max()
+                .map_or(0, |longest| longest.as_millis() as u64),
+        }
+    }
+
+    pub fn print(&self) {
+        let stats = self.stats();
+        if stats.count == 0 {
+            return;
+        }
+        println!(
+            "🧊 {} feed stalls over {}ms: {}ms silent in total, longest {}ms",
+            stats.count, stats.threshold_ms, stats.total_ms, stats.longest_ms
+        );
+    }
+}
@@ -0,0 +1,503 @@
+//! Latency summary statistics shared by every tool.
+//!
+//! The tools used to compute percentiles inline with slightly different index
+//! formulas (some clamped, some not), so the same samples could produce a
+//! different P99 depending on which binary printed it, and small or empty
+//! sample sets could index out of bounds. Everything now goes through
+//! [`percentile`], which is defined for every input.
+
+use serde::Serialize;
+
+/// Human-readable form of [`percentile`], recorded in the methodology report.
+pub const PERCENTILE_DEFINITION: &str =
+    "linear interpolation between closest ranks at h = (n - 1) * q, rounded to the nearest ms";
+
+/// Percentile of an ascending slice, interpolating linearly between the two
+/// closest ranks. `q` is clamped to `0.0..=1.0`; `None` for an empty slice.
+pub fn percentile(sorted: &[i64], q: f64) -> Option<i64> {
+    let last = sorted.len().checked_sub(1)?;
+    let h = last as f64 * q.clamp(0.0, 1.0);
+    let lower = h.floor() as usize;
+    let upper = (lower + 1).min(last);
+    let fraction = h - lower as f64;
+
+    let value = sorted[lower] as f64 + fraction * (sorted[upper] - sorted[lower]) as f64;
+    Some(value.round() as i64)
+}
+
+/// Percentiles, in percent, that the summaries print; parsed from
+/// `50,90,99,99.9`.
+#[derive(Debug, Clone)]
+pub struct Percentiles(pub Vec<f64>);
+
+impl std::str::FromStr for Percentiles {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let values = s
+            .split(',')
+            .map(|v| v.trim().parse::<f64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("Invalid percentiles '{}': {}", s, e))?;
+
+        if values.is_empty() || values.iter().any(|p| !(0.0..=100.0).contains(p)) {
+            return Err(anyhow::anyhow!(
+                "Percentiles must be between 0 and 100, got '{}'",
+                s
+            ));
+        }
+
+        Ok(Percentiles(values))
+    }
+}
+
+impl Percentiles {
+    /// `("P99.9", value)` for each requested percentile of `samples`, which
+    /// need not be sorted. Empty when there are no samples. Goes through
+    /// [`percentile`], so P99.9 of a handful of samples interpolates towards
+    /// the max instead of indexing past the end.
+    pub fn of(&self, samples: &[i64]) -> Vec<(String, i64)> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        self.0
+            .iter()
+            .filter_map(|&p| Some((format!("P{}", p), percentile(&sorted, p / 100.0)?)))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencyStats {
+    pub count: usize,
+    pub avg_ms: f64,
+    pub min_ms: i64,
+    pub max_ms: i64,
+    pub p50_ms: i64,
+    pub p90_ms: i64,
+    pub p95_ms: i64,
+    pub p99_ms: i64,
+    /// Population standard deviation.
+    pub std_dev_ms: f64,
+    /// Mean absolute difference between consecutive samples, in the order
+    /// they were passed in (arrival order for every caller).
+    pub jitter_ms: f64,
+}
+
+impl LatencyStats {
+    /// Summarize samples in arrival order; `None` when there are none. Only
+    /// [`LatencyStats::jitter_ms`] depends on the order.
+    pub fn from_samples(samples: &[i64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let count = sorted.len();
+        let avg_ms = sorted.iter().sum::<i64>() as f64 / count as f64;
+        let variance = sorted
+            .iter()
+            .map(|&s| (s as f64 - avg_ms).powi(2))
+            .sum::<f64>()
+            / count as f64;
+        let jitter_ms = if count > 1 {
+            samples.windows(2).map(|w| (w[1] - w[0]).abs()).sum::<i64>() as f64 / (count - 1) as f64
+        } else {
+            0.0
+        };
+
+        Some(LatencyStats {
+            count,
+            avg_ms,
+            min_ms: sorted[0],
+            max_ms: sorted[count - 1],
+            p50_ms: percentile(&sorted, 0.5)?,
+            p90_ms: percentile(&sorted, 0.9)?,
+            p95_ms: percentile(&sorted, 0.95)?,
+            p99_ms: percentile(&sorted, 0.99)?,
+            std_dev_ms: variance.sqrt(),
+            jitter_ms,
+        })
+    }
+}
+
+/// Pearson correlation coefficient of paired samples; `None` for fewer than
+/// two pairs or when either side is constant.
+pub fn pearson(pairs: &[(i64, i64)]) -> Option<f64> {
+    if pairs.len() < 2 {
+        return None;
+    }
+
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|&(x, _)| x as f64).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|&(_, y)| y as f64).sum::<f64>() / n;
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for &(x, y) in pairs {
+        let (dx, dy) = (x as f64 - mean_x, y as f64 - mean_y);
+        cov += dx * dy;
+        var_x += dx * dx;
+        var_y += dy * dy;
+    }
+
+    (var_x > 0.0 && var_y > 0.0).then(|| cov / (var_x * var_y).sqrt())
+}
+
+/// Latency of the samples whose key falls in one decile of the keys.
+#[derive(Debug, Clone, Serialize)]
+pub struct DecileStats {
+    /// 1 for the smallest keys.
+    pub decile: usize,
+    pub min_key: i64,
+    pub max_key: i64,
+    #[serde(flatten)]
+    pub stats: LatencyStats,
+}
+
+/// Split `(key, latency_ms)` samples into ten equally sized groups by key.
+/// With fewer than ten samples some deciles are empty and omitted.
+pub fn by_decile(samples: &[(i64, i64)]) -> Vec<DecileStats> {
+    let mut sorted = samples.to_vec();
+    sorted.sort_by_key(|&(key, _)| key);
+    let count = sorted.len();
+
+    (0..10)
+        .filter_map(|i| {
+            let group = &sorted[i * count / 10..(i + 1) * count / 10];
+            let latencies: Vec<i64> = group.iter().map(|&(_, latency)| latency).collect();
+            Some(DecileStats {
+                decile: i + 1,
+                min_key: group.first()?.0,
+                max_key: group.last()?.0,
+                stats: LatencyStats::from_samples(&latencies)?,
+            })
+        })
+        .collect()
+}
+
+/// Tukey's fence multiplier: samples beyond 1.5 IQR from the quartiles are outliers.
+const IQR_FENCE: f64 = 1.5;
+
+/// Spike-resistant companions to [`LatencyStats`], for runs where a few GC
+/// pauses or network blips dominate the average and max.
+#[derive(Debug, Clone, Serialize)]
+pub struct OutlierStats {
+    /// Share of samples dropped from each end, clamped to `0..=50`.
+    pub trim_percent: f64,
+    /// Mean of the samples left after trimming.
+    pub trimmed_avg_ms: f64,
+    pub trimmed_count: usize,
+    /// `P25 - 1.5 * IQR`.
+    pub lower_fence_ms: f64,
+    /// `P75 + 1.5 * IQR`.
+    pub upper_fence_ms: f64,
+    /// Samples outside the fences. They are flagged only, never removed.
+    pub outliers: usize,
+}
+
+impl OutlierStats {
+    /// Trim and flag a sorted copy of `samples`; `None` when there are none.
+    pub fn from_samples(samples: &[i64], trim_percent: f64) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let count = sorted.len();
+
+        let trim_percent = trim_percent.clamp(0.0, 50.0);
+        // Always keep at least the median
+        let trim =
+            ((count as f64 * trim_percent / 100.0).floor() as usize).min(count.checked_sub(1)? / 2);
+        let trimmed = &sorted[trim..count - trim];
+        let trimmed_avg_ms = trimmed.iter().sum::<i64>() as f64 / trimmed.len() as f64;
+
+        let q1 = percentile(&sorted, 0.25)? as f64;
+        let q3 = percentile(&sorted, 0.75)? as f64;
+        let lower_fence_ms = q1 - IQR_FENCE * (q3 - q1);
+        let upper_fence_ms = q3 + IQR_FENCE * (q3 - q1);
+        let outliers = sorted
+            .iter()
+            .filter(|&&s| (s as f64) < lower_fence_ms || (s as f64) > upper_fence_ms)
+            .count();
+
+        Some(OutlierStats {
+            trim_percent,
+            trimmed_avg_ms,
+            trimmed_count: trimmed.len(),
+            lower_fence_ms,
+            upper_fence_ms,
+            outliers,
+        })
+    }
+}
+
+/// Ascending bucket edges in ms for [`histogram`], parsed from `0,250,500,...`.
+#[derive(Debug, Clone)]
+pub struct HistogramBuckets(pub Vec<i64>);
+
+impl std::str::FromStr for HistogramBuckets {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let edges = s
+            .split(',')
+            .map(|v| v.trim().parse::<i64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("Invalid histogram buckets '{}': {}", s, e))?;
+
+        if edges.is_empty() || edges.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(anyhow::anyhow!(
+                "Histogram bucket edges must be strictly increasing, got '{}'",
+                s
+            ));
+        }
+
+        Ok(HistogramBuckets(edges))
+    }
+}
+
+/// One histogram bucket covering `[lower_ms, upper_ms)`; `None` is unbounded.
+#[derive(Debug, Clone, Serialize)]
+pub struct HistogramBucket {
+    pub lower_ms: Option<i64>,
+    pub upper_ms: Option<i64>,
+    pub count: usize,
+}
+
+/// Count samples per bucket. Samples below the first edge or at/above the
+/// last one land in open-ended buckets, so every sample is counted once.
+pub fn histogram(samples: &[i64], buckets: &HistogramBuckets) -> Vec<HistogramBucket> {
+    let edges = &buckets.0;
+    let mut bounds = vec![(None, edges.first().copied())];
+    bounds.extend(edges.windows(2).map(|w| (Some(w[0]), Some(w[1]))));
+    bounds.push((edges.last().copied(), None));
+
+    bounds
+        .into_iter()
+        .map(|(lower_ms, upper_ms)| HistogramBucket {
+            lower_ms,
+            upper_ms,
+            count: samples
+                .iter()
+                .filter(|&&s| lower_ms.is_none_or(|l| s >= l) && upper_ms.is_none_or(|u| s < u))
+                .count(),
+        })
+        .collect()
+}
+
+/// Write `bucket_lower_ms,bucket_upper_ms,count` rows; unbounded edges are empty.
+pub fn write_histogram_csv(
+    path: &str,
+    samples: &[i64],
+    buckets: &HistogramBuckets,
+) -> anyhow::Result<()> {
+    use std::io::Write;
+
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+    writeln!(writer, "bucket_lower_ms,bucket_upper_ms,count")?;
+
+    let cell = |edge: Option<i64>| edge.map(|e| e.to_string()).unwrap_or_default();
+    for bucket in histogram(samples, buckets) {
+        writeln!(
+            writer,
+            "{},{},{}",
+            cell(bucket.lower_ms),
+            cell(bucket.upper_ms),
+            bucket.count
+        )?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Target number of buckets when `--bucket-ms` is not given.
+const ASCII_HISTOGRAM_BUCKETS: i64 = 15;
+/// Length of the bar of the fullest bucket.
+const ASCII_HISTOGRAM_WIDTH: usize = 40;
+
+/// Bucket width splitting `[min_ms, max_ms]` into about
+/// [`ASCII_HISTOGRAM_BUCKETS`] buckets, rounded up to 1, 2 or 5 times a
+/// power of ten so the edges read well.
+pub fn auto_bucket_ms(min_ms: i64, max_ms: i64) -> i64 {
+    let raw = ((max_ms - min_ms) / ASCII_HISTOGRAM_BUCKETS).max(1);
+    let mut magnitude = 1;
+    loop {
+        for step in [1, 2, 5] {
+            if step * magnitude >= raw {
+                return step * magnitude;
+            }
+        }
+        magnitude *= 10;
+    }
+}
+
+/// Print the distribution as one bar per `bucket_ms` wide bucket from the
+/// lowest to the highest sample. Shows at a glance what the percentiles
+/// hide, such as a bimodal run.
+pub fn print_ascii_histogram(samples: &[i64], bucket_ms: Option<i64>) {
+    let (Some(&min_ms), Some(&max_ms)) = (samples.iter().min(), samples.iter().max()) else {
+        return;
+    };
+    let width = bucket_ms
+        .unwrap_or_else(|| auto_bucket_ms(min_ms, max_ms))
+        .max(1);
+
+    let first_edge = min_ms.div_euclid(width) * width;
+    let edges: Vec<i64> = (0..)
+        .map(|i| first_edge + i * width)
+        .take_while(|&edge| edge <= max_ms + width)
+        .collect();
+    // Edges span every sample, so only the bounded buckets can be non-empty
+    let buckets: Vec<HistogramBucket> = histogram(samples, &HistogramBuckets(edges))
+        .into_iter()
+        .filter(|b| b.lower_ms.is_some() && b.upper_ms.is_some())
+        .collect();
+    let fullest = buckets.iter().map(|b| b.count).max().unwrap_or(0).max(1);
+
+    println!("📶 Latency histogram ({}ms buckets):", width);
+    for bucket in &buckets {
+        let bar_len = (bucket.count * ASCII_HISTOGRAM_WIDTH).div_ceil(fullest);
+        println!(
+            "{:>6}-{:<6}ms | {:<bar_width$} {} ({:.1}%)",
+            bucket.lower_ms.unwrap_or_default(),
+            bucket.upper_ms.unwrap_or_default(),
+            "█".repeat(bar_len),
+            bucket.count,
+            bucket.count as f64 / samples.len() as f64 * 100.0,
+            bar_width = ASCII_HISTOGRAM_WIDTH
+        );
+    }
+}
+
+/// Summary of the samples received during one UTC hour of the day.
+#[derive(Debug, Clone, Serialize)]
+pub struct HourlyStats {
+    pub hour_utc: u32,
+    #[serde(flatten)]
+    pub stats: LatencyStats,
+}
+
+/// Group `(received_ms, latency_ms)` samples by the UTC hour they were
+/// received in. Runs spanning several days fold into the same 24 buckets;
+/// hours without samples are omitted.
+pub fn by_hour(samples: &[(i64, i64)]) -> Vec<HourlyStats> {
+    let mut hours: std::collections::BTreeMap<u32, Vec<i64>> = Default::default();
+    for &(received_ms, latency_ms) in samples {
+        let hour = received_ms.div_euclid(3_600_000).rem_euclid(24) as u32;
+        hours.entry(hour).or_default().push(latency_ms);
+    }
+
+    hours
+        .into_iter()
+        .filter_map(|(hour_utc, latencies)| {
+            LatencyStats::from_samples(&latencies).map(|stats| HourlyStats { hour_utc, stats })
+        })
+        .collect()
+}
+
+pub fn print_by_hour(hourly: &[HourlyStats]) {
+    println!("🕐 Latency by hour of day (UTC):");
+    println!("Hour  | Samples | Avg      | P95");
+    println!("{}", "-".repeat(36));
+    for hour in hourly {
+        println!(
+            "{:02}:00 | {:>7} | {:>6.1}ms | {}ms",
+            hour.hour_utc, hour.stats.count, hour.stats.avg_ms, hour.stats.p95_ms
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{BenchmarkReport, Measurement};
+
+    /// A run of 20 samples in arrival order, with two spikes in the tail.
+    const GOLDEN: [i64; 20] = [
+        412, 388, 404, 951, 397, 420, 376, 433, 402, 391, 415, 1870, 399, 384, 426, 408, 393, 417,
+        380, 410,
+    ];
+
+    fn assert_golden(stats: &LatencyStats) {
+        assert_eq!(stats.count, 20);
+        assert!((stats.avg_ms - 503.8).abs() < 1e-9, "avg {}", stats.avg_ms);
+        assert_eq!(stats.min_ms, 376);
+        assert_eq!(stats.max_ms, 1870);
+        assert_eq!(stats.p50_ms, 406);
+        assert_eq!(stats.p90_ms, 485);
+        assert_eq!(stats.p95_ms, 997);
+        assert_eq!(stats.p99_ms, 1695);
+    }
+
+    #[test]
+    fn every_summary_path_agrees_on_the_golden_run() {
+        let mut sorted = GOLDEN.to_vec();
+        sorted.sort();
+        assert_eq!(percentile(&sorted, 0.5), Some(406));
+        assert_eq!(percentile(&sorted, 0.95), Some(997));
+        assert_eq!(percentile(&sorted, 0.99), Some(1695));
+
+        assert_eq!(
+            Percentiles(vec![50.0, 95.0, 99.0]).of(&GOLDEN),
+            vec![
+                ("P50".to_string(), 406),
+                ("P95".to_string(), 997),
+                ("P99".to_string(), 1695)
+            ]
+        );
+
+        assert_golden(&LatencyStats::from_samples(&GOLDEN).unwrap());
+        let reversed: Vec<i64> = GOLDEN.iter().rev().copied().collect();
+        assert_golden(&LatencyStats::from_samples(&reversed).unwrap());
+
+        let same_hour: Vec<(i64, i64)> = GOLDEN.iter().map(|&latency| (0, latency)).collect();
+        let hourly = by_hour(&same_hour);
+        assert_eq!(hourly.len(), 1);
+        assert_golden(&hourly[0].stats);
+
+        let measurements = GOLDEN
+            .iter()
+            .enumerate()
+            .map(|(i, &latency_ms)| Measurement {
+                slot: i as u64,
+                received_ms: 0,
+                latency_ms,
+                created_at_ms: None,
+            })
+            .collect();
+        let report = BenchmarkReport::new("golden", "http://localhost", measurements, 0, 0);
+        assert_golden(report.stats.as_ref().unwrap());
+    }
+
+    #[test]
+    fn percentile_of_no_samples_is_none() {
+        assert_eq!(percentile(&[], 0.5), None);
+        assert!(LatencyStats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn percentile_of_one_sample_is_that_sample() {
+        for q in [0.0, 0.5, 0.95, 0.99, 1.0] {
+            assert_eq!(percentile(&[250], q), Some(250));
+        }
+    }
+
+    #[test]
+    fn percentile_of_two_samples_interpolates() {
+        assert_eq!(percentile(&[100, 200], 0.0), Some(100));
+        assert_eq!(percentile(&[100, 200], 0.25), Some(125));
+        assert_eq!(percentile(&[100, 200], 0.5), Some(150));
+        assert_eq!(percentile(&[100, 200], 0.95), Some(195));
+        assert_eq!(percentile(&[100, 200], 1.0), Some(200));
+    }
+
+    #[test]
+    fn percentile_of_hundred_samples_interpolates_and_clamps() {
+        let sorted: Vec<i64> = (0..100).map(|i| i * 10).collect();
+        assert_eq!(percentile(&sorted, 0.5), Some(495));
+        assert_eq!(percentile(&sorted, 0.9), Some(891));
+        assert_eq!(percentile(&sorted, 0.99), Some(980));
+        assert_eq!(percentile(&sorted, 1.0), Some(990));
+        assert_eq!(percentile(&sorted, 1.5), Some(990));
+        assert_eq!(percentile(&sorted, -0.5), Some(0));
+    }
+}
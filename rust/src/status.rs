@@ -0,0 +1,193 @@
+//! Latency status bands shared by every tool.
+//!
+//! Each tool used to hard-code its own EXCELLENT / GOOD / FAIR / SLOW ladder.
+//! The per-methodology defaults below keep those historical bands; a
+//! `--status-thresholds` flag or a `status_thresholds` entry in the shared
+//! config overrides them so the labels can match your own SLA.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LatencyStatus {
+    Excellent,
+    Good,
+    Fair,
+    Slow,
+}
+
+impl LatencyStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            LatencyStatus::Excellent => "🟢 EXCELLENT",
+            LatencyStatus::Good => "🟡 GOOD",
+            LatencyStatus::Fair => "🟠 FAIR",
+            LatencyStatus::Slow => "🔴 SLOW",
+        }
+    }
+
+    /// Lowercase name used in JSON output.
+    pub fn name(self) -> &'static str {
+        match self {
+            LatencyStatus::Excellent => "excellent",
+            LatencyStatus::Good => "good",
+            LatencyStatus::Fair => "fair",
+            LatencyStatus::Slow => "slow",
+        }
+    }
+}
+
+/// Upper bounds (exclusive) of the excellent / good / fair bands; anything at
+/// or above `fair_ms` is slow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusThresholds {
+    pub excellent_ms: i64,
+    pub good_ms: i64,
+    pub fair_ms: i64,
+}
+
+impl StatusThresholds {
+    /// Push-based block streams (laserstream_benchmark, racing_comparison).
+    pub const BLOCK_STREAM: StatusThresholds = StatusThresholds {
+        excellent_ms: 900,
+        good_ms: 1200,
+        fair_ms: 2000,
+    };
+
+    /// Per-method block propagation (latency_calculator).
+    pub const BLOCK_PROPAGATION: StatusThresholds = StatusThresholds {
+        excellent_ms: 500,
+        good_ms: 1000,
+        fair_ms: 2000,
+    };
+
+    /// HTTP slot polling (rpc_latency_test).
+    pub const RPC_POLLING: StatusThresholds = StatusThresholds {
+        excellent_ms: 300,
+        good_ms: 1000,
+        fair_ms: 3000,
+    };
+
+    pub fn new(excellent_ms: i64, good_ms: i64, fair_ms: i64) -> Result<Self> {
+        if !(0 < excellent_ms && excellent_ms < good_ms && good_ms < fair_ms) {
+            return Err(anyhow!(
+                "Status thresholds must be positive and strictly increasing, got {},{},{}",
+                excellent_ms,
+                good_ms,
+                fair_ms
+            ));
+        }
+
+        Ok(StatusThresholds {
+            excellent_ms,
+            good_ms,
+            fair_ms,
+        })
+    }
+
+    /// Flag beats config, config beats the methodology default.
+    pub fn resolve(
+        flag: Option<StatusThresholds>,
+        config: Option<StatusThresholds>,
+        default: StatusThresholds,
+    ) -> Result<StatusThresholds> {
+        match flag.or(config) {
+            // Config values bypass `new`, so validate them here
+            Some(t) => StatusThresholds::new(t.excellent_ms, t.good_ms, t.fair_ms),
+            None => Ok(default),
+        }
+    }
+
+    pub fn status(&self, latency_ms: i64) -> LatencyStatus {
+        if latency_ms < self.excellent_ms {
+            LatencyStatus::Excellent
+        } else if latency_ms < self.good_ms {
+            LatencyStatus::Good
+        } else if latency_ms < self.fair_ms {
+            LatencyStatus::Fair
+        } else {
+            LatencyStatus::Slow
+        }
+    }
+
+    /// Status of an averaged latency.
+    pub fn status_f64(&self, latency_ms: f64) -> LatencyStatus {
+        self.status(latency_ms.floor() as i64)
+    }
+
+    pub fn label(&self, latency_ms: i64) -> &'static str {
+        self.status(latency_ms).label()
+    }
+}
+
+/// Parses `EXCELLENT,GOOD,FAIR`, e.g. `500,1000,2000`.
+impl FromStr for StatusThresholds {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let values = s
+            .split(',')
+            .map(|v| v.trim().parse::<i64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow!("Invalid status thresholds '{}': {}", s, e))?;
+
+        match values.as_slice() {
+            [excellent, good, fair] => StatusThresholds::new(*excellent, *good, *fair),
+            _ => Err(anyhow!(
+                "Expected three comma-separated thresholds (excellent,good,fair), got '{}'",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for StatusThresholds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "excellent <{}ms, good <{}ms, fair <{}ms",
+            self.excellent_ms, self.good_ms, self.fair_ms
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_threshold_is_the_exclusive_upper_bound_of_its_band() {
+        let thresholds = StatusThresholds::BLOCK_STREAM;
+        let cases = [
+            (0, LatencyStatus::Excellent),
+            (899, LatencyStatus::Excellent),
+            (900, LatencyStatus::Good),
+            (901, LatencyStatus::Good),
+            (1199, LatencyStatus::Good),
+            (1200, LatencyStatus::Fair),
+            (1201, LatencyStatus::Fair),
+            (1999, LatencyStatus::Fair),
+            (2000, LatencyStatus::Slow),
+            (2001, LatencyStatus::Slow),
+        ];
+        for (latency_ms, expected) in cases {
+            assert_eq!(thresholds.status(latency_ms), expected, "{}ms", latency_ms);
+        }
+    }
+
+    #[test]
+    fn averages_are_floored_before_banding() {
+        let thresholds = StatusThresholds::BLOCK_STREAM;
+        assert_eq!(thresholds.status_f64(899.9), LatencyStatus::Excellent);
+        assert_eq!(thresholds.status_f64(900.0), LatencyStatus::Good);
+    }
+
+    #[test]
+    fn thresholds_must_be_strictly_increasing() {
+        assert!("500,1000,2000".parse::<StatusThresholds>().is_ok());
+        assert!("500,500,2000".parse::<StatusThresholds>().is_err());
+        assert!("0,1000,2000".parse::<StatusThresholds>().is_err());
+    }
+}
@@ -0,0 +1,82 @@
+//! When a benchmark run ends: `--duration`, `--blocks`, or whichever of the
+//! two is reached first.
+
+use anyhow::{anyhow, Result};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+pub struct StopCondition {
+    start: Instant,
+    duration: Option<Duration>,
+    blocks: Option<u64>,
+}
+
+impl StopCondition {
+    /// Start the clock now. At least one of the limits must be set.
+    pub fn new(duration_minutes: Option<u64>, blocks: Option<u64>) -> Result<Self> {
+        Self::with_duration(
+            duration_minutes.map(|m| Duration::from_secs(m * 60)),
+            blocks,
+        )
+    }
+
+    /// [`StopCondition::new`] for a duration that need not be whole minutes.
+    pub fn with_duration(duration: Option<Duration>, blocks: Option<u64>) -> Result<Self> {
+        if duration.is_none() && blocks.is_none() {
+            return Err(anyhow!("Set --duration, --blocks or both"));
+        }
+        Ok(StopCondition {
+            start: Instant::now(),
+            duration,
+            blocks,
+        })
+    }
+
+    /// True until `blocks` reaches the block limit or the duration has passed.
+    pub fn running(&self, blocks: u64) -> bool {
+        self.blocks.is_none_or(|limit| blocks < limit)
+            && self.duration.is_none_or(|d| self.start.elapsed() < d)
+    }
+
+    /// Time left before the duration limit; `None` without one.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.duration
+            .map(|d| d.saturating_sub(self.start.elapsed()))
+    }
+
+    pub fn duration_minutes(&self) -> Option<u64> {
+        self.duration.map(|d| d.as_secs() / 60)
+    }
+
+    pub fn blocks(&self) -> Option<u64> {
+        self.blocks
+    }
+
+    /// `12/100 blocks` with a block limit, `12 blocks` without.
+    pub fn progress(&self, blocks: u64) -> String {
+        match self.blocks {
+            Some(limit) => format!("{}/{} blocks", blocks, limit),
+            None => format!("{} blocks", blocks),
+        }
+    }
+}
+
+impl fmt::Display for StopCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let duration = self.duration.map(|d| match d.as_secs() {
+            secs if secs % 60 == 0 => format!("{} minutes", secs / 60),
+            secs => format!("{} seconds", secs),
+        });
+        match (duration, self.blocks) {
+            (Some(duration), Some(blocks)) => write!(
+                f,
+                "{} or {} blocks, whichever comes first",
+                duration, blocks
+            ),
+            (Some(duration), None) => write!(f, "{}", duration),
+            (None, Some(blocks)) => write!(f, "{} blocks", blocks),
+            (None, None) => unreachable!("StopCondition::new requires a limit"),
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! SQLite history of individual measurements (`--db <path>`).
+//!
+//! Every tool run with `--db` appends its samples to the same file, so
+//! latency can be compared across days instead of only within one run. Rows
+//! carry the run they came from (its start time) and the region label, when
+//! the tool knows one. The schema is versioned with `PRAGMA user_version`
+//! and migrated forward on open, so older databases keep working.
+
+use crate::clock;
+use crate::stats::LatencyStats;
+use crate::types::{BlockLatencyData, SlotLatency};
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+/// Applied in order; the database's `user_version` is the number applied.
+const MIGRATIONS: &[&str] = &["CREATE TABLE measurements (
+        id          INTEGER PRIMARY KEY,
+        run_id      INTEGER NOT NULL,
+        source      TEXT    NOT NULL,
+        region      TEXT,
+        slot        INTEGER NOT NULL,
+        received_ms INTEGER NOT NULL,
+        latency_ms  INTEGER NOT NULL
+    );
+    CREATE INDEX measurements_source_slot ON measurements (source, slot);
+    CREATE INDEX measurements_source_received ON measurements (source, received_ms);"];
+
+pub struct MeasurementStore {
+    conn: Connection,
+    /// Start of this run in unix ms, shared by every row it writes.
+    run_id: i64,
+}
+
+impl MeasurementStore {
+    /// Open or create the database and bring its schema up to date.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let conn = Connection::open(path)
+            .map_err(|e| anyhow!("Cannot open database {}: {}", path.display(), e))?;
+        // Samples arrive one at a time; WAL keeps each commit cheap
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        Self::with_connection(conn)
+    }
+
+    fn with_connection(mut conn: Connection) -> Result<Self> {
+        migrate(&mut conn)?;
+
+        Ok(MeasurementStore {
+            conn,
+            run_id: clock::now_ms(),
+        })
+    }
+
+    /// A block streamed from `source` (e.g. `"laserstream"`) in `region`.
+    pub fn insert_block_latency(
+        &self,
+        block: &BlockLatencyData,
+        source: &str,
+        region: &str,
+    ) -> Result<()> {
+        self.insert(
+            source,
+            Some(region),
+            block.slot,
+            block.received_ms,
+            block.propagation_latency_ms,
+        )
+    }
+
+    /// A slot detected by polling RPC; stored with source `"rpc"` and no
+    /// region.
+    pub fn insert_slot_latency(&self, slot: &SlotLatency) -> Result<()> {
+        self.insert("rpc", None, slot.slot, slot.detected_time, slot.latency_ms)
+    }
+
+    fn insert(
+        &self,
+        source: &str,
+        region: Option<&str>,
+        slot: u64,
+        received_ms: i64,
+        latency_ms: i64,
+    ) -> Result<()> {
+        self.conn
+            .prepare_cached(
+                "INSERT INTO measurements (run_id, source, region, slot, received_ms, latency_ms)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?
+            .execute(params![
+                self.run_id,
+                source,
+                region,
+                slot as i64,
+                received_ms,
+                latency_ms
+            ])?;
+        Ok(())
+    }
+
+    /// Summary of everything `source` recorded on `day` (UTC), across runs,
+    /// in arrival order. `None` when nothing was recorded.
+    pub fn daily_percentiles(&self, source: &str, day: NaiveDate) -> Result<Option<LatencyStats>> {
+        let start_ms = day
+            .and_hms_opt(0, 0, 0)
+            .map(|t| t.and_utc().timestamp_millis())
+            .ok_or_else(|| anyhow!("Invalid day {}", day))?;
+        let end_ms = start_ms + 86_400_000;
+
+        let mut statement = self.conn.prepare_cached(
+            "SELECT latency_ms FROM measurements
+             WHERE source = ?1 AND received_ms >= ?2 AND received_ms < ?3
+             ORDER BY received_ms",
+        )?;
+        let latencies = statement
+            .query_map(params![source, start_ms, end_ms], |row| {
+                row.get::<_, i64>(0)
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(LatencyStats::from_samples(&latencies))
+    }
+}
+
+fn migrate(conn: &mut Connection) -> Result<()> {
+    let version: usize = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    if version > MIGRATIONS.len() {
+        return Err(anyhow!(
+            "Database schema version {} is newer than this build supports ({})",
+            version,
+            MIGRATIONS.len()
+        ));
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration)?;
+        tx.pragma_update(None, "user_version", index + 1)?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2026-10-15 00:00 UTC in unix ms.
+    const DAY_START_MS: i64 = 1_792_022_400_000;
+
+    fn day() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 15).unwrap()
+    }
+
+    fn store() -> MeasurementStore {
+        MeasurementStore::with_connection(Connection::open_in_memory().unwrap()).unwrap()
+    }
+
+    fn block(slot: u64, received_ms: i64, propagation_latency_ms: i64) -> BlockLatencyData {
+        BlockLatencyData {
+            slot,
+            received_ms,
+            propagation_latency_ms,
+            created_at_ms: None,
+            transaction_count: 0,
+        }
+    }
+
+    #[test]
+    fn migrates_a_new_database() {
+        let store = store();
+        let version: usize = store
+            .conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, MIGRATIONS.len());
+
+        // Opening an up-to-date database again is a no-op
+        let mut conn = store.conn;
+        migrate(&mut conn).unwrap();
+    }
+
+    #[test]
+    fn rejects_a_newer_schema() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", MIGRATIONS.len() + 1)
+            .unwrap();
+        assert!(migrate(&mut conn).is_err());
+    }
+
+    #[test]
+    fn inserts_both_kinds_of_rows() {
+        let store = store();
+        store
+            .insert_block_latency(&block(100, DAY_START_MS + 1_000, 420), "laserstream", "tyo")
+            .unwrap();
+        store
+            .insert_slot_latency(&SlotLatency {
+                slot: 101,
+                block_time: DAY_START_MS / 1000,
+                detected_time: DAY_START_MS + 2_000,
+                latency_ms: 2_000,
+            })
+            .unwrap();
+
+        let rows: Vec<(String, Option<String>, i64, i64, i64)> = store
+            .conn
+            .prepare("SELECT source, region, slot, received_ms, latency_ms FROM measurements ORDER BY slot")
+            .unwrap()
+            .query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?))
+            })
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    "laserstream".to_string(),
+                    Some("tyo".to_string()),
+                    100,
+                    DAY_START_MS + 1_000,
+                    420
+                ),
+                ("rpc".to_string(), None, 101, DAY_START_MS + 2_000, 2_000),
+            ]
+        );
+    }
+
+    #[test]
+    fn daily_percentiles_covers_one_utc_day_of_one_source() {
+        let store = store();
+        for (i, latency_ms) in [400, 300, 500, 350].into_iter().enumerate() {
+            let received_ms = DAY_START_MS + i as i64 * 3_600_000;
+            store
+                .insert_block_latency(
+                    &block(i as u64, received_ms, latency_ms),
+                    "laserstream",
+                    "tyo",
+                )
+                .unwrap();
+        }
+        // Just outside the day on either side, and another source
+        store
+            .insert_block_latency(&block(10, DAY_START_MS - 1, 9_000), "laserstream", "tyo")
+            .unwrap();
+        store
+            .insert_block_latency(
+                &block(11, DAY_START_MS + 86_400_000, 9_000),
+                "laserstream",
+                "tyo",
+            )
+            .unwrap();
+        store
+            .insert_block_latency(&block(12, DAY_START_MS, 9_000), "websocket", "tyo")
+            .unwrap();
+
+        let stats = store
+            .daily_percentiles("laserstream", day())
+            .unwrap()
+            .unwrap();
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.avg_ms, 387.5);
+        assert_eq!((stats.min_ms, stats.max_ms), (300, 500));
+        // Arrival order: 400, 300, 500, 350
+        assert_eq!(stats.jitter_ms, (100.0 + 200.0 + 150.0) / 3.0);
+
+        assert!(store.daily_percentiles("rpc", day()).unwrap().is_none());
+    }
+}
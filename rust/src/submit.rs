@@ -0,0 +1,124 @@
+//! End-to-end timing of a transaction the benchmark submits itself.
+//!
+//! [`submit_and_time`] fetches a blockhash, signs a self-transfer of
+//! [`SELF_TRANSFER_LAMPORTS`] with the given keypair, sends it with
+//! `sendTransaction` and polls it to confirmed through
+//! [`landing::track_transaction_speed`]. It costs the transaction fee, so
+//! callers only reach it with an explicit keypair.
+
+use crate::landing::{self, LandingStatus};
+use crate::rpc;
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::{json, Value};
+use solana_sdk::hash::Hash;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Sent from the payer to itself, so only the fee leaves the account.
+pub const SELF_TRANSFER_LAMPORTS: u64 = 1;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionSpeed {
+    pub signature: String,
+    /// `getLatestBlockhash` round trip, before anything was signed.
+    pub blockhash_ms: f64,
+    /// `sendTransaction` round trip.
+    pub send_ms: f64,
+    /// Ms from submitting (just before `sendTransaction`) to the first poll
+    /// that saw the transaction confirmed. `None` when it did not land.
+    pub confirm_ms: Option<i64>,
+    pub status: LandingStatus,
+}
+
+/// Submit a self-transfer signed by `keypair` and time it until confirmed,
+/// failed, or `deadline` after submission.
+pub async fn submit_and_time(
+    keypair: &Keypair,
+    rpc_url: &str,
+    deadline: Duration,
+) -> Result<TransactionSpeed> {
+    let client = Client::builder().timeout(Duration::from_secs(10)).build()?;
+
+    let blockhash_start = Instant::now();
+    let blockhash = get_latest_blockhash(&client, rpc_url).await?;
+    let blockhash_ms = blockhash_start.elapsed().as_secs_f64() * 1000.0;
+
+    let payer = keypair.pubkey();
+    let transfer = system_instruction::transfer(&payer, &payer, SELF_TRANSFER_LAMPORTS);
+    let transaction =
+        Transaction::new_signed_with_payer(&[transfer], Some(&payer), &[keypair], blockhash);
+    let wire = base64::engine::general_purpose::STANDARD.encode(bincode::serialize(&transaction)?);
+
+    let submitted = Instant::now();
+    let json_value = rpc::call(
+        &client,
+        rpc_url,
+        "sendTransaction",
+        json!([wire, {"encoding": "base64", "preflightCommitment": "processed"}]),
+    )
+    .await?;
+    let send_ms = submitted.elapsed().as_secs_f64() * 1000.0;
+
+    if let Some(error) = json_value.get("error") {
+        return Err(anyhow!("sendTransaction error: {}", error));
+    }
+    let signature = json_value
+        .get("result")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("sendTransaction returned no signature"))?
+        .to_string();
+
+    let remaining = deadline.saturating_sub(submitted.elapsed());
+    let (status, tracked_ms) =
+        landing::track_transaction_speed(&client, rpc_url, &signature, remaining).await?;
+
+    Ok(TransactionSpeed {
+        signature,
+        blockhash_ms,
+        send_ms,
+        // Tracking starts once sendTransaction has answered
+        confirm_ms: tracked_ms.map(|ms| ms + send_ms.round() as i64),
+        status,
+    })
+}
+
+async fn get_latest_blockhash(client: &Client, rpc_url: &str) -> Result<Hash> {
+    let json_value = rpc::call(
+        client,
+        rpc_url,
+        "getLatestBlockhash",
+        json!([{"commitment": "confirmed"}]),
+    )
+    .await?;
+
+    if let Some(error) = json_value.get("error") {
+        return Err(anyhow!("getLatestBlockhash error: {}", error));
+    }
+
+    let blockhash = json_value
+        .pointer("/result/value/blockhash")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("Failed to get latest blockhash"))?;
+    Hash::from_str(blockhash).map_err(|e| anyhow!("Invalid blockhash '{}': {}", blockhash, e))
+}
+
+impl TransactionSpeed {
+    pub fn print(&self) {
+        println!();
+        println!("🚀 Transaction Round Trip");
+        println!("{}", "=".repeat(50));
+        println!("Signature:            {}", self.signature);
+        println!("Blockhash fetch:      {:.1}ms", self.blockhash_ms);
+        println!("sendTransaction:      {:.1}ms", self.send_ms);
+        match self.confirm_ms {
+            Some(ms) => println!("Submit to confirmed:  {}ms", ms),
+            None => println!("Submit to confirmed:  not landed ({:?})", self.status),
+        }
+    }
+}
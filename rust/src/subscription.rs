@@ -0,0 +1,168 @@
+//! Laserstream subscriptions with their handle kept in reach.
+//!
+//! `helius_laserstream::subscribe` returns the update stream together with a
+//! `StreamHandle` that writes further `SubscribeRequest`s on the same
+//! connection. [`subscribe`] wraps both: every server `Ping` is answered
+//! with a ping request (the server replies with a `Pong`) so the connection
+//! is not dropped as idle, and the returned [`Subscription`] can cancel the
+//! stream on shutdown instead of waiting for it to be dropped.
+//!
+//! The library reconnects on its own and replays from the last slot it saw,
+//! which does not show up as an error. A reconnect is inferred when a block
+//! arrives for a slot at or below the highest one already delivered; slots
+//! after it can carry a replay's delay, so timing baselines around it are
+//! suspect.
+
+use anyhow::{anyhow, Result};
+use futures::stream::{self, AbortHandle, Stream, StreamExt};
+use helius_laserstream::grpc::subscribe_update::UpdateOneof;
+use helius_laserstream::grpc::{SubscribeRequest, SubscribeRequestPing, SubscribeUpdate};
+use helius_laserstream::{LaserstreamConfig, LaserstreamError, StreamHandle};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use tracing::{debug, warn};
+
+/// Counters of one subscription, readable while it runs.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct SubscriptionHealth {
+    /// Server pings answered through the handle.
+    pub pings_answered: u64,
+    /// Pongs received for those answers.
+    pub pongs: u64,
+    /// Times the stream went back to an already delivered slot, i.e. the
+    /// library reconnected and replayed.
+    pub suspected_reconnects: u64,
+    /// Block updates for slots at or below the highest one seen.
+    pub replayed_blocks: u64,
+}
+
+impl SubscriptionHealth {
+    pub fn print(&self) {
+        println!(
+            "🔌 Laserstream connection: {} pings answered, {} pongs",
+            self.pings_answered, self.pongs
+        );
+        if self.suspected_reconnects > 0 {
+            println!(
+                "⚠️  {} suspected reconnects ({} replayed blocks); latencies around them include the replay delay",
+                self.suspected_reconnects, self.replayed_blocks
+            );
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    health: SubscriptionHealth,
+    highest_slot: Option<u64>,
+    /// Inside a run of replayed blocks, so it counts as one reconnect.
+    replaying: bool,
+}
+
+impl State {
+    fn observe_block(&mut self, slot: u64) {
+        match self.highest_slot {
+            Some(highest) if slot <= highest => {
+                self.health.replayed_blocks += 1;
+                if !self.replaying {
+                    self.replaying = true;
+                    self.health.suspected_reconnects += 1;
+                    warn!(
+                        "Laserstream went back to slot {} after {}; the client probably reconnected, latencies around it include the replay",
+                        slot, highest
+                    );
+                }
+            }
+            _ => {
+                self.highest_slot = Some(slot);
+                self.replaying = false;
+            }
+        }
+    }
+}
+
+/// Handle on a running subscription. Cloneable, so a shutdown path can hold
+/// one while the read loop owns the stream.
+#[derive(Clone)]
+pub struct Subscription {
+    handle: Arc<StreamHandle>,
+    abort: AbortHandle,
+    state: Arc<Mutex<State>>,
+}
+
+impl Subscription {
+    /// End the stream: its next poll returns `None`.
+    pub fn cancel(&self) {
+        self.abort.abort();
+    }
+
+    /// Send another request on the connection, e.g. to change the filters.
+    pub async fn write(&self, request: SubscribeRequest) -> Result<()> {
+        self.handle
+            .write(request)
+            .await
+            .map_err(|e| anyhow!("Laserstream write failed: {}", e))
+    }
+
+    pub fn health(&self) -> SubscriptionHealth {
+        self.state.lock().unwrap().health
+    }
+}
+
+/// Subscribe like `helius_laserstream::subscribe`, answering pings and
+/// watching for reconnects. Pings and pongs are still yielded.
+pub fn subscribe(
+    config: LaserstreamConfig,
+    request: SubscribeRequest,
+) -> (
+    impl Stream<Item = Result<SubscribeUpdate, LaserstreamError>>,
+    Subscription,
+) {
+    let (updates, handle) = helius_laserstream::subscribe(config, request);
+    let (updates, abort) = stream::abortable(updates);
+    let subscription = Subscription {
+        handle: Arc::new(handle),
+        abort,
+        state: Arc::new(Mutex::new(State::default())),
+    };
+
+    let watcher = subscription.clone();
+    let updates = updates.then(move |update| {
+        let watcher = watcher.clone();
+        async move {
+            if let Ok(update) = &update {
+                watch(&watcher, update).await;
+            }
+            update
+        }
+    });
+
+    (updates, subscription)
+}
+
+async fn watch(subscription: &Subscription, update: &SubscribeUpdate) {
+    match &update.update_oneof {
+        Some(UpdateOneof::Ping(_)) => {
+            let id = {
+                let mut state = subscription.state.lock().unwrap();
+                state.health.pings_answered += 1;
+                state.health.pings_answered as i32
+            };
+            let request = SubscribeRequest {
+                ping: Some(SubscribeRequestPing { id }),
+                ..Default::default()
+            };
+            if let Err(e) = subscription.write(request).await {
+                debug!("{}", e);
+            }
+        }
+        Some(UpdateOneof::Pong(_)) => subscription.state.lock().unwrap().health.pongs += 1,
+        Some(UpdateOneof::Block(block)) => {
+            subscription.state.lock().unwrap().observe_block(block.slot)
+        }
+        Some(UpdateOneof::BlockMeta(meta)) => {
+            subscription.state.lock().unwrap().observe_block(meta.slot)
+        }
+        _ => {}
+    }
+}
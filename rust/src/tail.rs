@@ -0,0 +1,53 @@
+//! Fixed-height live view for per-block output.
+//!
+//! Keeps the last N lines plus a stats footer on screen and redraws them in
+//! place with ANSI cursor movement, instead of scrolling forever. Lighter than
+//! a full TUI and works in any VT100-compatible terminal.
+
+use std::collections::VecDeque;
+use std::io::{self, Write};
+
+#[derive(Debug)]
+pub struct TailView {
+    capacity: usize,
+    lines: VecDeque<String>,
+    /// Number of terminal rows drawn by the previous render.
+    drawn: usize,
+}
+
+impl TailView {
+    pub fn new(capacity: usize) -> Self {
+        TailView {
+            capacity: capacity.max(1),
+            lines: VecDeque::with_capacity(capacity.max(1)),
+            drawn: 0,
+        }
+    }
+
+    pub fn push(&mut self, line: String) {
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    /// Redraw the retained lines followed by `footer` (which may span several
+    /// lines) over the previous frame.
+    pub fn render(&mut self, footer: &str) {
+        let mut out = io::stdout().lock();
+
+        if self.drawn > 0 {
+            // Move to the top of the previous frame and clear everything below
+            let _ = write!(out, "\x1b[{}A\r\x1b[J", self.drawn);
+        }
+
+        for line in &self.lines {
+            let _ = writeln!(out, "{}", line);
+        }
+        let _ = writeln!(out, "{}", "-".repeat(50));
+        let _ = writeln!(out, "{}", footer);
+        let _ = out.flush();
+
+        self.drawn = self.lines.len() + 1 + footer.lines().count().max(1);
+    }
+}
@@ -0,0 +1,15 @@
+//! The measurement tools, one module each, plus `compare_reports` and
+//! `history` over their saved output.
+//!
+//! Each module exposes its clap `Args` and an async `run`. The per-tool
+//! binaries in `src/bin` and the `bench` subcommands are thin wrappers over
+//! these, so every entry point accepts the same flags.
+
+pub mod compare_reports;
+pub mod history;
+pub mod laserstream_benchmark;
+pub mod latency_calculator;
+pub mod racing_comparison;
+pub mod rpc_latency_test;
+pub mod rpc_vs_laserstream_logger;
+pub mod signature_poll_latency;
@@ -0,0 +1,164 @@
+//! Latency deltas between two saved runs (`bench compare`).
+//!
+//! Reads the statistics of two JSON reports written by this build, either a
+//! `BenchmarkReport` (`bench ... --report`) or a `laserstream_benchmark
+//! --json` summary, and prints the change of each metric. A gated metric
+//! that worsened by more than the threshold fails the comparison, so the
+//! exit status can gate a deploy.
+
+use crate::report;
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+#[derive(Parser)]
+#[command(name = "compare-reports")]
+#[command(about = "Compare the latency of two saved benchmark reports and fail on a regression")]
+pub struct Args {
+    #[arg(long, value_name = "JSON", help = "Earlier report, the reference")]
+    baseline: PathBuf,
+
+    #[arg(
+        long,
+        value_name = "JSON",
+        help = "Report to judge against the baseline"
+    )]
+    candidate: PathBuf,
+
+    #[arg(
+        long,
+        default_value = "10",
+        value_name = "PERCENT",
+        help = "Largest increase of a gated metric that still passes"
+    )]
+    threshold_percent: f64,
+
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_value = "p95",
+        help = "Metrics whose regression fails the comparison"
+    )]
+    gate: Vec<Metric>,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Metric {
+    Avg,
+    P50,
+    P95,
+    P99,
+}
+
+impl Metric {
+    const ALL: [Metric; 4] = [Metric::Avg, Metric::P50, Metric::P95, Metric::P99];
+
+    fn name(self) -> &'static str {
+        match self {
+            Metric::Avg => "avg",
+            Metric::P50 => "p50",
+            Metric::P95 => "p95",
+            Metric::P99 => "p99",
+        }
+    }
+
+    fn of(self, stats: &SavedStats) -> f64 {
+        match self {
+            Metric::Avg => stats.avg_ms,
+            Metric::P50 => stats.p50_ms as f64,
+            Metric::P95 => stats.p95_ms as f64,
+            Metric::P99 => stats.p99_ms as f64,
+        }
+    }
+}
+
+/// The fields of `LatencyStats` compared here, as both report shapes
+/// serialize them.
+#[derive(Debug, Deserialize)]
+struct SavedStats {
+    avg_ms: f64,
+    p50_ms: i64,
+    p95_ms: i64,
+    p99_ms: i64,
+}
+
+pub async fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let baseline = load_stats(&args.baseline)?;
+    let candidate = load_stats(&args.candidate)?;
+
+    println!("📊 Report comparison");
+    println!("Baseline:  {}", args.baseline.display());
+    println!("Candidate: {}", args.candidate.display());
+    println!(
+        "Gate: {} may not worsen by more than {}%",
+        args.gate
+            .iter()
+            .map(|m| m.name())
+            .collect::<Vec<_>>()
+            .join(", "),
+        args.threshold_percent
+    );
+    println!();
+    println!("Metric | Baseline   | Candidate  | Delta      | Change   | Verdict");
+    println!("{}", "-".repeat(68));
+
+    let mut regressions = 0;
+    for metric in Metric::ALL {
+        let (before, after) = (metric.of(&baseline), metric.of(&candidate));
+        let change = percent_change(before, after);
+        let gated = args.gate.contains(&metric);
+        let regressed = gated && change.is_some_and(|c| c > args.threshold_percent);
+        if regressed {
+            regressions += 1;
+        }
+
+        println!(
+            "{:<6} | {:>8.1}ms | {:>8.1}ms | {:>+8.1}ms | {:>8} | {}",
+            metric.name(),
+            before,
+            after,
+            after - before,
+            change
+                .map(|c| format!("{:+.1}%", c))
+                .unwrap_or_else(|| "n/a".to_string()),
+            match (gated, regressed) {
+                (false, _) => "-",
+                (true, false) => "✅ PASS",
+                (true, true) => "❌ FAIL",
+            }
+        );
+    }
+
+    println!();
+    if regressions > 0 {
+        return Err(anyhow!("{} gated metrics regressed", regressions).into());
+    }
+    println!("✅ No regression beyond {}%", args.threshold_percent);
+    Ok(())
+}
+
+/// Change from `before` to `after` in percent of `before`; `None` when the
+/// baseline is zero.
+fn percent_change(before: f64, after: f64) -> Option<f64> {
+    (before != 0.0).then(|| (after - before) / before.abs() * 100.0)
+}
+
+/// `stats` of a `BenchmarkReport` or `latency_stats` of a
+/// `laserstream_benchmark --json` summary, after checking the schema version.
+fn load_stats(path: &Path) -> Result<SavedStats> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Cannot read {}: {}", path.display(), e))?;
+    let report: Value = serde_json::from_str(&content)
+        .map_err(|e| anyhow!("{}: not JSON: {}", path.display(), e))?;
+    report::check_schema_version(&report).map_err(|e| anyhow!("{}: {}", path.display(), e))?;
+
+    let stats = report
+        .get("stats")
+        .or_else(|| report.get("latency_stats"))
+        .filter(|stats| !stats.is_null())
+        .ok_or_else(|| anyhow!("{}: the run has no latency statistics", path.display()))?;
+    SavedStats::deserialize(stats).map_err(|e| anyhow!("{}: {}", path.display(), e))
+}
@@ -0,0 +1,74 @@
+//! Day-by-day latency of one source from a `--db` history (`bench history`).
+//!
+//! Reads the SQLite database that `laserstream_benchmark --db` and
+//! `rpc_latency_test --db` append to and prints one row per UTC day, so a
+//! regression that builds up over days shows as a trend.
+
+use crate::storage::MeasurementStore;
+use anyhow::anyhow;
+use chrono::{Days, NaiveDate, Utc};
+use clap::Parser;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "history")]
+#[command(about = "Print the daily latency percentiles of one source from a --db history")]
+pub struct Args {
+    #[arg(long, value_name = "PATH", help = "SQLite database written with --db")]
+    db: PathBuf,
+
+    #[arg(
+        long,
+        default_value = "laserstream",
+        help = "Source to summarize: laserstream (laserstream_benchmark) or rpc (rpc_latency_test)"
+    )]
+    source: String,
+
+    #[arg(
+        long,
+        value_name = "YYYY-MM-DD",
+        help = "Last UTC day to show (default: today)"
+    )]
+    day: Option<NaiveDate>,
+
+    #[arg(
+        long,
+        default_value = "7",
+        value_name = "N",
+        value_parser = clap::value_parser!(u64).range(1..),
+        help = "Number of days to show, ending at --day"
+    )]
+    days: u64,
+}
+
+pub async fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    if !args.db.exists() {
+        return Err(anyhow!("No database at {}", args.db.display()).into());
+    }
+    let store = MeasurementStore::open(&args.db)?;
+    let last = args.day.unwrap_or_else(|| Utc::now().date_naive());
+    let first = last
+        .checked_sub_days(Days::new(args.days - 1))
+        .ok_or_else(|| anyhow!("--days {} reaches before the calendar", args.days))?;
+
+    println!(
+        "📅 Daily latency of {} ({})",
+        args.source,
+        args.db.display()
+    );
+    println!();
+    println!("Day        | Samples  | Avg        | P50      | P95      | P99");
+    println!("{}", "-".repeat(70));
+
+    for day in first.iter_days().take(args.days as usize) {
+        match store.daily_percentiles(&args.source, day)? {
+            Some(stats) => println!(
+                "{} | {:>8} | {:>8.1}ms | {:>6}ms | {:>6}ms | {:>6}ms",
+                day, stats.count, stats.avg_ms, stats.p50_ms, stats.p95_ms, stats.p99_ms
+            ),
+            None => println!("{} | {:>8} | -", day, 0),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,1805 @@
+//! Helius Laserstream block propagation benchmark (`laserstream_benchmark`, `bench grpc`).
+
+use crate::block_time::{self, BlockTimeRejections};
+use crate::breakdown::{LatencyBreakdown, NegativeLatency};
+use crate::clock::{self, TimeSource};
+use crate::confirmation::ConfirmationDelay;
+use crate::continuity::SlotContinuity;
+use crate::environment::RunEnvironment;
+use crate::hook::SampleHook;
+use crate::inject::{InjectSpec, LatencyInjector};
+use crate::logging::{self, LogFormat};
+use crate::methodology::Methodology;
+use crate::multistream::{MultiStream, StreamPenalty};
+use crate::output::{OutputSpec, Outputs, Sample, Sink};
+use crate::prewarm::{self, Prewarm};
+use crate::report::{self, BenchmarkReport, Measurement};
+use crate::rpc::{self, Commitment};
+use crate::shutdown::Shutdown;
+use crate::slot_rate::SlotRate;
+use crate::stats::{self, HistogramBuckets, LatencyStats, OutlierStats, Percentiles};
+use crate::status::{LatencyStatus, StatusThresholds};
+use crate::stop::StopCondition;
+use crate::storage::MeasurementStore;
+use crate::subscription::{self, SubscriptionHealth};
+use crate::tail::TailView;
+use crate::types::BlockLatencyData;
+use crate::warmup::{Warmup, WARMUP_LABEL};
+use anyhow::Result;
+use clap::builder::ArgPredicate;
+use clap::Parser;
+use futures::StreamExt;
+use helius_laserstream::{
+    grpc::{
+        SlotStatus, SubscribeRequest, SubscribeRequestFilterBlocks,
+        SubscribeRequestFilterBlocksMeta, SubscribeRequestFilterSlots,
+    },
+    LaserstreamConfig,
+};
+use reqwest::{Client, Url};
+use serde_json::{self, json};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, oneshot};
+use tokio::time;
+
+/// Default accepted propagation latency window `[min, max)`; anything else is
+/// treated as bogus and counted as excluded.
+const MIN_LATENCY_MS: i64 = 0;
+const MAX_LATENCY_MS: i64 = 60000;
+/// Poll cadence of the optional RPC baseline.
+const RPC_BASELINE_POLL_MS: u64 = rpc::SLOT_POLL_INTERVAL_MS;
+/// Laserstream mainnet regions, `laserstream-mainnet-<region>.helius-rpc.com`.
+const REGIONS: &[&str] = &["tyo", "ewr", "pitt", "slc", "ams", "fra", "sgp"];
+/// Blocks measured per region by `--auto-region` before ranking.
+const REGION_PROBE_BLOCKS: usize = 10;
+/// A region that cannot deliver its probe blocks within this is skipped.
+const REGION_PROBE_TIMEOUT: Duration = Duration::from_secs(20);
+
+#[derive(Parser)]
+#[command(name = "laserstream-benchmark")]
+#[command(about = "Benchmark Helius Laserstream block propagation latency")]
+pub struct Args {
+    #[arg(long, help = "Helius API key")]
+    api_key: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "https://laserstream-mainnet-tyo.helius-rpc.com",
+        help = "Helius Laserstream endpoint"
+    )]
+    endpoint: String,
+
+    #[arg(
+        long,
+        default_value = "5",
+        default_value_if("blocks", ArgPredicate::IsPresent, None),
+        value_name = "MINUTES",
+        help = "Test duration in minutes (no default with --blocks; with both, whichever comes first)"
+    )]
+    duration: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Stop after N accepted blocks (with --duration, whichever comes first)"
+    )]
+    blocks: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "K",
+        value_parser = clap::value_parser!(u64).range(2..),
+        help = "Open the block stream K times and report each connection's penalty vs the earliest arrival of every slot (--blocks counts slots all K delivered)"
+    )]
+    streams: Option<u64>,
+
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "REGION,...",
+        conflicts_with_all = ["streams", "auto_region"],
+        help = "Subscribe to every listed region at once (e.g. tyo,ewr,fra, or all) and rank them by median penalty vs the fastest region of each slot (overrides --endpoint)"
+    )]
+    regions: Vec<String>,
+
+    #[arg(long, help = "Output results as JSON (same as --output json)")]
+    json: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "text",
+        help = "Format of the diagnostics on stderr; RUST_LOG sets their levels"
+    )]
+    log_format: LogFormat,
+
+    #[arg(
+        long,
+        value_name = "SINKS",
+        help = "Comma-separated outputs: json[:PATH], csv:PATH, prometheus:PORT, influx:URL|PATH"
+    )]
+    output: Option<OutputSpec>,
+
+    #[arg(
+        long,
+        value_name = "PORT",
+        help = "Serve Prometheus /metrics on this port (same as --output prometheus:PORT)"
+    )]
+    metrics_port: Option<u16>,
+
+    #[arg(long, help = "Include the methodology constants used in this run")]
+    fairness_report: bool,
+
+    #[arg(long, help = "Skip the best-effort public IP / location lookup")]
+    no_env_probe: bool,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Keep only the last N block lines on screen with a live stats footer"
+    )]
+    tail: Option<usize>,
+
+    #[arg(
+        long,
+        value_name = "RPC_URL",
+        help = "Measure this HTTP RPC concurrently as a real baseline for the comparison"
+    )]
+    compare_against_rpc: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "CSV",
+        help = "Write raw slot,block_time,created_at,received_time tuples for every block"
+    )]
+    raw_tuples: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Append every measurement to this SQLite database (created and migrated if needed)"
+    )]
+    db: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "EXCELLENT,GOOD,FAIR",
+        help = "Status band upper bounds in ms (default 900,1200,2000)"
+    )]
+    status_thresholds: Option<StatusThresholds>,
+
+    #[arg(
+        long,
+        help = "Report upstream, delivery and total latency as separate distributions"
+    )]
+    breakdown: bool,
+
+    #[arg(
+        long,
+        help = "Resolve DNS and wait for (and discard) the first stream update before timing"
+    )]
+    prewarm_dns: bool,
+
+    #[arg(
+        long,
+        value_name = "CMD",
+        help = "Run CMD (via sh -c) for each sample with SLOT, LATENCY_MS, SOURCE, STATUS set"
+    )]
+    on_sample: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "CMD",
+        help = "Run CMD for each SLOW sample (at or above the fair threshold)"
+    )]
+    on_alert: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "1000",
+        help = "Minimum milliseconds between runs of each hook command"
+    )]
+    hook_interval_ms: u64,
+
+    #[arg(
+        long,
+        default_value = "clock_realtime",
+        value_name = "clock_realtime|ntp[:server]|file:<path>",
+        help = "Wall clock for latency math; ntp[:server] applies an offset measured once at startup, file:<path> the PTP/GPS offset (ms) read from that file"
+    )]
+    time_source: TimeSource,
+
+    #[arg(
+        long,
+        value_name = "CSV",
+        help = "Write bucket_lower_ms,bucket_upper_ms,count of the latency distribution"
+    )]
+    histogram_out: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "0,250,500,750,1000,1250,1500,2000,3000,5000",
+        value_name = "EDGES",
+        help = "Ascending bucket edges in ms for --histogram-out"
+    )]
+    histogram_buckets: HistogramBuckets,
+
+    #[arg(
+        long,
+        help = "Also report avg/P95 per UTC hour of day (useful for multi-hour runs)"
+    )]
+    by_hour: bool,
+
+    #[arg(
+        long,
+        default_value = "5",
+        value_name = "PERCENT",
+        help = "Percent of blocks dropped from each end for the trimmed average"
+    )]
+    trim_percent: f64,
+
+    #[arg(
+        long,
+        default_value = "50,90,95,99",
+        value_name = "LIST",
+        help = "Percentiles to report, e.g. 50,90,99,99.9"
+    )]
+    percentiles: Percentiles,
+
+    #[arg(
+        long,
+        help = "Print an ASCII histogram of the latency distribution with the results"
+    )]
+    histogram: bool,
+
+    #[arg(
+        long,
+        requires = "histogram",
+        value_name = "MS",
+        value_parser = clap::value_parser!(i64).range(1..),
+        help = "Bucket width of --histogram (default: chosen from the min/max latency)"
+    )]
+    bucket_ms: Option<i64>,
+
+    #[arg(
+        long,
+        help = "Also stream slot statuses on the same connection and report the processed → confirmed delay"
+    )]
+    compare_commitment_latency: bool,
+
+    #[arg(
+        long,
+        default_value = "30",
+        value_name = "N",
+        help = "Withhold the verdict and claim lines when fewer than N blocks were measured"
+    )]
+    min_blocks_for_verdict: usize,
+
+    #[arg(
+        long,
+        value_name = "CSV",
+        help = "Write slot,block_time,received_time,propagation_latency_ms per accepted block, flushed as it arrives"
+    )]
+    csv: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "CSV",
+        help = "Write seq,received_time,slot,propagation_latency_ms for every timed block in arrival order, flushed as it arrives"
+    )]
+    timeseries: Option<String>,
+
+    #[arg(
+        long,
+        default_value_t = MIN_LATENCY_MS,
+        allow_negative_numbers = true,
+        help = "Lower bound (inclusive) of accepted propagation latency in ms"
+    )]
+    min_latency_ms: i64,
+
+    #[arg(
+        long,
+        default_value_t = MAX_LATENCY_MS,
+        help = "Upper bound (exclusive) of accepted propagation latency in ms"
+    )]
+    max_latency_ms: i64,
+
+    #[arg(
+        long,
+        help = "Probe every known region with a short test, rank them by median latency and benchmark the fastest (overrides --endpoint)"
+    )]
+    auto_region: bool,
+
+    #[arg(
+        long,
+        default_value = "0",
+        value_name = "N",
+        help = "Discard the first N accepted blocks from the statistics and every output (still printed live, marked warmup)"
+    )]
+    warmup: u64,
+
+    #[arg(
+        long,
+        default_value = "4",
+        value_name = "SLOTS",
+        help = "Warn as it happens when more than SLOTS slots pass between consecutive blocks"
+    )]
+    gap_warn_slots: u64,
+
+    #[arg(long, hide = true, value_name = "MS@PERCENT")]
+    inject_latency: Option<InjectSpec>,
+
+    #[arg(long, hide = true)]
+    seed: Option<u64>,
+}
+
+impl Args {
+    fn thresholds(&self) -> StatusThresholds {
+        self.status_thresholds
+            .unwrap_or(StatusThresholds::BLOCK_STREAM)
+    }
+
+    fn latency_window(&self) -> Range<i64> {
+        self.min_latency_ms..self.max_latency_ms
+    }
+
+    /// `--output` with `--metrics-port` folded in.
+    fn output_spec(&self) -> OutputSpec {
+        let mut spec = self.output.clone().unwrap_or_default();
+        if let Some(port) = self.metrics_port {
+            spec.0.push(Sink::Prometheus(port));
+        }
+        spec
+    }
+
+    /// Region label for metrics: the `tyo` of `laserstream-mainnet-tyo.helius-rpc.com`,
+    /// or the whole host when it does not follow that pattern.
+    fn region(&self) -> String {
+        let host = Url::parse(&self.endpoint)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| self.endpoint.clone());
+        let label = host.split('.').next().unwrap_or(&host);
+        match label.strip_prefix("laserstream-mainnet-") {
+            Some(region) => region.to_string(),
+            None => host,
+        }
+    }
+
+    /// Where the JSON summary goes, if anywhere: `Some(None)` is stdout.
+    fn json_target(&self) -> Option<Option<PathBuf>> {
+        if self.json {
+            return Some(None);
+        }
+        self.output.as_ref().and_then(OutputSpec::json)
+    }
+}
+
+/// Side measurements reported next to the latency distribution.
+#[derive(Default)]
+struct Diagnostics {
+    breakdown: LatencyBreakdown,
+    negative: NegativeLatency,
+    confirmation: ConfirmationDelay,
+    continuity: SlotContinuity,
+    slot_rate: SlotRate,
+    /// Blocks that arrived without a block time and were not measured.
+    missing_block_time: u64,
+    /// Zero, future or stale block times, not measured either.
+    block_time_rejections: BlockTimeRejections,
+    /// Pings answered and suspected reconnects of the block subscription.
+    connection: SubscriptionHealth,
+    /// `--streams` / `--regions`: each stream's label and penalty vs the
+    /// earliest arrival of every slot.
+    streams: Vec<(String, StreamPenalty)>,
+}
+
+/// Propagation latencies of an HTTP RPC polled alongside the stream.
+struct RpcBaseline {
+    endpoint: String,
+    latencies: Vec<i64>,
+}
+
+pub async fn run(mut args: Args) -> Result<BenchmarkReport, Box<dyn std::error::Error>> {
+    logging::init(args.log_format, false, false);
+    let api_key = args
+        .api_key
+        .clone()
+        .or_else(|| std::env::var("HELIUS_API_KEY").ok())
+        .unwrap_or_else(|| "9de07723-0030-4ee0-b175-6722231d5d97".to_string());
+
+    println!("🚀 Helius Laserstream Block Propagation Benchmark");
+    println!("Testing claim: 'Fastest block propagation'");
+    println!(
+        "Stop after: {}",
+        StopCondition::new(args.duration, args.blocks)?
+    );
+    println!("Endpoint: {}", args.endpoint);
+    println!("Time source: {}", args.time_source);
+
+    let mut injector = args
+        .inject_latency
+        .map(|spec| LatencyInjector::new(spec, args.seed));
+    if let Some(injector) = &injector {
+        println!("{}", injector.banner());
+    }
+    println!();
+
+    clock::init(&args.time_source)?;
+
+    if args.auto_region {
+        let ranking = probe_regions(&api_key).await;
+        print_region_ranking(&ranking);
+        let (best, _) = ranking
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("No region delivered its probe blocks"))?;
+        args.endpoint = region_endpoint(best);
+        println!("🏆 Benchmarking the fastest region: {}", args.endpoint);
+        println!();
+    }
+
+    if let Some(streams) = args.streams {
+        let endpoints = (1..=streams)
+            .map(|stream| (stream.to_string(), args.endpoint.clone()))
+            .collect();
+        return Ok(run_multi_stream_method(&args, api_key, endpoints).await?);
+    }
+
+    if !args.regions.is_empty() {
+        let endpoints: Vec<(String, String)> = region_list(&args.regions)
+            .into_iter()
+            .map(|region| {
+                let endpoint = region_endpoint(&region);
+                (region, endpoint)
+            })
+            .collect();
+        if endpoints.len() < 2 {
+            return Err("--regions needs at least two regions".into());
+        }
+        return Ok(run_multi_stream_method(&args, api_key, endpoints).await?);
+    }
+
+    let environment = RunEnvironment::capture(&args.endpoint, !args.no_env_probe).await;
+
+    let config = LaserstreamConfig {
+        api_key,
+        endpoint: args.endpoint.parse()?,
+        ..Default::default()
+    };
+
+    // Subscribe to all blocks for comprehensive latency testing
+    let mut block_filters = HashMap::new();
+    block_filters.insert(
+        "all_blocks".to_string(),
+        SubscribeRequestFilterBlocks {
+            account_include: vec![],           // All blocks
+            include_transactions: Some(false), // Don't need tx data for latency test
+            include_accounts: Some(false),
+            include_entries: Some(false),
+        },
+    );
+
+    // One commitment applies to the whole request, so the confirmed timing
+    // comes from slot status updates (every transition, not just the
+    // request's commitment) rather than from a second block filter.
+    let mut slot_filters = HashMap::new();
+    if args.compare_commitment_latency {
+        slot_filters.insert(
+            "slot_statuses".to_string(),
+            SubscribeRequestFilterSlots {
+                filter_by_commitment: Some(false),
+                interslot_updates: Some(false),
+            },
+        );
+    }
+
+    let request = SubscribeRequest {
+        blocks: block_filters,
+        slots: slot_filters,
+        ..Default::default()
+    };
+
+    let dns_ms = if args.prewarm_dns {
+        prewarm::resolve_ms(&args.endpoint).await
+    } else {
+        None
+    };
+
+    println!("📡 Connecting to Helius Laserstream...");
+    let subscribe_start = Instant::now();
+    let (stream, subscription) = subscription::subscribe(config, request);
+    futures::pin_mut!(stream);
+
+    if args.prewarm_dns {
+        // The stream connects lazily, so the first update absorbs the setup cost
+        let connect_ms = match stream.next().await {
+            Some(Ok(_)) => Some(subscribe_start.elapsed().as_secs_f64() * 1000.0),
+            _ => None,
+        };
+        Prewarm { dns_ms, connect_ms }.print();
+    }
+
+    let mut latencies = Vec::new();
+    let stop = StopCondition::new(args.duration, args.blocks)?;
+    let mut shutdown = Shutdown::install();
+
+    // Dropped when the stream loop ends, which stops the baseline with it
+    let (baseline_stop, baseline_done) = oneshot::channel::<()>();
+    let baseline_handle = args.compare_against_rpc.clone().map(|url| {
+        println!("🌐 Measuring RPC baseline concurrently: {}", url);
+        tokio::spawn(measure_rpc_baseline(
+            url,
+            baseline_done,
+            args.latency_window(),
+            shutdown.clone(),
+        ))
+    });
+
+    let mut block_count = 0u64;
+    let mut total_latency = 0i64;
+    let mut min_latency = i64::MAX;
+    let mut max_latency = 0i64;
+    let mut tail = args.tail.map(TailView::new);
+    let thresholds = args.thresholds();
+    let mut diagnostics = Diagnostics::default();
+    let mut excluded = 0u64;
+    let mut warmup = Warmup::new(args.warmup);
+
+    let mut csv = match &args.csv {
+        Some(path) => {
+            let mut writer = BufWriter::new(File::create(path)?);
+            writeln!(
+                writer,
+                "slot,block_time,received_time,propagation_latency_ms"
+            )?;
+            Some(writer)
+        }
+        None => None,
+    };
+    // Every timed block before the window and warmup filters, numbered in
+    // arrival order so reordering shows up as a slot going backwards
+    let mut timeseries = match &args.timeseries {
+        Some(path) => {
+            let mut writer = BufWriter::new(File::create(path)?);
+            writeln!(writer, "seq,received_time,slot,propagation_latency_ms")?;
+            Some(writer)
+        }
+        None => None,
+    };
+    let mut timeseries_seq = 0u64;
+    let region = args.region();
+    let outputs = Outputs::start(&args.output_spec(), &region)?;
+    // Synthetic samples must never end up in the history
+    let store = if args.db.is_some() && args.inject_latency.is_some() {
+        eprintln!("⚠️  --db ignored while --inject-latency is active");
+        None
+    } else {
+        args.db.as_deref().map(MeasurementStore::open).transpose()?
+    };
+
+    let hook_interval = Duration::from_millis(args.hook_interval_ms);
+    let mut sample_hook = args
+        .on_sample
+        .clone()
+        .map(|cmd| SampleHook::new(cmd, hook_interval));
+    let mut alert_hook = args
+        .on_alert
+        .clone()
+        .map(|cmd| SampleHook::new(cmd, hook_interval));
+
+    // Unfiltered reference timestamps so latency can be recomputed offline.
+    // block_time is unix seconds, created_at / received_time are unix ms.
+    let mut raw_tuples = match &args.raw_tuples {
+        Some(path) => {
+            let mut writer = BufWriter::new(File::create(path)?);
+            writeln!(writer, "slot,block_time,created_at,received_time")?;
+            Some(writer)
+        }
+        None => None,
+    };
+
+    println!("⏱️  Starting latency measurement...");
+    println!();
+
+    while stop.running(block_count) {
+        let next = tokio::select! {
+            _ = shutdown.wait() => break,
+            next = stream.next() => next,
+        };
+        if let Some(result) = next {
+            match result {
+                Ok(update) => {
+                    let received_time = clock::now_ms();
+
+                    let created_at = update
+                        .created_at
+                        .map(|ts| (ts.seconds * 1000) + (ts.nanos as i64 / 1_000_000));
+
+                    if let Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::Block(
+                        block,
+                    )) = update.update_oneof
+                    {
+                        let slot = block.slot;
+                        let reported_block_time = block.block_time.map(|bt| bt.timestamp);
+
+                        let step = diagnostics.continuity.record(slot, block.parent_slot);
+                        diagnostics.slot_rate.record(slot, received_time);
+                        if step
+                            .missing()
+                            .is_some_and(|missing| missing > args.gap_warn_slots)
+                        {
+                            let line = format!(
+                                "⚠️  Slot {}: {} gap of {} slots (parent {})",
+                                slot,
+                                step.name(),
+                                step.missing().unwrap_or_default(),
+                                block.parent_slot
+                            );
+                            match tail.as_mut() {
+                                Some(view) => view.push(line),
+                                None => println!("{}", line),
+                            }
+                        }
+
+                        if let Some(writer) = raw_tuples.as_mut() {
+                            writeln!(
+                                writer,
+                                "{},{},{},{}",
+                                slot,
+                                optional_field(reported_block_time),
+                                optional_field(created_at),
+                                received_time
+                            )?;
+                        }
+
+                        // Falling back to received_time / 1000 would leave only the
+                        // sub-second remainder of received_time as "latency"
+                        let Some(block_time) = reported_block_time else {
+                            diagnostics.missing_block_time += 1;
+                            continue;
+                        };
+                        diagnostics
+                            .negative
+                            .record(block_time, created_at, received_time);
+                        if !diagnostics.block_time_rejections.accept(
+                            block_time,
+                            slot,
+                            received_time,
+                        ) {
+                            continue;
+                        }
+
+                        let mut propagation_latency_ms = received_time - (block_time * 1000);
+                        if let Some(injector) = injector.as_mut() {
+                            propagation_latency_ms = injector.apply(propagation_latency_ms);
+                        }
+
+                        if let Some(writer) = timeseries.as_mut() {
+                            writeln!(
+                                writer,
+                                "{},{},{},{}",
+                                timeseries_seq, received_time, slot, propagation_latency_ms
+                            )?;
+                            writer.flush()?;
+                            timeseries_seq += 1;
+                        }
+
+                        // Filter out unrealistic latencies (negative or too large)
+                        let accepted = args.latency_window().contains(&propagation_latency_ms);
+                        if accepted && warmup.absorb() {
+                            let line = format!(
+                                "⚡ Slot {}: {}ms {}",
+                                slot, propagation_latency_ms, WARMUP_LABEL
+                            );
+                            match tail.as_mut() {
+                                Some(view) => view.push(line),
+                                None => println!("{}", line),
+                            }
+                        } else if accepted {
+                            block_count += 1;
+                            total_latency += propagation_latency_ms;
+                            min_latency = min_latency.min(propagation_latency_ms);
+                            max_latency = max_latency.max(propagation_latency_ms);
+
+                            let latency_data = BlockLatencyData {
+                                slot,
+                                received_ms: received_time,
+                                propagation_latency_ms,
+                                created_at_ms: created_at,
+                                transaction_count: block.executed_transaction_count,
+                            };
+
+                            if let Some(writer) = csv.as_mut() {
+                                writeln!(
+                                    writer,
+                                    "{},{},{},{}",
+                                    slot, block_time, received_time, propagation_latency_ms
+                                )?;
+                                // Per block, so a crash mid-run still leaves the rows so far
+                                writer.flush()?;
+                            }
+
+                            latencies.push(latency_data.clone());
+                            let sample = Sample {
+                                source: "laserstream",
+                                slot,
+                                received_ms: received_time,
+                                latency_ms: propagation_latency_ms,
+                                network_latency_ms: created_at.map(|ms| received_time - ms),
+                            };
+                            if let Some(store) = &store {
+                                if let Err(e) = store.insert_block_latency(
+                                    &latency_data,
+                                    "laserstream",
+                                    &region,
+                                ) {
+                                    eprintln!("❌ Cannot store slot {}: {}", slot, e);
+                                }
+                            }
+                            outputs.send(sample);
+                            diagnostics.breakdown.record(
+                                reported_block_time,
+                                created_at,
+                                received_time,
+                            );
+
+                            let status = thresholds.status(propagation_latency_ms);
+                            if let Some(hook) = sample_hook.as_mut() {
+                                hook.fire(
+                                    "laserstream",
+                                    slot,
+                                    propagation_latency_ms,
+                                    status.name(),
+                                );
+                            }
+                            if status == LatencyStatus::Slow {
+                                if let Some(hook) = alert_hook.as_mut() {
+                                    hook.fire(
+                                        "laserstream",
+                                        slot,
+                                        propagation_latency_ms,
+                                        status.name(),
+                                    );
+                                }
+                            }
+
+                            // Real-time feedback
+                            let line = format!(
+                                "⚡ Slot {}: {}ms {}",
+                                slot,
+                                propagation_latency_ms,
+                                thresholds.label(propagation_latency_ms)
+                            );
+                            let avg = total_latency / block_count as i64;
+
+                            if let Some(view) = tail.as_mut() {
+                                view.push(line);
+                                view.render(&format!(
+                                    "📊 Blocks: {} | Avg: {}ms | Min: {}ms | Max: {}ms",
+                                    block_count, avg, min_latency, max_latency
+                                ));
+                            } else {
+                                println!("{}", line);
+
+                                // Show running average every 10 blocks
+                                if block_count.is_multiple_of(10) {
+                                    println!(
+                                        "📊 Running Average: {}ms (after {} blocks)",
+                                        avg, block_count
+                                    );
+                                    println!();
+                                }
+                            }
+                        } else {
+                            excluded += 1;
+                        }
+                    } else if let Some(
+                        helius_laserstream::grpc::subscribe_update::UpdateOneof::Slot(slot_update),
+                    ) = update.update_oneof
+                    {
+                        if slot_update.status == SlotStatus::SlotProcessed as i32 {
+                            diagnostics
+                                .confirmation
+                                .processed(slot_update.slot, received_time);
+                        } else if slot_update.status == SlotStatus::SlotConfirmed as i32 {
+                            diagnostics
+                                .confirmation
+                                .confirmed(slot_update.slot, received_time);
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("❌ Laserstream error: {}", e);
+                    outputs.stream_error("laserstream");
+                }
+            }
+        }
+    }
+
+    subscription.cancel();
+    diagnostics.connection = subscription.health();
+    drop(baseline_stop);
+    outputs.finish().await;
+
+    if let Some(hook) = &sample_hook {
+        hook.print_summary("--on-sample");
+    }
+    if let Some(hook) = &alert_hook {
+        hook.print_summary("--on-alert");
+    }
+
+    if let Some(mut writer) = csv {
+        // Trailing summary row: average over the accepted blocks
+        if block_count > 0 {
+            writeln!(
+                writer,
+                "summary,,,{:.1}",
+                total_latency as f64 / block_count as f64
+            )?;
+        }
+        writer.flush()?;
+        println!(
+            "💾 Per-block CSV written to {}",
+            args.csv.as_deref().unwrap_or_default()
+        );
+    }
+
+    warmup.print_summary();
+    if diagnostics.missing_block_time > 0 {
+        println!(
+            "🚫 {} blocks without a block time were not measured",
+            diagnostics.missing_block_time
+        );
+    }
+    diagnostics.block_time_rejections.print();
+    if excluded > 0 {
+        println!(
+            "🚫 {} blocks outside the accepted window [{}, {})ms were excluded",
+            excluded, args.min_latency_ms, args.max_latency_ms
+        );
+    }
+
+    if timeseries.is_some() {
+        println!(
+            "💾 Time series ({} rows) written to {}",
+            timeseries_seq,
+            args.timeseries.as_deref().unwrap_or_default()
+        );
+    }
+
+    if let Some(mut writer) = raw_tuples {
+        writer.flush()?;
+        println!(
+            "💾 Raw tuples written to {}",
+            args.raw_tuples.as_deref().unwrap_or_default()
+        );
+    }
+
+    if let Some(path) = &args.histogram_out {
+        let samples: Vec<i64> = latencies.iter().map(|l| l.propagation_latency_ms).collect();
+        stats::write_histogram_csv(path, &samples, &args.histogram_buckets)?;
+        println!("📊 Histogram written to {}", path);
+    }
+
+    let rpc_baseline = match (args.compare_against_rpc.clone(), baseline_handle) {
+        (Some(endpoint), Some(handle)) => match handle.await? {
+            Ok(latencies) => Some(RpcBaseline {
+                endpoint,
+                latencies,
+            }),
+            Err(e) => {
+                eprintln!("❌ RPC baseline failed: {}", e);
+                None
+            }
+        },
+        _ => None,
+    };
+
+    // Calculate final statistics
+    if !latencies.is_empty() {
+        print_benchmark_results(
+            &latencies,
+            &args,
+            &environment,
+            rpc_baseline.as_ref(),
+            &diagnostics,
+            excluded,
+            injector.as_ref(),
+        );
+    } else {
+        println!("❌ No blocks received during test period");
+    }
+
+    if let Some(injector) = &injector {
+        injector.print_summary();
+    }
+
+    let measurements = latencies
+        .iter()
+        .map(|l| Measurement {
+            slot: l.slot,
+            received_ms: l.received_ms,
+            latency_ms: l.propagation_latency_ms,
+            created_at_ms: l.created_at_ms,
+        })
+        .collect();
+    let mut report = BenchmarkReport::new(
+        "laserstream_benchmark",
+        &args.endpoint,
+        measurements,
+        excluded,
+        warmup.discarded,
+    );
+    report.breakdown = Some(diagnostics.breakdown.summary());
+    Ok(report)
+}
+
+fn print_benchmark_results(
+    latencies: &[BlockLatencyData],
+    args: &Args,
+    environment: &RunEnvironment,
+    rpc_baseline: Option<&RpcBaseline>,
+    diagnostics: &Diagnostics,
+    excluded: u64,
+    injector: Option<&LatencyInjector>,
+) {
+    let times: Vec<i64> = latencies.iter().map(|l| l.propagation_latency_ms).collect();
+    let LatencyStats {
+        count,
+        avg_ms: avg,
+        min_ms: min,
+        max_ms: max,
+        p50_ms: p50,
+        p90_ms: p90,
+        p95_ms: p95,
+        p99_ms: p99,
+        std_dev_ms,
+        jitter_ms,
+    } = match LatencyStats::from_samples(&times) {
+        Some(stats) => stats,
+        None => return,
+    };
+    let outliers = OutlierStats::from_samples(&times, args.trim_percent);
+    let by_tx_count: Vec<(i64, i64)> = latencies
+        .iter()
+        .map(|l| (l.transaction_count as i64, l.propagation_latency_ms))
+        .collect();
+    let tx_count_correlation = stats::pearson(&by_tx_count);
+    let tx_count_deciles = stats::by_decile(&by_tx_count);
+
+    // Realistic speed categories
+    let thresholds = args.thresholds();
+    let sub_excellent = times
+        .iter()
+        .filter(|&&t| t < thresholds.excellent_ms)
+        .count();
+    let sub_good = times.iter().filter(|&&t| t < thresholds.good_ms).count();
+    let sub_fair = times.iter().filter(|&&t| t < thresholds.fair_ms).count();
+    let verdict = thresholds.status_f64(avg);
+    let enough_for_verdict = count >= args.min_blocks_for_verdict;
+    let hourly = args.by_hour.then(|| {
+        let samples: Vec<(i64, i64)> = latencies
+            .iter()
+            .map(|l| (l.received_ms, l.propagation_latency_ms))
+            .collect();
+        stats::by_hour(&samples)
+    });
+
+    let json_target = args.json_target();
+    if let Some(target) = &json_target {
+        let mut results = serde_json::json!({
+            "schema_version": report::SCHEMA_VERSION,
+            "provider": "Helius Laserstream",
+            "test_duration_minutes": args.duration,
+            "block_limit": args.blocks,
+            "blocks_received": count,
+            "blocks_excluded": excluded,
+            "blocks_without_block_time": diagnostics.missing_block_time,
+            "block_time_rejections": diagnostics.block_time_rejections,
+            "latency_stats": {
+                "avg_ms": avg,
+                "min_ms": min,
+                "max_ms": max,
+                "p50_ms": p50,
+                "p90_ms": p90,
+                "p95_ms": p95,
+                "p99_ms": p99,
+                "std_dev_ms": std_dev_ms,
+                "jitter_ms": jitter_ms
+            },
+            "outliers": outliers,
+            "tx_count_correlation": tx_count_correlation,
+            "latency_by_tx_count_decile": tx_count_deciles,
+            "speed_distribution": {
+                "thresholds": thresholds,
+                "sub_excellent": sub_excellent,
+                "sub_good": sub_good,
+                "sub_fair": sub_fair,
+                "sub_excellent_percent": (sub_excellent as f64 / count as f64) * 100.0,
+                "sub_good_percent": (sub_good as f64 / count as f64) * 100.0,
+                "sub_fair_percent": (sub_fair as f64 / count as f64) * 100.0
+            },
+            "negative_latency": diagnostics.negative,
+            "slot_continuity": diagnostics.continuity.summary(),
+            "slot_rate": diagnostics.slot_rate.summary(),
+            "connection": diagnostics.connection,
+            "verdict": if enough_for_verdict { verdict.name() } else { "insufficient_data" },
+            "environment": environment
+        });
+        if let Some(baseline) = rpc_baseline {
+            results["rpc_baseline"] = match LatencyStats::from_samples(&baseline.latencies) {
+                Some(rpc_stats) => json!({
+                    "endpoint": baseline.endpoint,
+                    "blocks_measured": rpc_stats.count,
+                    "avg_ms": rpc_stats.avg_ms,
+                    "p50_ms": rpc_stats.p50_ms,
+                    "p95_ms": rpc_stats.p95_ms,
+                    "laserstream_advantage_ms": rpc_stats.avg_ms - avg
+                }),
+                None => json!({
+                    "endpoint": baseline.endpoint,
+                    "blocks_measured": 0
+                }),
+            };
+        }
+        if args.breakdown {
+            results["breakdown"] = serde_json::to_value(diagnostics.breakdown.summary()).unwrap();
+        }
+        if let Some(hourly) = &hourly {
+            results["by_hour"] = serde_json::to_value(hourly).unwrap();
+        }
+        if let Some(injector) = injector {
+            results["synthetic_latency"] = json!({
+                "warning": injector.banner(),
+                "injected_samples": injector.injected
+            });
+        }
+        if args.compare_commitment_latency {
+            results["confirmation_delay"] =
+                serde_json::to_value(diagnostics.confirmation.summary()).unwrap();
+        }
+        if !diagnostics.streams.is_empty() {
+            results["streams"] = diagnostics
+                .streams
+                .iter()
+                .map(|(label, penalty)| {
+                    let mut stream = serde_json::to_value(penalty).unwrap();
+                    stream["label"] = json!(label);
+                    stream
+                })
+                .collect();
+        }
+        if args.fairness_report {
+            results["methodology"] = serde_json::to_value(methodology(args)).unwrap();
+        }
+        let rendered = serde_json::to_string_pretty(&results).unwrap();
+        match target {
+            Some(path) => match std::fs::write(path, rendered) {
+                Ok(()) => println!("📄 JSON summary written to {}", path.display()),
+                Err(e) => eprintln!("❌ Cannot write {}: {}", path.display(), e),
+            },
+            None => println!("{}", rendered),
+        }
+    }
+
+    // JSON on stdout replaces the human-readable report
+    if !matches!(json_target, Some(None)) {
+        println!();
+        println!("🏁 Helius Laserstream Benchmark Results");
+        if let Some(injector) = injector {
+            println!("{}", injector.banner());
+        }
+        println!("{}", "=".repeat(50));
+        println!("Blocks tested: {}", count);
+        println!("Average latency: {:.1}ms", avg);
+        if let Some(outliers) = &outliers {
+            println!(
+                "Trimmed average ({}% each end): {:.1}ms",
+                outliers.trim_percent, outliers.trimmed_avg_ms
+            );
+        }
+        println!("Min latency: {}ms", min);
+        println!("Max latency: {}ms", max);
+        println!("Std deviation: {:.1}ms", std_dev_ms);
+        println!("Jitter: {:.1}ms", jitter_ms);
+        for (label, value) in args.percentiles.of(&times) {
+            println!("{}: {}ms", label, value);
+        }
+        if let Some(outliers) = &outliers {
+            println!(
+                "Outliers (1.5 IQR): {} outside [{:.0}, {:.0}]ms",
+                outliers.outliers, outliers.lower_fence_ms, outliers.upper_fence_ms
+            );
+        }
+        println!();
+
+        println!("⚡ Realistic Speed Distribution:");
+        println!(
+            "Sub-{}ms (Excellent): {}/{} ({:.1}%)",
+            thresholds.excellent_ms,
+            sub_excellent,
+            count,
+            (sub_excellent as f64 / count as f64) * 100.0
+        );
+        println!(
+            "Sub-{}ms (Good): {}/{} ({:.1}%)",
+            thresholds.good_ms,
+            sub_good,
+            count,
+            (sub_good as f64 / count as f64) * 100.0
+        );
+        println!(
+            "Sub-{}ms (Fair): {}/{} ({:.1}%)",
+            thresholds.fair_ms,
+            sub_fair,
+            count,
+            (sub_fair as f64 / count as f64) * 100.0
+        );
+        println!();
+
+        if args.histogram {
+            stats::print_ascii_histogram(&times, args.bucket_ms);
+            println!();
+        }
+
+        diagnostics.connection.print();
+        println!();
+
+        println!("🎯 Performance Verdict:");
+        match verdict {
+            _ if !enough_for_verdict => print_insufficient_data(count, args.min_blocks_for_verdict),
+            LatencyStatus::Excellent => println!(
+                "✅ EXCELLENT - Sub-{}ms latency! Outstanding real-world performance",
+                thresholds.excellent_ms
+            ),
+            LatencyStatus::Good => println!(
+                "🟡 GOOD - Sub-{}ms latency, great for most applications",
+                thresholds.good_ms
+            ),
+            LatencyStatus::Fair => println!(
+                "🟠 FAIR - Sub-{}ms latency, consider region optimization",
+                thresholds.fair_ms
+            ),
+            LatencyStatus::Slow => println!(
+                "🔴 SLOW - {}ms+ latency, investigate network/provider issues",
+                thresholds.fair_ms
+            ),
+        }
+
+        if diagnostics.negative.total() > 0 {
+            println!();
+            diagnostics.negative.print();
+        }
+
+        println!();
+        diagnostics.continuity.print();
+
+        println!();
+        diagnostics.slot_rate.print();
+
+        println!();
+        print_tx_count_correlation(tx_count_correlation, &tx_count_deciles);
+
+        if args.breakdown {
+            diagnostics.breakdown.print("Laserstream");
+        }
+
+        if let Some(hourly) = &hourly {
+            println!();
+            stats::print_by_hour(hourly);
+        }
+
+        if args.compare_commitment_latency {
+            println!();
+            diagnostics.confirmation.print();
+        }
+
+        println!();
+        match rpc_baseline {
+            Some(baseline) => {
+                print_baseline_comparison(baseline, avg, p50, count, args.min_blocks_for_verdict)
+            }
+            None => {
+                println!("📈 Compared to typical RPC providers:");
+                println!("• Regular HTTP RPC: 3-5 seconds");
+                println!("• Premium WebSocket: 500-2000ms");
+                println!("• Laserstream: {:.0}ms average", avg);
+
+                if !enough_for_verdict {
+                    print_insufficient_data(count, args.min_blocks_for_verdict);
+                } else if avg < 200.0 {
+                    println!("🏆 CLAIM VERIFIED: Laserstream IS significantly faster!");
+                } else if avg < 500.0 {
+                    println!("✅ CLAIM SUPPORTED: Much faster than regular RPCs");
+                } else {
+                    println!("⚠️  CLAIM QUESTIONABLE: Similar to other premium providers");
+                }
+                println!("💡 Pass --compare-against-rpc <url> for a measured baseline");
+            }
+        }
+
+        environment.print();
+
+        if args.fairness_report {
+            methodology(args).print();
+        }
+    }
+}
+
+fn print_tx_count_correlation(correlation: Option<f64>, deciles: &[stats::DecileStats]) {
+    println!("📦 Block size vs latency:");
+    match correlation {
+        Some(r) => println!("Pearson r (tx count, latency): {:.3}", r),
+        None => println!("Pearson r (tx count, latency): n/a"),
+    }
+    println!("Decile | Tx count    | Blocks | Avg      | P50");
+    println!("{}", "-".repeat(48));
+    for decile in deciles {
+        println!(
+            "{:>6} | {:>5}-{:<5} | {:>6} | {:>6.1}ms | {}ms",
+            decile.decile,
+            decile.min_key,
+            decile.max_key,
+            decile.stats.count,
+            decile.stats.avg_ms,
+            decile.stats.p50_ms
+        );
+    }
+}
+
+fn print_insufficient_data(count: usize, min_blocks: usize) {
+    println!(
+        "⚠️  Insufficient data for verdict (n={} < {})",
+        count, min_blocks
+    );
+}
+
+/// CSV cell for a timestamp that may be absent (left empty).
+fn optional_field(value: Option<i64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn print_baseline_comparison(
+    baseline: &RpcBaseline,
+    ls_avg: f64,
+    ls_p50: i64,
+    ls_count: usize,
+    min_blocks: usize,
+) {
+    println!(
+        "📈 Compared to measured RPC baseline ({}):",
+        baseline.endpoint
+    );
+
+    let rpc_stats = match LatencyStats::from_samples(&baseline.latencies) {
+        Some(rpc_stats) => rpc_stats,
+        None => {
+            println!("❌ RPC baseline collected no blocks - no comparison possible");
+            return;
+        }
+    };
+
+    println!(
+        "• HTTP RPC: {:.0}ms average, {}ms median, {}ms P95 ({} blocks)",
+        rpc_stats.avg_ms, rpc_stats.p50_ms, rpc_stats.p95_ms, rpc_stats.count
+    );
+    println!(
+        "• Laserstream: {:.0}ms average, {}ms median",
+        ls_avg, ls_p50
+    );
+
+    let advantage_ms = rpc_stats.avg_ms - ls_avg;
+    let advantage_pct = advantage_ms / rpc_stats.avg_ms * 100.0;
+
+    if ls_count.min(rpc_stats.count) < min_blocks {
+        print_insufficient_data(ls_count.min(rpc_stats.count), min_blocks);
+    } else if advantage_pct >= 20.0 {
+        println!(
+            "🏆 CLAIM VERIFIED: Laserstream was {:.0}ms ({:.1}%) faster than this RPC",
+            advantage_ms, advantage_pct
+        );
+    } else if advantage_ms > 0.0 {
+        println!(
+            "✅ CLAIM SUPPORTED: Laserstream was {:.0}ms ({:.1}%) faster than this RPC",
+            advantage_ms, advantage_pct
+        );
+    } else {
+        println!(
+            "⚠️  CLAIM NOT SUPPORTED: this RPC was {:.0}ms faster than Laserstream on average",
+            -advantage_ms
+        );
+    }
+}
+
+/// Laserstream endpoint of one of the [`REGIONS`].
+fn region_endpoint(region: &str) -> String {
+    format!("https://laserstream-mainnet-{}.helius-rpc.com", region)
+}
+
+/// Median block latency of every region in [`REGIONS`] over a short probe,
+/// fastest first. Regions that fail or time out are left out.
+async fn probe_regions(api_key: &str) -> Vec<(String, f64)> {
+    println!(
+        "🧭 Probing {} regions ({} blocks each)...",
+        REGIONS.len(),
+        REGION_PROBE_BLOCKS
+    );
+
+    let mut ranking = Vec::new();
+    for region in REGIONS {
+        match time::timeout(REGION_PROBE_TIMEOUT, probe_region(api_key, region)).await {
+            Ok(Ok(Some(median))) => ranking.push((region.to_string(), median)),
+            Ok(Ok(None)) => println!("   {}: no blocks", region),
+            Ok(Err(e)) => println!("   {}: {}", region, e),
+            Err(_) => println!(
+                "   {}: timed out after {}s",
+                region,
+                REGION_PROBE_TIMEOUT.as_secs()
+            ),
+        }
+    }
+
+    ranking.sort_by(|a, b| a.1.total_cmp(&b.1));
+    ranking
+}
+
+async fn probe_region(api_key: &str, region: &str) -> Result<Option<f64>> {
+    let config = LaserstreamConfig {
+        api_key: api_key.to_string(),
+        endpoint: region_endpoint(region).parse()?,
+        ..Default::default()
+    };
+    let mut request = SubscribeRequest::default();
+    request.blocks_meta.insert(
+        "probe".to_string(),
+        SubscribeRequestFilterBlocksMeta::default(),
+    );
+
+    let (stream, _subscription) = subscription::subscribe(config, request);
+    futures::pin_mut!(stream);
+
+    let mut latencies = Vec::with_capacity(REGION_PROBE_BLOCKS);
+    while latencies.len() < REGION_PROBE_BLOCKS {
+        let Some(update) = stream.next().await else {
+            break;
+        };
+        let received_time = clock::now_ms();
+        if let Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::BlockMeta(meta)) =
+            update?.update_oneof
+        {
+            if let Some(block_time) = meta.block_time {
+                if block_time::validate_block_time(block_time.timestamp, meta.slot, received_time)
+                    .is_ok()
+                {
+                    latencies.push(received_time - block_time.timestamp * 1000);
+                }
+            }
+        }
+    }
+
+    latencies.sort_unstable();
+    Ok(stats::percentile(&latencies, 0.5).map(|median| median as f64))
+}
+
+/// `--streams K` / `--regions`: one block meta stream per `(label,
+/// endpoint)`. Each stream's penalty is measured against the earliest
+/// arrival of every slot. With K copies of one endpoint this isolates
+/// per-connection variance from the latency they all share; with one stream
+/// per region it shows which region is fastest from this host.
+///
+/// The earliest arrival of each scored slot is the latency this host could
+/// have had, and goes through the same window, warmup and sinks as the
+/// single stream.
+async fn run_multi_stream_method(
+    args: &Args,
+    api_key: String,
+    endpoints: Vec<(String, String)>,
+) -> Result<BenchmarkReport> {
+    let streams = endpoints.len();
+    let by_region = !args.regions.is_empty();
+    let stop = StopCondition::new(args.duration, args.blocks)?;
+    let mut shutdown = Shutdown::install();
+    let environment = RunEnvironment::capture(&endpoints[0].1, !args.no_env_probe).await;
+    // (stream, slot, received ms, block time, transaction count)
+    let (sender, mut receiver) = mpsc::unbounded_channel::<(usize, u64, i64, Option<i64>, u64)>();
+
+    let mut tasks = Vec::with_capacity(streams);
+    for (stream, (label, endpoint)) in endpoints.iter().enumerate() {
+        let config = LaserstreamConfig {
+            api_key: api_key.clone(),
+            endpoint: endpoint.parse()?,
+            ..Default::default()
+        };
+        let label = label.clone();
+        let sender = sender.clone();
+        tasks.push(tokio::spawn(async move {
+            let mut request = SubscribeRequest::default();
+            request.blocks_meta.insert(
+                "all".to_string(),
+                SubscribeRequestFilterBlocksMeta::default(),
+            );
+            let (updates, _subscription) = subscription::subscribe(config, request);
+            futures::pin_mut!(updates);
+
+            while let Some(update) = updates.next().await {
+                let received_time = clock::now_ms();
+                match update {
+                    Ok(update) => {
+                        if let Some(
+                            helius_laserstream::grpc::subscribe_update::UpdateOneof::BlockMeta(
+                                meta,
+                            ),
+                        ) = update.update_oneof
+                        {
+                            let block_time = meta.block_time.map(|bt| bt.timestamp);
+                            if sender
+                                .send((
+                                    stream,
+                                    meta.slot,
+                                    received_time,
+                                    block_time,
+                                    meta.executed_transaction_count,
+                                ))
+                                .is_err()
+                            {
+                                break;
+                            }
+                        }
+                    }
+                    Err(e) => eprintln!("❌ Stream {} error: {}", label, e),
+                }
+            }
+        }));
+    }
+    drop(sender);
+
+    let labels: Vec<&str> = endpoints.iter().map(|(label, _)| label.as_str()).collect();
+    if by_region {
+        println!("🌍 Racing {} regions: {}...", streams, labels.join(", "));
+    } else {
+        println!("🔀 Racing {} identical block streams...", streams);
+    }
+    println!();
+
+    let region = if by_region {
+        labels.join(",")
+    } else {
+        args.region()
+    };
+    let outputs = Outputs::start(&args.output_spec(), &region)?;
+    let store = args.db.as_deref().map(MeasurementStore::open).transpose()?;
+    let mut csv = match &args.csv {
+        Some(path) => {
+            let mut writer = BufWriter::new(File::create(path)?);
+            writeln!(
+                writer,
+                "slot,block_time,received_time,propagation_latency_ms"
+            )?;
+            Some(writer)
+        }
+        None => None,
+    };
+    let window = args.latency_window();
+    let mut diagnostics = Diagnostics::default();
+    let mut excluded = 0u64;
+    let mut warmup = Warmup::new(args.warmup);
+    let mut latencies = Vec::new();
+
+    let mut multi = MultiStream::new(streams);
+    // Slot → (block time, transaction count) of the first delivery, kept
+    // only as long as `multi` remembers the slot
+    let mut block_times: BTreeMap<u64, (i64, u64)> = BTreeMap::new();
+    let mut ended = false;
+    while !ended {
+        let next = tokio::select! {
+            _ = shutdown.wait() => None,
+            next = receiver.recv() => Some(next),
+        };
+        match next {
+            Some(Some((stream, slot, received_time, block_time, transaction_count))) => {
+                if let Some(block_time) = block_time {
+                    block_times
+                        .entry(slot)
+                        .or_insert((block_time, transaction_count));
+                }
+                multi.record(stream, slot, received_time);
+            }
+            Some(None) => {
+                println!("All streams ended");
+                ended = true;
+            }
+            None => ended = true,
+        }
+        if ended || !stop.running(multi.complete()) {
+            ended = true;
+            for task in &tasks {
+                task.abort();
+            }
+            multi.finish();
+        }
+
+        for (slot, received_ms) in multi.take_scored() {
+            let Some((block_time, transaction_count)) = block_times.remove(&slot) else {
+                diagnostics.missing_block_time += 1;
+                continue;
+            };
+            if !diagnostics
+                .block_time_rejections
+                .accept(block_time, slot, received_ms)
+            {
+                continue;
+            }
+            let propagation_latency_ms = received_ms - block_time * 1000;
+            if !window.contains(&propagation_latency_ms) {
+                excluded += 1;
+                continue;
+            }
+            if warmup.absorb() {
+                continue;
+            }
+
+            let latency_data = BlockLatencyData {
+                slot,
+                received_ms,
+                propagation_latency_ms,
+                created_at_ms: None,
+                transaction_count,
+            };
+            if let Some(writer) = csv.as_mut() {
+                writeln!(
+                    writer,
+                    "{},{},{},{}",
+                    slot, block_time, received_ms, propagation_latency_ms
+                )?;
+                writer.flush()?;
+            }
+            if let Some(store) = &store {
+                if let Err(e) = store.insert_block_latency(&latency_data, "laserstream", &region) {
+                    eprintln!("❌ Cannot store slot {}: {}", slot, e);
+                }
+            }
+            outputs.send(Sample {
+                source: "laserstream",
+                slot,
+                received_ms,
+                latency_ms: propagation_latency_ms,
+                network_latency_ms: None,
+            });
+            latencies.push(latency_data);
+        }
+        block_times = block_times.split_off(&multi.forget_before());
+    }
+    outputs.finish().await;
+
+    if let Some(mut writer) = csv {
+        if !latencies.is_empty() {
+            let total: i64 = latencies.iter().map(|l| l.propagation_latency_ms).sum();
+            writeln!(
+                writer,
+                "summary,,,{:.1}",
+                total as f64 / latencies.len() as f64
+            )?;
+        }
+        writer.flush()?;
+        println!(
+            "💾 Per-block CSV written to {}",
+            args.csv.as_deref().unwrap_or_default()
+        );
+    }
+
+    println!();
+    if by_region {
+        print_region_matrix(&endpoints, &multi);
+    } else {
+        multi.print();
+    }
+
+    warmup.print_summary();
+    if diagnostics.missing_block_time > 0 {
+        println!(
+            "🚫 {} slots without a block time were not measured",
+            diagnostics.missing_block_time
+        );
+    }
+    diagnostics.block_time_rejections.print();
+    if excluded > 0 {
+        println!(
+            "🚫 {} slots outside the accepted window [{}, {})ms were excluded",
+            excluded, args.min_latency_ms, args.max_latency_ms
+        );
+    }
+
+    diagnostics.streams = multi
+        .summary()
+        .into_iter()
+        .map(|penalty| (endpoints[penalty.stream].0.clone(), penalty))
+        .collect();
+    if !latencies.is_empty() {
+        print_benchmark_results(
+            &latencies,
+            args,
+            &environment,
+            None,
+            &diagnostics,
+            excluded,
+            None,
+        );
+    } else {
+        println!("❌ No slot with a block time was scored during the test period");
+    }
+
+    let measurements = latencies
+        .iter()
+        .map(|l| Measurement {
+            slot: l.slot,
+            received_ms: l.received_ms,
+            latency_ms: l.propagation_latency_ms,
+            created_at_ms: None,
+        })
+        .collect();
+    let endpoint = if by_region {
+        endpoints
+            .iter()
+            .map(|(_, endpoint)| endpoint.as_str())
+            .collect::<Vec<_>>()
+            .join(",")
+    } else {
+        args.endpoint.clone()
+    };
+    Ok(BenchmarkReport::new(
+        "laserstream_benchmark",
+        &endpoint,
+        measurements,
+        excluded,
+        warmup.discarded,
+    ))
+}
+
+/// `--regions` names, `all` expanded to [`REGIONS`], duplicates dropped.
+fn region_list(regions: &[String]) -> Vec<String> {
+    let mut list: Vec<String> = Vec::new();
+    for region in regions {
+        let names = if region == "all" {
+            REGIONS.iter().map(|r| r.to_string()).collect()
+        } else {
+            vec![region.trim().to_lowercase()]
+        };
+        for name in names {
+            if !list.contains(&name) {
+                list.push(name);
+            }
+        }
+    }
+    list
+}
+
+/// Regions ranked by median penalty vs the fastest region of each slot.
+/// Regions that delivered nothing go last.
+fn print_region_matrix(endpoints: &[(String, String)], multi: &MultiStream) {
+    let mut ranking = multi.summary();
+    ranking.sort_by_key(|stream| {
+        stream
+            .penalty
+            .as_ref()
+            .map_or(i64::MAX, |penalty| penalty.p50_ms)
+    });
+
+    println!(
+        "🌍 Region penalty vs the fastest region of each slot ({} slots scored, {} delivered by all):",
+        multi.scored_slots(),
+        multi.complete()
+    );
+    println!("Rank | Region | Fastest | Missed | Dups   | Median | P95    | Avg");
+    println!("{}", "-".repeat(71));
+    for (rank, stream) in ranking.iter().enumerate() {
+        let region = &endpoints[stream.stream].0;
+        match &stream.penalty {
+            Some(penalty) => println!(
+                "{:<4} | {:<6} | {:>7} | {:>6} | {:>6} | {:>4}ms | {:>4}ms | {:.1}ms",
+                rank + 1,
+                region,
+                stream.first,
+                stream.missed,
+                stream.duplicates,
+                penalty.p50_ms,
+                penalty.p95_ms,
+                penalty.avg_ms
+            ),
+            None => println!(
+                "{:<4} | {:<6} | {:>7} | {:>6} | {:>6} | no slots delivered",
+                rank + 1,
+                region,
+                stream.first,
+                stream.missed,
+                stream.duplicates
+            ),
+        }
+    }
+    multi.print_late();
+}
+
+fn print_region_ranking(ranking: &[(String, f64)]) {
+    println!();
+    println!("Rank | Region | Median");
+    println!("{}", "-".repeat(25));
+    for (rank, (region, median)) in ranking.iter().enumerate() {
+        println!("{:<4} | {:<6} | {:.0}ms", rank + 1, region, median);
+    }
+    println!();
+}
+
+/// Poll an HTTP RPC for new processed slots and record block propagation
+/// latency the same way rpc_latency_test does, until `done` resolves (the
+/// stream loop ended) or shutdown.
+async fn measure_rpc_baseline(
+    rpc_url: String,
+    mut done: oneshot::Receiver<()>,
+    window: Range<i64>,
+    mut shutdown: Shutdown,
+) -> Result<Vec<i64>> {
+    let client = Client::builder().timeout(Duration::from_secs(5)).build()?;
+
+    let mut latencies = Vec::new();
+    let mut last_slot = rpc::get_latest_slot(&client, &rpc_url, Commitment::Processed).await?;
+
+    loop {
+        if let Ok(current_slot) = rpc::retry(
+            || rpc::get_latest_slot(&client, &rpc_url, Commitment::Processed),
+            rpc::POLL_RETRY_ATTEMPTS,
+            rpc::POLL_RETRY_BASE_DELAY,
+        )
+        .await
+        {
+            if current_slot > last_slot {
+                let detected_time = clock::now_ms();
+
+                if let Ok(Some(block_time)) = rpc::retry(
+                    || rpc::get_block_time(&client, &rpc_url, current_slot),
+                    rpc::POLL_RETRY_ATTEMPTS,
+                    rpc::POLL_RETRY_BASE_DELAY,
+                )
+                .await
+                {
+                    let latency_ms = detected_time - (block_time * 1000);
+                    if window.contains(&latency_ms) {
+                        latencies.push(latency_ms);
+                    }
+                }
+                last_slot = current_slot;
+            }
+        }
+
+        tokio::select! {
+            _ = shutdown.wait() => break,
+            _ = &mut done => break,
+            _ = time::sleep(Duration::from_millis(RPC_BASELINE_POLL_MS)) => {}
+        }
+    }
+
+    Ok(latencies)
+}
+
+fn methodology(args: &Args) -> Methodology {
+    Methodology {
+        tool: "laserstream_benchmark",
+        source: "gRPC block stream (Helius Laserstream)",
+        latency_formula: "received_time_ms - block_time * 1000",
+        time_source: args.time_source.to_string(),
+        commitment: "processed (stream default)",
+        poll_interval_ms: None,
+        poll_interval_max_ms: None,
+        latency_filter: Some(format!(
+            "{} <= latency_ms < {}",
+            args.min_latency_ms, args.max_latency_ms
+        )),
+        // --prewarm-dns discards the first update
+        warmup_samples: args.warmup + args.prewarm_dns as u64,
+        percentile_definition: stats::PERCENTILE_DEFINITION,
+        status_thresholds: args.thresholds(),
+    }
+}
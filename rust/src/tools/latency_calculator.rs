@@ -0,0 +1,1734 @@
+//! Average block latency over a fixed number of blocks by RPC, gRPC or WebSocket (`latency_calculator`, `bench websocket`).
+
+use crate::block;
+use crate::block_time::{self, BlockTimeRejections};
+use crate::capabilities::{self, Capability};
+use crate::clock::{self, TimeSource};
+use crate::environment::RunEnvironment;
+use crate::grpc_source::{self, GrpcKind, GrpcSource};
+use crate::keepalive::{Keepalive, KeepaliveEvent, PONG_TIMEOUT};
+use crate::logging::{self, LogFormat};
+use crate::methodology::Methodology;
+use crate::pubkey;
+use crate::report::{BenchmarkReport, Measurement};
+use crate::rpc::{self, http_url, websocket_url, Commitment};
+use crate::shutdown::Shutdown;
+use crate::slot_rate;
+use crate::stall::StallDetector;
+use crate::stats::{self, LatencyStats, OutlierStats, Percentiles};
+use crate::status::{LatencyStatus, StatusThresholds};
+use crate::stop::StopCondition;
+use crate::warmup::{Warmup, WARMUP_LABEL};
+use anyhow::Result;
+use clap::Parser;
+use futures::StreamExt;
+use futures_util::{SinkExt, StreamExt as FuturesStreamExt};
+use helius_laserstream::grpc::{
+    SlotStatus, SubscribeRequestFilterAccounts, SubscribeRequestFilterBlocksMeta,
+    SubscribeRequestFilterSlots,
+};
+use helius_laserstream::grpc::{SubscribeRequest, SubscribeRequestFilterBlocks};
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::collections::hash_map::{Entry, HashMap};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::task::JoinSet;
+use tokio::time::{self, Instant};
+use tokio_tungstenite::{
+    connect_async, tungstenite::protocol::Message, MaybeTlsStream, WebSocketStream,
+};
+use tracing::{debug, trace, warn};
+
+const RPC_POLL_INTERVAL_MS: u64 = rpc::SLOT_POLL_INTERVAL_MS;
+/// Account updates whose block meta has not arrived within this many slots
+/// are dropped.
+const MAX_PENDING_SLOTS: u64 = 150;
+/// After a duration stop, how long to keep reading for the block metas of
+/// slots already stamped.
+const BLOCK_META_DRAIN: Duration = Duration::from_secs(5);
+
+#[derive(Parser)]
+#[command(name = "latency-calculator")]
+#[command(about = "Calculate average latency for RPC or gRPC over specified number of blocks")]
+pub struct Args {
+    #[arg(
+        long,
+        value_enum,
+        help = "Method to test: rpc, grpc, websocket, account, or slot"
+    )]
+    method: Method,
+
+    #[arg(
+        long,
+        value_parser = pubkey::parse_pubkey,
+        required_if_eq("method", "account"),
+        help = "Account pubkey to watch with --method account"
+    )]
+    account: Option<String>,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "websocket",
+        help = "Stream for --method account: websocket (accountSubscribe) or grpc (account filter)"
+    )]
+    account_stream: StreamKind,
+
+    #[arg(
+        long,
+        default_value = "60",
+        value_name = "SECS",
+        help = "With --method account, stop when the account has not changed for SECS seconds"
+    )]
+    account_timeout: u64,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "websocket",
+        help = "Stream for --method slot: websocket (slotSubscribe) or grpc (slot filter + block meta)"
+    )]
+    slot_stream: StreamKind,
+
+    #[arg(long, help = "Endpoint URL")]
+    endpoint: String,
+
+    #[arg(
+        long,
+        help = "API key (for gRPC; appended as ?api-key= to WebSocket endpoints that carry none)"
+    )]
+    api_key: Option<String>,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "helius",
+        help = "gRPC provider: helius (Laserstream, --api-key or HELIUS_API_KEY) or yellowstone (vanilla Yellowstone, --api-key sent as x-token)"
+    )]
+    grpc_kind: GrpcKind,
+
+    #[arg(
+        long,
+        required_unless_present_any = ["target_slot", "duration"],
+        help = "Number of blocks to calculate average latency (with --duration, whichever comes first)"
+    )]
+    blocks: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "MINUTES",
+        help = "Stop after MINUTES minutes (with --blocks, whichever comes first)"
+    )]
+    duration: Option<u64>,
+
+    #[arg(
+        long,
+        num_args = 0..=1,
+        value_name = "SLOT",
+        help = "Single-shot probe: measure one block (the latest, or SLOT if given), print one line and exit"
+    )]
+    target_slot: Option<Option<u64>>,
+
+    #[arg(
+        long,
+        help = "Debug diagnostics on stderr (same as RUST_LOG=solana_rpc_performance_rust=debug)"
+    )]
+    verbose: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "text",
+        help = "Format of the diagnostics on stderr; RUST_LOG sets their levels"
+    )]
+    log_format: LogFormat,
+
+    #[arg(long, help = "Print the methodology constants used in this run")]
+    fairness_report: bool,
+
+    #[arg(long, help = "Skip the best-effort public IP / location lookup")]
+    no_env_probe: bool,
+
+    #[arg(
+        long,
+        help = "Skip the startup check of which RPC methods and commitments the provider supports"
+    )]
+    no_capability_probe: bool,
+
+    #[arg(
+        long,
+        value_name = "EXCELLENT,GOOD,FAIR",
+        help = "Status band upper bounds in ms (default 500,1000,2000)"
+    )]
+    status_thresholds: Option<StatusThresholds>,
+
+    #[arg(
+        long,
+        default_value = "clock_realtime",
+        value_name = "clock_realtime|ntp[:server]|file:<path>",
+        help = "Wall clock for latency math; ntp[:server] applies an offset measured once at startup, file:<path> the PTP/GPS offset (ms) read from that file"
+    )]
+    time_source: TimeSource,
+
+    #[arg(
+        long,
+        default_value = "0",
+        value_name = "N",
+        help = "Discard the first N measurements from the statistics (still printed live, marked warmup)"
+    )]
+    warmup: u64,
+
+    #[arg(
+        long,
+        default_value = "5",
+        value_name = "PERCENT",
+        help = "Percent of measurements dropped from each end for the trimmed average"
+    )]
+    trim_percent: f64,
+
+    #[arg(
+        long,
+        default_value = "50,90,95,99",
+        value_name = "LIST",
+        help = "Percentiles to report, e.g. 50,90,99,99.9"
+    )]
+    percentiles: Percentiles,
+
+    #[arg(
+        long,
+        help = "Print an ASCII histogram of the latency distribution with the results"
+    )]
+    histogram: bool,
+
+    #[arg(
+        long,
+        requires = "histogram",
+        value_name = "MS",
+        value_parser = clap::value_parser!(i64).range(1..),
+        help = "Bucket width of --histogram (default: chosen from the min/max latency)"
+    )]
+    bucket_ms: Option<i64>,
+
+    #[arg(
+        long,
+        default_value = "15",
+        value_name = "SECS",
+        help = "Send a WebSocket ping every SECS seconds so idle providers keep the connection open (0 disables)"
+    )]
+    ws_ping_interval: u64,
+
+    #[arg(
+        long,
+        default_value = "5000",
+        value_name = "MS",
+        help = "With --method websocket, a gap of more than MS between blocks is a stall: warned about, counted, and the block ending it left out of the statistics"
+    )]
+    stall_threshold_ms: u64,
+
+    #[arg(
+        long,
+        help = "With --method websocket, reconnect and resubscribe when the feed stalls"
+    )]
+    reconnect_on_stall: bool,
+}
+
+impl Args {
+    fn grpc_source(&self) -> Result<Box<dyn GrpcSource>> {
+        let token = match self.grpc_kind {
+            GrpcKind::Helius => self
+                .api_key
+                .clone()
+                .or_else(|| std::env::var("HELIUS_API_KEY").ok()),
+            GrpcKind::Yellowstone => self.api_key.clone(),
+        };
+        grpc_source::source(self.grpc_kind, &self.endpoint, token)
+    }
+
+    /// Starts the clock, so build it right before the measurement loop.
+    /// A single-shot probe stops after one block.
+    fn stop_condition(&self) -> Result<StopCondition> {
+        if self.single_shot() {
+            return StopCondition::new(None, Some(1));
+        }
+        StopCondition::new(self.duration, self.blocks)
+    }
+
+    fn single_shot(&self) -> bool {
+        self.target_slot.is_some()
+    }
+
+    /// Lowest slot a single-shot probe will accept from a stream.
+    fn min_slot(&self) -> u64 {
+        self.target_slot.flatten().unwrap_or(0)
+    }
+
+    /// Provider methods the chosen method depends on; gRPC streams need
+    /// none of them.
+    fn required_capabilities(&self) -> Vec<Capability> {
+        match self.method {
+            Method::Rpc => vec![
+                Capability::GetSlot(Commitment::Processed),
+                Capability::GetBlockTime,
+            ],
+            Method::Websocket => vec![Capability::BlockSubscribe(Commitment::Processed)],
+            Method::Account if matches!(self.account_stream, StreamKind::Websocket) => {
+                vec![Capability::GetBlockTime]
+            }
+            Method::Slot if matches!(self.slot_stream, StreamKind::Websocket) => {
+                vec![Capability::GetBlockTime]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// A single-shot probe measures one block, so it has no warmup.
+    fn warmup(&self) -> Warmup {
+        Warmup::new(if self.single_shot() { 0 } else { self.warmup })
+    }
+
+    fn thresholds(&self) -> StatusThresholds {
+        self.status_thresholds
+            .unwrap_or(StatusThresholds::BLOCK_PROPAGATION)
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Debug)]
+enum Method {
+    Rpc,
+    Grpc,
+    Websocket,
+    /// Updates to `--account`, timed against the block time of their slot.
+    Account,
+    /// Slot notifications: arrival cadence, and arrival vs the block time
+    /// of the slot.
+    Slot,
+}
+
+#[derive(clap::ValueEnum, Clone, Debug)]
+enum StreamKind {
+    Websocket,
+    Grpc,
+}
+
+#[derive(Debug, Clone)]
+struct LatencyMeasurement {
+    slot: u64,
+    /// Unix seconds.
+    block_time: i64,
+    /// Unix ms.
+    received_time: i64,
+    /// `received_time - block_time * 1000`.
+    latency_ms: i64,
+    /// The notification carried no slot, so it is `parentSlot + 1`.
+    slot_inferred: bool,
+}
+
+pub async fn run(args: Args) -> Result<BenchmarkReport, Box<dyn std::error::Error>> {
+    logging::init(args.log_format, args.verbose, false);
+    clock::init(&args.time_source)?;
+
+    if args.single_shot() {
+        let measurement = run_target_slot_probe(&args).await?;
+        return Ok(report(&args, &[measurement], 0));
+    }
+
+    println!("🚀 Latency Calculator");
+    println!("Method: {:?}", args.method);
+    println!("Endpoint: {}", args.endpoint);
+    println!("Stop after: {}", args.stop_condition()?);
+    println!("Time source: {}", args.time_source);
+    println!();
+
+    let required = args.required_capabilities();
+    if !required.is_empty() && !args.no_capability_probe {
+        let client = Client::builder().timeout(Duration::from_secs(10)).build()?;
+        // The RPC method polls the endpoint as given, the others derive it
+        let rpc_url = match args.method {
+            Method::Rpc => args.endpoint.clone(),
+            _ => http_url(&args.endpoint, args.api_key.as_deref()),
+        };
+        let ws_url = websocket_url(&args.endpoint, args.api_key.as_deref());
+        let capabilities = capabilities::probe(&client, &rpc_url, &ws_url).await;
+        capabilities.print();
+        capabilities.require(&required)?;
+    }
+
+    let environment = RunEnvironment::capture(&args.endpoint, !args.no_env_probe).await;
+
+    let mut warmup = args.warmup();
+    let mut rejections = BlockTimeRejections::default();
+    let mut stalls = StallDetector::new(args.stall_threshold_ms);
+    let measurements = match args.method {
+        Method::Rpc => measure_rpc_latency(&args, &mut warmup, &mut rejections).await?,
+        Method::Grpc => measure_grpc_latency(&args, &mut warmup, &mut rejections).await?,
+        Method::Websocket => {
+            measure_websocket_latency(&args, &mut warmup, &mut rejections, &mut stalls).await?
+        }
+        Method::Account => measure_account_latency(&args, &mut warmup, &mut rejections).await?,
+        Method::Slot => measure_slot_latency(&args, &mut warmup, &mut rejections).await?,
+    };
+
+    print_results(&measurements, &args);
+    rejections.print();
+    stalls.print();
+    environment.print();
+
+    let mut report = report(&args, &measurements, warmup.discarded);
+    if matches!(args.method, Method::Websocket) {
+        report.stalls = Some(stalls.stats());
+    }
+    Ok(report)
+}
+
+fn report(
+    args: &Args,
+    measurements: &[LatencyMeasurement],
+    warmup_discarded: u64,
+) -> BenchmarkReport {
+    let measurements = measurements
+        .iter()
+        .map(|m| Measurement {
+            slot: m.slot,
+            received_ms: m.received_time,
+            latency_ms: m.latency_ms,
+            created_at_ms: None,
+        })
+        .collect();
+    BenchmarkReport::new(
+        "latency_calculator",
+        &args.endpoint,
+        measurements,
+        0,
+        warmup_discarded,
+    )
+}
+
+/// Measure a single block's latency and print it as one `key=value` line,
+/// so the probe can be scripted from a shell loop.
+async fn run_target_slot_probe(
+    args: &Args,
+) -> Result<LatencyMeasurement, Box<dyn std::error::Error>> {
+    let warmup = &mut args.warmup();
+    let rejections = &mut BlockTimeRejections::default();
+    let measurement = match (&args.method, args.target_slot.flatten()) {
+        (Method::Rpc, Some(slot)) => probe_rpc_slot(&args.endpoint, slot).await?,
+        (Method::Rpc, None) => measure_rpc_latency(args, warmup, rejections)
+            .await?
+            .into_iter()
+            .next(),
+        (Method::Grpc, _) => measure_grpc_latency(args, warmup, rejections)
+            .await?
+            .into_iter()
+            .next(),
+        (Method::Websocket, _) => measure_websocket_latency(
+            args,
+            warmup,
+            rejections,
+            &mut StallDetector::new(args.stall_threshold_ms),
+        )
+        .await?
+        .into_iter()
+        .next(),
+        (Method::Account, _) => measure_account_latency(args, warmup, rejections)
+            .await?
+            .into_iter()
+            .next(),
+        (Method::Slot, _) => measure_slot_latency(args, warmup, rejections)
+            .await?
+            .into_iter()
+            .next(),
+    };
+
+    let measurement =
+        measurement.ok_or_else(|| anyhow::anyhow!("No block measured for single-shot probe"))?;
+
+    println!(
+        "slot={} block_time={} received_time={} latency_ms={} method={}",
+        measurement.slot,
+        measurement.block_time,
+        measurement.received_time,
+        measurement.latency_ms,
+        format!("{:?}", args.method).to_lowercase()
+    );
+
+    Ok(measurement)
+}
+
+/// Fetch the block time of an explicit slot, retrying briefly while the RPC
+/// has not produced it yet.
+async fn probe_rpc_slot(endpoint: &str, slot: u64) -> Result<Option<LatencyMeasurement>> {
+    let client = Client::builder().timeout(Duration::from_secs(10)).build()?;
+
+    for _ in 0..20 {
+        if let Some(block_time) = rpc::get_block_time(&client, endpoint, slot).await? {
+            let received_time = clock::now_ms();
+
+            return Ok(Some(LatencyMeasurement {
+                slot,
+                block_time,
+                received_time,
+                latency_ms: received_time - (block_time * 1000),
+                slot_inferred: false,
+            }));
+        }
+        time::sleep(Duration::from_millis(250)).await;
+    }
+
+    Err(anyhow::anyhow!(
+        "Block time for slot {} not available",
+        slot
+    ))
+}
+
+async fn measure_rpc_latency(
+    args: &Args,
+    warmup: &mut Warmup,
+    rejections: &mut BlockTimeRejections,
+) -> Result<Vec<LatencyMeasurement>> {
+    let mut shutdown = Shutdown::install();
+    let client = Client::builder().timeout(Duration::from_secs(10)).build()?;
+    let mut measurements = Vec::new();
+    let mut processed_blocks = 0u64;
+
+    if !args.single_shot() {
+        println!("📡 Starting RPC latency measurement...");
+        println!("Slot       | Block Time    | Received Time | Latency   | Status");
+        println!("{}", "-".repeat(70));
+    }
+
+    let mut last_slot =
+        rpc::get_latest_slot(&client, &args.endpoint, Commitment::Processed).await?;
+
+    let stop = args.stop_condition()?;
+    while stop.running(processed_blocks) {
+        let fetch_slot = || rpc::get_latest_slot(&client, &args.endpoint, Commitment::Processed);
+        match rpc::retry(
+            fetch_slot,
+            rpc::POLL_RETRY_ATTEMPTS,
+            rpc::POLL_RETRY_BASE_DELAY,
+        )
+        .await
+        {
+            Ok(current_slot) => {
+                if current_slot > last_slot {
+                    // Process the new slot
+                    let fetch_block_time =
+                        || rpc::get_block_time(&client, &args.endpoint, current_slot);
+                    match rpc::retry(
+                        fetch_block_time,
+                        rpc::POLL_RETRY_ATTEMPTS,
+                        rpc::POLL_RETRY_BASE_DELAY,
+                    )
+                    .await
+                    {
+                        Ok(Some(block_time)) => {
+                            let received_time = clock::now_ms();
+
+                            let latency_ms = received_time - (block_time * 1000);
+
+                            if rejections.accept(block_time, current_slot, received_time) {
+                                let measurement = LatencyMeasurement {
+                                    slot: current_slot,
+                                    block_time,
+                                    received_time,
+                                    latency_ms,
+                                    slot_inferred: false,
+                                };
+
+                                record_measurement(args, warmup, &mut measurements, measurement);
+                                processed_blocks = measurements.len() as u64;
+
+                                debug!("Progress: {} processed", stop.progress(processed_blocks));
+                            }
+                        }
+                        Ok(None) => {
+                            debug!("Block time not available for slot {}", current_slot);
+                        }
+                        Err(e) => {
+                            debug!("Error getting block time for slot {}: {}", current_slot, e);
+                        }
+                    }
+                    last_slot = current_slot;
+                }
+            }
+            Err(e) => {
+                debug!("Error getting latest slot: {}", e);
+            }
+        }
+
+        tokio::select! {
+            _ = shutdown.wait() => break,
+            _ = time::sleep(Duration::from_millis(RPC_POLL_INTERVAL_MS)) => {}
+        }
+    }
+
+    Ok(measurements)
+}
+
+async fn measure_grpc_latency(
+    args: &Args,
+    warmup: &mut Warmup,
+    rejections: &mut BlockTimeRejections,
+) -> Result<Vec<LatencyMeasurement>> {
+    let mut shutdown = Shutdown::install();
+    let source = args.grpc_source()?;
+
+    let mut request = SubscribeRequest::default();
+
+    request.blocks_meta.insert(
+        "all".to_string(),
+        SubscribeRequestFilterBlocksMeta::default(),
+    );
+
+    let mut stream = source.subscribe(request);
+
+    let mut measurements = Vec::new();
+    let mut processed_blocks = 0u64;
+
+    if !args.single_shot() {
+        println!(
+            "📡 Starting gRPC latency measurement ({})...",
+            source.name()
+        );
+        println!("Slot       | Block Time    | Received Time | Latency   | Status");
+        println!("{}", "-".repeat(70));
+    }
+
+    let stop = args.stop_condition()?;
+    while stop.running(processed_blocks) {
+        let next = tokio::select! {
+            _ = shutdown.wait() => break,
+            next = stream.next() => next,
+        };
+        if let Some(result) = next {
+            match result {
+                Ok(update) => {
+                    let received_time = clock::now_ms();
+
+                    if let Some(
+                        helius_laserstream::grpc::subscribe_update::UpdateOneof::BlockMeta(block),
+                    ) = update.update_oneof
+                    {
+                        let slot = block.slot;
+
+                        if let Some(bt) = block.block_time {
+                            let block_time = bt.timestamp;
+                            let latency_ms = received_time - (block_time * 1000);
+
+                            if slot >= args.min_slot()
+                                && rejections.accept(block_time, slot, received_time)
+                            {
+                                let measurement = LatencyMeasurement {
+                                    slot,
+                                    block_time,
+                                    received_time,
+                                    latency_ms,
+                                    slot_inferred: false,
+                                };
+
+                                record_measurement(args, warmup, &mut measurements, measurement);
+                                processed_blocks = measurements.len() as u64;
+
+                                debug!("Progress: {} processed", stop.progress(processed_blocks));
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    debug!("gRPC stream error: {}", e);
+                }
+            }
+        }
+    }
+
+    Ok(measurements)
+}
+
+async fn measure_websocket_latency(
+    args: &Args,
+    warmup: &mut Warmup,
+    rejections: &mut BlockTimeRejections,
+    stalls: &mut StallDetector,
+) -> Result<Vec<LatencyMeasurement>> {
+    let mut shutdown = Shutdown::install();
+    let mut measurements = Vec::new();
+    let mut processed_blocks = 0u64;
+
+    if !args.single_shot() {
+        println!("📡 Starting WebSocket latency measurement...");
+        println!("Slot       | Block Time    | Received Time | Latency   | Status");
+        println!("{}", "-".repeat(70));
+    }
+
+    let (mut write, mut read) = subscribe_blocks(args).await?.split();
+
+    // Handle subscription confirmation and block notifications
+    let mut subscription_confirmed = false;
+    let mut keepalive = Keepalive::new(args.ws_ping_interval);
+    // Only text frames reset it, so pongs cannot hide a silent subscription
+    let idle_timeout = Duration::from_secs(30);
+    let timeout = time::sleep(idle_timeout);
+    tokio::pin!(timeout);
+    let stop = args.stop_condition()?;
+    while stop.running(processed_blocks) {
+        tokio::select! {
+            _ = shutdown.wait() => break,
+            event = keepalive.next() => handle_keepalive(event, &mut write).await?,
+            msg_result = read.next() => {
+                if let Some(msg) = msg_result {
+                    match msg? {
+                        Message::Text(text) => {
+                            let received_time = clock::now_ms();
+                            timeout.as_mut().reset(Instant::now() + idle_timeout);
+
+                            if let Ok(json_msg) = serde_json::from_str::<Value>(&text) {
+                                trace!("Received WebSocket message: {}", serde_json::to_string_pretty(&json_msg).unwrap_or_else(|_| "Invalid JSON".to_string()));
+                                // Check if this is a block notification
+                                if let Some(params) = json_msg.get("params") {
+                                    if let Some(result) = params.get("result") {
+                                        if let Some(block) = result.pointer("/value/block") {
+                                            if let Some((slot, slot_inferred)) = block::notification_slot(result) {
+                                                if slot_inferred {
+                                                    warn!("Block notification carried no slot, inferred {} from parentSlot", slot);
+                                                }
+
+                                                if let Some(gap) = stalls.block() {
+                                                    println!("⏯️  Slot {} ended a {}ms stall, left out of the statistics", slot, gap.as_millis());
+                                                } else if let Some(block_time) = block.get("blockTime").and_then(|bt| bt.as_i64()) {
+                                                    let latency_ms = received_time - (block_time * 1000);
+
+                                                    if slot >= args.min_slot() && rejections.accept(block_time, slot, received_time) {
+                                                        let measurement = LatencyMeasurement {
+                                                            slot,
+                                                            block_time,
+                                                            received_time,
+                                                            latency_ms,
+                                                            slot_inferred,
+                                                        };
+
+                                                        record_measurement(args, warmup, &mut measurements, measurement);
+                                                        processed_blocks = measurements.len() as u64;
+
+                                                        debug!("Progress: {} processed", stop.progress(processed_blocks));
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                } else if json_msg.get("result").is_some() {
+                                    // Subscription confirmation
+                                    subscription_confirmed = true;
+                                    debug!("WebSocket subscription confirmed");
+                                }
+                            }
+                        }
+                        Message::Close(_) => {
+                            println!("WebSocket connection closed");
+                            break;
+                        }
+                        Message::Pong(_) => keepalive.pong(),
+                        // tungstenite queues the pong reply itself
+                        _ => {}
+                    }
+                } else {
+                    println!("WebSocket connection ended");
+                    break;
+                }
+            }
+            _ = stalls.wait() => {
+                let silent = stalls.begin();
+                println!("⚠️  No block for {}ms, the feed has stalled", silent.as_millis());
+                if args.reconnect_on_stall {
+                    println!("🔄 Reconnecting...");
+                    (write, read) = subscribe_blocks(args).await?.split();
+                    subscription_confirmed = false;
+                    timeout.as_mut().reset(Instant::now() + idle_timeout);
+                }
+            }
+            _ = &mut timeout => {
+                if !subscription_confirmed {
+                    return Err(anyhow::anyhow!("WebSocket subscription timeout"));
+                } else {
+                    println!("No new blocks received in 30 seconds, continuing...");
+                    timeout.as_mut().reset(Instant::now() + idle_timeout);
+                }
+            }
+        }
+    }
+    stalls.finish();
+
+    Ok(measurements)
+}
+
+/// Connect and send the `blockSubscribe` request.
+async fn subscribe_blocks(args: &Args) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>> {
+    let (mut ws_stream, _) =
+        connect_async(&websocket_url(&args.endpoint, args.api_key.as_deref())).await?;
+
+    // Subscribe to block notifications
+    let subscription = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "blockSubscribe",
+        "params": [
+            "all",
+            {
+                "commitment": "processed",
+                "encoding": "json",
+                "transactionDetails": "none",
+                "rewards": false
+            }
+        ]
+    });
+
+    ws_stream
+        .send(Message::Text(subscription.to_string()))
+        .await?;
+    Ok(ws_stream)
+}
+
+async fn measure_account_latency(
+    args: &Args,
+    warmup: &mut Warmup,
+    rejections: &mut BlockTimeRejections,
+) -> Result<Vec<LatencyMeasurement>> {
+    let account = args
+        .account
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("--account is required for the account method"))?;
+
+    if !args.single_shot() {
+        println!(
+            "📡 Starting account update latency measurement for {} ({:?})...",
+            account, args.account_stream
+        );
+        println!("Slot       | Block Time    | Received Time | Latency   | Status");
+        println!("{}", "-".repeat(70));
+    }
+
+    match args.account_stream {
+        StreamKind::Websocket => {
+            measure_websocket_account_latency(args, account, warmup, rejections).await
+        }
+        StreamKind::Grpc => measure_grpc_account_latency(args, account, warmup, rejections).await,
+    }
+}
+
+/// `accountSubscribe` notifications carry only the slot, so the block time
+/// is looked up over HTTP after the update has been timestamped.
+async fn measure_websocket_account_latency(
+    args: &Args,
+    account: &str,
+    warmup: &mut Warmup,
+    rejections: &mut BlockTimeRejections,
+) -> Result<Vec<LatencyMeasurement>> {
+    let mut shutdown = Shutdown::install();
+    let client = Client::builder().timeout(Duration::from_secs(10)).build()?;
+    let rpc_url = http_url(&args.endpoint, args.api_key.as_deref());
+    let mut measurements = Vec::new();
+
+    let (ws_stream, _) =
+        connect_async(&websocket_url(&args.endpoint, args.api_key.as_deref())).await?;
+    let (mut write, mut read) = ws_stream.split();
+
+    let subscription = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "accountSubscribe",
+        "params": [
+            account,
+            {
+                "commitment": "processed",
+                "encoding": "base64"
+            }
+        ]
+    });
+
+    write.send(Message::Text(subscription.to_string())).await?;
+
+    let idle = Duration::from_secs(args.account_timeout);
+    let mut deadline = Instant::now() + idle;
+    let mut last_slot = None;
+    let mut lookups = JoinSet::new();
+    let mut keepalive = Keepalive::new(args.ws_ping_interval);
+    let stop = args.stop_condition()?;
+    while stop.running(measurements.len() as u64) {
+        let msg = tokio::select! {
+            _ = shutdown.wait() => break,
+            _ = time::sleep_until(deadline) => {
+                print_account_timeout(account, args.account_timeout, measurements.len());
+                break;
+            }
+            event = keepalive.next() => {
+                handle_keepalive(event, &mut write).await?;
+                continue;
+            }
+            Some(lookup) = lookups.join_next() => {
+                accept_lookup(args, warmup, rejections, &mut measurements, lookup?);
+                continue;
+            }
+            msg = read.next() => msg,
+        };
+
+        let text = match msg {
+            Some(Ok(Message::Text(text))) => text,
+            Some(Ok(Message::Close(_))) | None => {
+                println!("WebSocket connection closed");
+                break;
+            }
+            Some(Ok(Message::Pong(_))) => {
+                keepalive.pong();
+                continue;
+            }
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => return Err(e.into()),
+        };
+        let received_time = clock::now_ms();
+
+        let Ok(json_msg) = serde_json::from_str::<Value>(&text) else {
+            continue;
+        };
+        let Some(slot) = json_msg
+            .pointer("/params/result/context/slot")
+            .and_then(Value::as_u64)
+        else {
+            if json_msg.get("result").is_some() {
+                debug!("WebSocket account subscription confirmed");
+            }
+            continue;
+        };
+        deadline = Instant::now() + idle;
+
+        // Several writes can land in one slot; the first one is the latency
+        if last_slot == Some(slot) {
+            continue;
+        }
+        last_slot = Some(slot);
+
+        // The block time can take seconds to appear; looking it up here would
+        // leave later updates unread and stamp them late
+        let (client, rpc_url) = (client.clone(), rpc_url.clone());
+        lookups.spawn(async move {
+            (
+                slot,
+                received_time,
+                wait_for_block_time(&client, &rpc_url, slot).await,
+            )
+        });
+    }
+
+    // Updates already stamped still count, up to the --blocks target
+    while let Some(lookup) = lookups.join_next().await {
+        if stop
+            .blocks()
+            .is_some_and(|limit| measurements.len() as u64 >= limit)
+        {
+            break;
+        }
+        accept_lookup(args, warmup, rejections, &mut measurements, lookup?);
+    }
+
+    Ok(measurements)
+}
+
+/// Record an account update or slot whose block time lookup has finished.
+fn accept_lookup(
+    args: &Args,
+    warmup: &mut Warmup,
+    rejections: &mut BlockTimeRejections,
+    measurements: &mut Vec<LatencyMeasurement>,
+    (slot, received_time, block_time): (u64, i64, Result<Option<i64>>),
+) {
+    match block_time {
+        Ok(Some(block_time)) => accept_measurement(
+            args,
+            warmup,
+            rejections,
+            measurements,
+            slot,
+            block_time,
+            received_time,
+        ),
+        Ok(None) => {
+            debug!("Block time not available for slot {}", slot);
+        }
+        Err(e) => {
+            debug!("Error getting block time for slot {}: {}", slot, e);
+        }
+    }
+}
+
+/// Account updates usually arrive before their slot's block meta, so they are
+/// held until the block time for the slot is known.
+async fn measure_grpc_account_latency(
+    args: &Args,
+    account: &str,
+    warmup: &mut Warmup,
+    rejections: &mut BlockTimeRejections,
+) -> Result<Vec<LatencyMeasurement>> {
+    let mut shutdown = Shutdown::install();
+    let source = args.grpc_source()?;
+
+    let mut request = SubscribeRequest::default();
+    request.accounts.insert(
+        "account".to_string(),
+        SubscribeRequestFilterAccounts {
+            account: vec![account.to_string()],
+            ..Default::default()
+        },
+    );
+    request.blocks_meta.insert(
+        "all".to_string(),
+        SubscribeRequestFilterBlocksMeta::default(),
+    );
+
+    let mut stream = source.subscribe(request);
+
+    let mut measurements = Vec::new();
+    let mut pending: HashMap<u64, i64> = HashMap::new();
+    let mut block_times: HashMap<u64, i64> = HashMap::new();
+
+    let idle = Duration::from_secs(args.account_timeout);
+    let mut deadline = Instant::now() + idle;
+    let stop = args.stop_condition()?;
+    while stop.running(measurements.len() as u64) {
+        let next = tokio::select! {
+            _ = shutdown.wait() => break,
+            _ = time::sleep_until(deadline) => {
+                print_account_timeout(account, args.account_timeout, measurements.len());
+                break;
+            }
+            next = stream.next() => next,
+        };
+        let Some(result) = next else {
+            println!("gRPC stream ended");
+            break;
+        };
+
+        match result {
+            Ok(update) => {
+                let received_time = clock::now_ms();
+
+                match update.update_oneof {
+                    Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::Account(
+                        update,
+                    )) => {
+                        // Startup snapshots are not changes
+                        if update.is_startup {
+                            continue;
+                        }
+                        deadline = Instant::now() + idle;
+
+                        match block_times.get(&update.slot) {
+                            Some(&block_time) => {
+                                // Later writes in the same slot are not counted again
+                                if let Entry::Vacant(entry) = pending.entry(update.slot) {
+                                    entry.insert(received_time);
+                                    accept_measurement(
+                                        args,
+                                        warmup,
+                                        rejections,
+                                        &mut measurements,
+                                        update.slot,
+                                        block_time,
+                                        received_time,
+                                    );
+                                }
+                            }
+                            None => {
+                                pending.entry(update.slot).or_insert(received_time);
+                            }
+                        }
+                    }
+                    Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::BlockMeta(
+                        block,
+                    )) => {
+                        let Some(bt) = block.block_time else {
+                            continue;
+                        };
+                        block_times.insert(block.slot, bt.timestamp);
+                        if let Some(&first_seen) = pending.get(&block.slot) {
+                            accept_measurement(
+                                args,
+                                warmup,
+                                rejections,
+                                &mut measurements,
+                                block.slot,
+                                bt.timestamp,
+                                first_seen,
+                            );
+                        }
+
+                        pending.retain(|&slot, _| slot + MAX_PENDING_SLOTS >= block.slot);
+                        block_times.retain(|&slot, _| slot + MAX_PENDING_SLOTS >= block.slot);
+                    }
+                    _ => {}
+                }
+            }
+            Err(e) => {
+                debug!("gRPC stream error: {}", e);
+            }
+        }
+    }
+
+    Ok(measurements)
+}
+
+/// Time between consecutive slot notifications. Only notifications for the
+/// next slot count; a jump over skipped-over slots would read as a slow one.
+#[derive(Debug, Default)]
+struct SlotCadence {
+    last: Option<(u64, i64)>,
+    intervals_ms: Vec<i64>,
+    slots: u64,
+    /// Notifications more than one slot past the previous one.
+    jumps: u64,
+    /// Repeated or older slots, ignored.
+    stale: u64,
+}
+
+impl SlotCadence {
+    /// True for a slot newer than every one seen so far.
+    fn record(&mut self, slot: u64, received_time: i64) -> bool {
+        match self.last {
+            Some((last_slot, _)) if slot <= last_slot => {
+                self.stale += 1;
+                return false;
+            }
+            Some((last_slot, last_time)) if slot == last_slot + 1 => {
+                self.intervals_ms.push(received_time - last_time)
+            }
+            Some(_) => self.jumps += 1,
+            None => {}
+        }
+        self.last = Some((slot, received_time));
+        self.slots += 1;
+        true
+    }
+
+    fn print(&self) {
+        println!();
+        println!("⏲️  Slot Arrival Cadence ({} slots)", self.slots);
+        match LatencyStats::from_samples(&self.intervals_ms) {
+            Some(stats) => {
+                println!(
+                    "Avg interval:       {:.1}ms ({}ms ideal)",
+                    stats.avg_ms,
+                    1000.0 / slot_rate::IDEAL_SLOTS_PER_SEC
+                );
+                println!("Median interval:    {}ms", stats.p50_ms);
+                println!("95th percentile:    {}ms", stats.p95_ms);
+                println!("Max interval:       {}ms", stats.max_ms);
+                println!("Jitter:             {:.1}ms", stats.jitter_ms);
+            }
+            None => println!("Not enough consecutive slots for a cadence"),
+        }
+        if self.jumps > 0 {
+            println!(
+                "{} notifications skipped past one or more slots",
+                self.jumps
+            );
+        }
+        if self.stale > 0 {
+            println!(
+                "{} repeated or out-of-order notifications ignored",
+                self.stale
+            );
+        }
+    }
+}
+
+async fn measure_slot_latency(
+    args: &Args,
+    warmup: &mut Warmup,
+    rejections: &mut BlockTimeRejections,
+) -> Result<Vec<LatencyMeasurement>> {
+    if !args.single_shot() {
+        println!(
+            "📡 Starting slot notification latency measurement ({:?})...",
+            args.slot_stream
+        );
+        println!("Slot       | Block Time    | Received Time | Latency   | Status");
+        println!("{}", "-".repeat(70));
+    }
+
+    let mut cadence = SlotCadence::default();
+    let measurements = match args.slot_stream {
+        StreamKind::Websocket => {
+            measure_websocket_slot_latency(args, warmup, rejections, &mut cadence).await?
+        }
+        StreamKind::Grpc => {
+            measure_grpc_slot_latency(args, warmup, rejections, &mut cadence).await?
+        }
+    };
+    if !args.single_shot() {
+        cadence.print();
+    }
+
+    Ok(measurements)
+}
+
+/// `slotSubscribe` carries no block time, so each new slot's block time is
+/// looked up over HTTP in the background once it has been timestamped, and
+/// the slot is measured when its lookup finishes.
+async fn measure_websocket_slot_latency(
+    args: &Args,
+    warmup: &mut Warmup,
+    rejections: &mut BlockTimeRejections,
+    cadence: &mut SlotCadence,
+) -> Result<Vec<LatencyMeasurement>> {
+    let mut shutdown = Shutdown::install();
+    let client = Client::builder().timeout(Duration::from_secs(10)).build()?;
+    let rpc_url = http_url(&args.endpoint, args.api_key.as_deref());
+
+    let (ws_stream, _) =
+        connect_async(&websocket_url(&args.endpoint, args.api_key.as_deref())).await?;
+    let (mut write, mut read) = ws_stream.split();
+
+    let subscription = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "slotSubscribe"
+    });
+    write.send(Message::Text(subscription.to_string())).await?;
+
+    let mut measurements = Vec::new();
+    let mut lookups = JoinSet::new();
+    let mut keepalive = Keepalive::new(args.ws_ping_interval);
+    let idle_timeout = Duration::from_secs(30);
+    let timeout = time::sleep(idle_timeout);
+    tokio::pin!(timeout);
+    let stop = args.stop_condition()?;
+    while stop.running(measurements.len() as u64) {
+        let msg = tokio::select! {
+            _ = shutdown.wait() => break,
+            _ = &mut timeout => {
+                println!("No slot notification in {}s; stopping", idle_timeout.as_secs());
+                break;
+            }
+            event = keepalive.next() => {
+                handle_keepalive(event, &mut write).await?;
+                continue;
+            }
+            Some(lookup) = lookups.join_next() => {
+                accept_lookup(args, warmup, rejections, &mut measurements, lookup?);
+                continue;
+            }
+            msg = read.next() => msg,
+        };
+
+        let text = match msg {
+            Some(Ok(Message::Text(text))) => text,
+            Some(Ok(Message::Close(_))) | None => {
+                println!("WebSocket connection closed");
+                break;
+            }
+            Some(Ok(Message::Pong(_))) => {
+                keepalive.pong();
+                continue;
+            }
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => return Err(e.into()),
+        };
+        let received_time = clock::now_ms();
+        timeout.as_mut().reset(Instant::now() + idle_timeout);
+
+        let Ok(json_msg) = serde_json::from_str::<Value>(&text) else {
+            continue;
+        };
+        let Some(slot) = json_msg
+            .pointer("/params/result/slot")
+            .and_then(Value::as_u64)
+        else {
+            if json_msg.get("result").is_some() {
+                debug!("WebSocket slot subscription confirmed");
+            }
+            continue;
+        };
+
+        if cadence.record(slot, received_time) && slot >= args.min_slot() {
+            let (client, rpc_url) = (client.clone(), rpc_url.clone());
+            lookups.spawn(async move {
+                (
+                    slot,
+                    received_time,
+                    wait_for_block_time(&client, &rpc_url, slot).await,
+                )
+            });
+        }
+    }
+
+    // Block times land a few seconds after the slot; slots already stamped
+    // still count, up to the --blocks target
+    while let Some(lookup) = lookups.join_next().await {
+        if stop
+            .blocks()
+            .is_some_and(|limit| measurements.len() as u64 >= limit)
+        {
+            break;
+        }
+        accept_lookup(args, warmup, rejections, &mut measurements, lookup?);
+    }
+
+    Ok(measurements)
+}
+
+/// Slot updates arrive before the block meta of their slot, so each slot's
+/// arrival is held until its block time is known.
+async fn measure_grpc_slot_latency(
+    args: &Args,
+    warmup: &mut Warmup,
+    rejections: &mut BlockTimeRejections,
+    cadence: &mut SlotCadence,
+) -> Result<Vec<LatencyMeasurement>> {
+    let mut shutdown = Shutdown::install();
+    let source = args.grpc_source()?;
+
+    let mut request = SubscribeRequest::default();
+    request.slots.insert(
+        "slots".to_string(),
+        SubscribeRequestFilterSlots {
+            filter_by_commitment: Some(true),
+            interslot_updates: Some(false),
+        },
+    );
+    request.blocks_meta.insert(
+        "all".to_string(),
+        SubscribeRequestFilterBlocksMeta::default(),
+    );
+
+    let mut stream = source.subscribe(request);
+    let mut slots = PendingSlots::default();
+
+    let stop = args.stop_condition()?;
+    let mut interrupted = false;
+    while stop.running(slots.measurements.len() as u64) {
+        let next = tokio::select! {
+            _ = shutdown.wait() => {
+                interrupted = true;
+                break;
+            }
+            next = stream.next() => next,
+        };
+        let Some(result) = next else {
+            println!("gRPC stream ended");
+            interrupted = true;
+            break;
+        };
+
+        match result {
+            Ok(update) => {
+                let received_time = clock::now_ms();
+
+                match update.update_oneof {
+                    Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::Slot(update)) => {
+                        if update.status == SlotStatus::SlotProcessed as i32 {
+                            slots.slot(args, cadence, update.slot, received_time);
+                        }
+                    }
+                    Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::BlockMeta(
+                        block,
+                    )) => slots.block_meta(
+                        args,
+                        warmup,
+                        rejections,
+                        block.slot,
+                        block.block_time.map(|bt| bt.timestamp),
+                    ),
+                    _ => {}
+                }
+            }
+            Err(e) => {
+                debug!("gRPC stream error: {}", e);
+            }
+        }
+    }
+
+    // Slots already stamped still count once their block meta arrives, up
+    // to the --blocks target
+    let drain = time::sleep(BLOCK_META_DRAIN);
+    tokio::pin!(drain);
+    while !interrupted
+        && !slots.arrivals.is_empty()
+        && stop
+            .blocks()
+            .is_none_or(|limit| (slots.measurements.len() as u64) < limit)
+    {
+        let next = tokio::select! {
+            _ = shutdown.wait() => break,
+            _ = &mut drain => break,
+            next = stream.next() => next,
+        };
+        match next {
+            Some(Ok(update)) => {
+                if let Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::BlockMeta(
+                    block,
+                )) = update.update_oneof
+                {
+                    slots.block_meta(
+                        args,
+                        warmup,
+                        rejections,
+                        block.slot,
+                        block.block_time.map(|bt| bt.timestamp),
+                    );
+                }
+            }
+            Some(Err(e)) => debug!("gRPC stream error: {}", e),
+            None => break,
+        }
+    }
+
+    Ok(slots.measurements)
+}
+
+/// Processed-slot arrivals of the gRPC slot stream, each held until the
+/// block meta of its slot brings the block time.
+#[derive(Debug, Default)]
+struct PendingSlots {
+    /// Slot → first arrival ms.
+    arrivals: HashMap<u64, i64>,
+    measurements: Vec<LatencyMeasurement>,
+}
+
+impl PendingSlots {
+    /// A processed slot update; only slots newer than every one seen so far
+    /// are held.
+    fn slot(&mut self, args: &Args, cadence: &mut SlotCadence, slot: u64, received_time: i64) {
+        if cadence.record(slot, received_time) && slot >= args.min_slot() {
+            self.arrivals.insert(slot, received_time);
+        }
+    }
+
+    /// The block meta of `slot` measures its held arrival, if any.
+    fn block_meta(
+        &mut self,
+        args: &Args,
+        warmup: &mut Warmup,
+        rejections: &mut BlockTimeRejections,
+        slot: u64,
+        block_time: Option<i64>,
+    ) {
+        if let (Some(block_time), Some(first_seen)) = (block_time, self.arrivals.remove(&slot)) {
+            accept_measurement(
+                args,
+                warmup,
+                rejections,
+                &mut self.measurements,
+                slot,
+                block_time,
+                first_seen,
+            );
+        }
+        self.arrivals
+            .retain(|&pending, _| pending + MAX_PENDING_SLOTS >= slot);
+    }
+}
+
+/// Keep an account-update or slot measurement if its block time passes
+/// validation.
+fn accept_measurement(
+    args: &Args,
+    warmup: &mut Warmup,
+    rejections: &mut BlockTimeRejections,
+    measurements: &mut Vec<LatencyMeasurement>,
+    slot: u64,
+    block_time: i64,
+    received_time: i64,
+) {
+    if slot < args.min_slot() || !rejections.accept(block_time, slot, received_time) {
+        return;
+    }
+    let latency_ms = received_time - (block_time * 1000);
+
+    let measurement = LatencyMeasurement {
+        slot,
+        block_time,
+        received_time,
+        latency_ms,
+        slot_inferred: false,
+    };
+    record_measurement(args, warmup, measurements, measurement);
+
+    match args.blocks {
+        Some(limit) => debug!(
+            "Progress: {}/{} updates processed",
+            measurements.len(),
+            limit
+        ),
+        None => debug!("Progress: {} updates processed", measurements.len()),
+    }
+}
+
+/// Print a measurement and keep it for the statistics unless it falls in
+/// the warmup.
+fn record_measurement(
+    args: &Args,
+    warmup: &mut Warmup,
+    measurements: &mut Vec<LatencyMeasurement>,
+    measurement: LatencyMeasurement,
+) {
+    let warming_up = warmup.absorb();
+    if !args.single_shot() {
+        let status = if warming_up {
+            WARMUP_LABEL
+        } else {
+            args.thresholds().label(measurement.latency_ms)
+        };
+        print_measurement_row(&measurement, status);
+    }
+    if !warming_up {
+        measurements.push(measurement);
+    }
+}
+
+/// Block time of `slot`, retrying briefly while the RPC has not produced it yet.
+async fn wait_for_block_time(client: &Client, rpc_url: &str, slot: u64) -> Result<Option<i64>> {
+    for _ in 0..20 {
+        if let Some(block_time) = rpc::get_block_time(client, rpc_url, slot).await? {
+            return Ok(Some(block_time));
+        }
+        time::sleep(Duration::from_millis(250)).await;
+    }
+
+    Ok(None)
+}
+
+/// Send the ping, or report the pong that never came.
+async fn handle_keepalive<S>(event: KeepaliveEvent, write: &mut S) -> Result<()>
+where
+    S: futures_util::Sink<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin,
+{
+    match event {
+        KeepaliveEvent::Ping => write.send(Message::Ping(Vec::new())).await?,
+        KeepaliveEvent::PongOverdue => println!(
+            "⚠️  No pong within {}s, the WebSocket connection may be stale",
+            PONG_TIMEOUT.as_secs()
+        ),
+    }
+    Ok(())
+}
+
+fn print_account_timeout(account: &str, timeout_secs: u64, measured: usize) {
+    println!(
+        "⏰ Account {} did not change in the last {}s ({} updates measured); stopping",
+        account, timeout_secs, measured
+    );
+    if measured == 0 {
+        println!("💡 Pick an account written every few slots (e.g. an active AMM pool) or raise --account-timeout");
+    }
+}
+
+fn print_results(measurements: &[LatencyMeasurement], args: &Args) {
+    if measurements.is_empty() {
+        println!("❌ No measurements collected");
+        return;
+    }
+
+    let latencies: Vec<i64> = measurements.iter().map(|m| m.latency_ms).collect();
+    let stats = match LatencyStats::from_samples(&latencies) {
+        Some(stats) => stats,
+        None => return,
+    };
+    let avg = stats.avg_ms;
+    let outliers = OutlierStats::from_samples(&latencies, args.trim_percent);
+
+    // Count performance categories
+    let thresholds = args.thresholds();
+    let StatusThresholds {
+        excellent_ms,
+        good_ms,
+        fair_ms,
+    } = thresholds;
+    let excellent = latencies.iter().filter(|&&l| l < excellent_ms).count();
+    let good = latencies
+        .iter()
+        .filter(|&&l| l >= excellent_ms && l < good_ms)
+        .count();
+    let fair = latencies
+        .iter()
+        .filter(|&&l| l >= good_ms && l < fair_ms)
+        .count();
+    let slow = latencies.iter().filter(|&&l| l >= fair_ms).count();
+
+    println!();
+    println!("📊 Latency Results Summary");
+    println!("{}", "=".repeat(50));
+    println!("Method:             {:?}", args.method);
+    println!("Endpoint:           {}", args.endpoint);
+    println!("Blocks processed:   {}", measurements.len());
+    println!("Average latency:    {:.1}ms", avg);
+    if let Some(outliers) = &outliers {
+        println!(
+            "Trimmed average:    {:.1}ms ({}% each end)",
+            outliers.trimmed_avg_ms, outliers.trim_percent
+        );
+    }
+    println!("Min latency:        {}ms", stats.min_ms);
+    println!("Max latency:        {}ms", stats.max_ms);
+    println!("Std deviation:      {:.1}ms", stats.std_dev_ms);
+    println!("Jitter:             {:.1}ms", stats.jitter_ms);
+    for (label, value) in args.percentiles.of(&latencies) {
+        println!("{:<20}{}ms", format!("{}:", label), value);
+    }
+    if let Some(outliers) = &outliers {
+        println!(
+            "Outliers (1.5 IQR): {} outside [{:.0}, {:.0}]ms",
+            outliers.outliers, outliers.lower_fence_ms, outliers.upper_fence_ms
+        );
+    }
+    println!();
+
+    println!("⚡ Performance Distribution:");
+    println!(
+        "🟢 Excellent (<{}ms):   {}/{} ({:.1}%)",
+        excellent_ms,
+        excellent,
+        measurements.len(),
+        (excellent as f64 / measurements.len() as f64) * 100.0
+    );
+    println!(
+        "🟡 Good ({}-{}ms):    {}/{} ({:.1}%)",
+        excellent_ms,
+        good_ms,
+        good,
+        measurements.len(),
+        (good as f64 / measurements.len() as f64) * 100.0
+    );
+    println!(
+        "🟠 Fair ({}-{}ms):   {}/{} ({:.1}%)",
+        good_ms,
+        fair_ms,
+        fair,
+        measurements.len(),
+        (fair as f64 / measurements.len() as f64) * 100.0
+    );
+    println!(
+        "🔴 Slow (>{}ms):       {}/{} ({:.1}%)",
+        fair_ms,
+        slow,
+        measurements.len(),
+        (slow as f64 / measurements.len() as f64) * 100.0
+    );
+    println!();
+
+    if args.histogram {
+        stats::print_ascii_histogram(&latencies, args.bucket_ms);
+        println!();
+    }
+
+    println!("🎯 Overall Assessment:");
+    match thresholds.status_f64(avg) {
+        LatencyStatus::Excellent => println!("✅ EXCELLENT - Very fast latency!"),
+        LatencyStatus::Good => println!("🟡 GOOD - Acceptable latency for most use cases"),
+        LatencyStatus::Fair => println!("🟠 FAIR - Moderate latency, consider optimization"),
+        LatencyStatus::Slow => {
+            println!("🔴 SLOW - High latency, investigate network/provider issues")
+        }
+    }
+
+    if args.fairness_report {
+        methodology(args, thresholds).print();
+    }
+}
+
+fn methodology(args: &Args, thresholds: StatusThresholds) -> Methodology {
+    let (source, poll_interval_ms) = match args.method {
+        Method::Rpc => (
+            "HTTP polling (getSlot + getBlockTime)",
+            Some(RPC_POLL_INTERVAL_MS),
+        ),
+        Method::Grpc => ("gRPC block meta stream", None),
+        Method::Websocket => ("WebSocket blockSubscribe", None),
+        Method::Account => (
+            "Account updates (accountSubscribe or gRPC account filter) + block time of their slot",
+            None,
+        ),
+        Method::Slot => (
+            "Slot notifications (slotSubscribe or gRPC slot filter) + block time of the slot",
+            None,
+        ),
+    };
+
+    Methodology {
+        tool: "latency_calculator",
+        source,
+        latency_formula: "received_time_ms - block_time * 1000",
+        time_source: args.time_source.to_string(),
+        commitment: "processed",
+        poll_interval_ms,
+        poll_interval_max_ms: None,
+        latency_filter: Some(format!(
+            "block_time > 0, at most {}ms ahead of and {}ms behind received_time",
+            block_time::FUTURE_TOLERANCE_MS,
+            block_time::MAX_AGE_MS
+        )),
+        warmup_samples: args.warmup,
+        percentile_definition: stats::PERCENTILE_DEFINITION,
+        status_thresholds: thresholds,
+    }
+}
+
+fn print_measurement_row(measurement: &LatencyMeasurement, status: &str) {
+    let inferred = if measurement.slot_inferred {
+        " (slot inferred)"
+    } else {
+        ""
+    };
+    println!(
+        "{:<10} | {:<12} | {:<12} | {:<9}ms | {}{}",
+        measurement.slot,
+        measurement.block_time,
+        measurement.received_time / 1000,
+        measurement.latency_ms,
+        status,
+        inferred
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Unix seconds of the block in the tests.
+    const BLOCK_TIME: i64 = 1_792_022_400;
+
+    #[test]
+    fn grpc_single_shot_runs_until_the_block_meta_of_its_slot() {
+        let args = Args::parse_from([
+            "latency-calculator",
+            "--method",
+            "slot",
+            "--slot-stream",
+            "grpc",
+            "--endpoint",
+            "http://127.0.0.1:10000",
+            "--target-slot",
+        ]);
+        let stop = args.stop_condition().unwrap();
+        let mut warmup = args.warmup();
+        let mut rejections = BlockTimeRejections::default();
+        let mut cadence = SlotCadence::default();
+        let mut slots = PendingSlots::default();
+
+        slots.slot(&args, &mut cadence, 300_000_000, BLOCK_TIME * 1000 + 450);
+        assert!(
+            stop.running(slots.measurements.len() as u64),
+            "the slot update alone must not end the probe"
+        );
+
+        slots.block_meta(
+            &args,
+            &mut warmup,
+            &mut rejections,
+            300_000_000,
+            Some(BLOCK_TIME),
+        );
+        assert!(!stop.running(slots.measurements.len() as u64));
+        assert_eq!(slots.measurements.len(), 1);
+        assert_eq!(slots.measurements[0].slot, 300_000_000);
+        assert_eq!(slots.measurements[0].latency_ms, 450);
+        assert!(slots.arrivals.is_empty());
+    }
+}
@@ -0,0 +1,1089 @@
+//! Per-slot race between Laserstream, a WebSocket `blockSubscribe` and an RPC
+//! poller (`racing_comparison`, `bench race`).
+//!
+//! A slot is ranked once every racing source delivered it, or once two did
+//! and the rest missed the straggler timeout. A slot only one source
+//! delivered is announced after the no-show timeout.
+
+use crate::block;
+use crate::capabilities::{self, Capability};
+use crate::clock::{self, TimeSource};
+use crate::keepalive::{Keepalive, KeepaliveEvent, PONG_TIMEOUT};
+use crate::logging::{self, LogFormat};
+use crate::poll::{self, AdaptiveInterval, SlotTracker};
+use crate::rpc::{self, Commitment, RPCConfig, TimedOut};
+use crate::shutdown::Shutdown;
+use crate::status::StatusThresholds;
+use crate::stop::StopCondition;
+use crate::subscription;
+use crate::tail::TailView;
+use crate::types::BlockEvent;
+use anyhow::{anyhow, Result};
+use clap::builder::ArgPredicate;
+use clap::Parser;
+use futures::{SinkExt, StreamExt};
+use helius_laserstream::{
+    grpc::{SubscribeRequest, SubscribeRequestFilterBlocks},
+    LaserstreamConfig,
+};
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time;
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+use tracing::debug;
+
+#[derive(Parser)]
+#[command(name = "racing-comparison")]
+#[command(about = "Real-time racing comparison between LaserStream, WebSocket and RPC")]
+pub struct Args {
+    #[arg(long, help = "Helius API key")]
+    api_key: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "https://laserstream-mainnet-tyo.helius-rpc.com",
+        help = "Helius Laserstream endpoint"
+    )]
+    endpoint: String,
+
+    #[arg(
+        long,
+        default_value = "../shared/config.json",
+        help = "Config file path"
+    )]
+    config: String,
+
+    #[arg(
+        long,
+        default_value = "3",
+        default_value_if("blocks", ArgPredicate::IsPresent, None),
+        value_name = "MINUTES",
+        help = "Test duration in minutes (no default with --blocks; with both, whichever comes first)"
+    )]
+    duration: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Stop after N slots delivered by at least two sources (with --duration, whichever comes first)"
+    )]
+    blocks: Option<u64>,
+
+    #[arg(
+        long,
+        help = "Debug diagnostics on stderr (same as RUST_LOG=solana_rpc_performance_rust=debug)"
+    )]
+    verbose: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "text",
+        help = "Format of the diagnostics on stderr; RUST_LOG sets their levels"
+    )]
+    log_format: LogFormat,
+
+    #[arg(
+        long,
+        default_value = "clock_realtime",
+        value_name = "clock_realtime|ntp[:server]|file:<path>",
+        help = "Wall clock for latency math; ntp[:server] applies an offset measured once at startup, file:<path> the PTP/GPS offset (ms) read from that file"
+    )]
+    time_source: TimeSource,
+
+    #[arg(
+        long,
+        default_value_t = 10,
+        value_name = "SECS",
+        help = "Seconds to wait for a second source before a slot is scored as a no-show"
+    )]
+    no_show_timeout: u64,
+
+    #[arg(
+        long,
+        default_value_t = 2000,
+        value_name = "MS",
+        help = "Once two sources delivered a slot, wait this long for the rest before ranking it"
+    )]
+    straggler_timeout_ms: u64,
+
+    #[arg(
+        long,
+        value_name = "URL",
+        help = "WebSocket endpoint for blockSubscribe (default: the RPC URL with a ws(s) scheme)"
+    )]
+    websocket_url: Option<String>,
+
+    #[arg(long, help = "Race only LaserStream and RPC")]
+    no_websocket: bool,
+
+    #[arg(
+        long,
+        help = "Skip the startup check of which RPC methods and commitments the provider supports"
+    )]
+    no_capability_probe: bool,
+
+    #[arg(
+        long,
+        default_value_t = poll::DEFAULT_MIN_POLL_MS,
+        value_name = "MS",
+        help = "Shortest sleep between RPC getSlot polls; the interval adapts to half the observed slot time"
+    )]
+    min_poll_ms: u64,
+
+    #[arg(
+        long,
+        default_value_t = poll::DEFAULT_MAX_POLL_MS,
+        value_name = "MS",
+        help = "Longest sleep between RPC getSlot polls"
+    )]
+    max_poll_ms: u64,
+
+    #[arg(
+        long,
+        value_name = "EXCELLENT,GOOD,FAIR",
+        help = "Status band upper bounds in ms (default 900,1200,2000)"
+    )]
+    status_thresholds: Option<StatusThresholds>,
+
+    #[arg(
+        long,
+        help = "Redraw the latest slots and a rolling win rate / advantage / slots-per-second panel in place instead of scrolling the table"
+    )]
+    dashboard: bool,
+
+    #[arg(
+        long,
+        default_value = "350",
+        value_name = "MS",
+        help = "Give up on a getBlockTime after MS and skip the slot, so a stalled call cannot hold up the 400ms poll"
+    )]
+    call_timeout_ms: u64,
+}
+
+/// Ranked slots that the dashboard's rolling figures cover.
+const DASHBOARD_WINDOW: usize = 100;
+/// Table rows kept on screen above the dashboard panel.
+const DASHBOARD_ROWS: usize = 10;
+/// Seconds between WebSocket pings, so a quiet connection is not dropped.
+const WS_PING_INTERVAL_SECS: u64 = 30;
+
+/// A transport in the race.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Source {
+    Laserstream,
+    Websocket,
+    Rpc,
+}
+
+impl Source {
+    /// Table column order.
+    const ALL: [Source; 3] = [Source::Laserstream, Source::Websocket, Source::Rpc];
+
+    fn name(self) -> &'static str {
+        match self {
+            Source::Laserstream => "LaserStream",
+            Source::Websocket => "WebSocket",
+            Source::Rpc => "RPC",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RaceTimeouts {
+    /// A slot only one source delivered is announced after this long.
+    no_show_ms: i64,
+    /// A slot two or more sources delivered is ranked after this long.
+    straggler_ms: i64,
+}
+
+/// A ranked slot in the dashboard window. Times are monotonic ms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RankedSlot {
+    /// `None` is a tie.
+    winner: Option<Source>,
+    /// Arrival of the last source that made it.
+    ranked_at: i64,
+    laserstream_ms: Option<i64>,
+    rpc_ms: Option<i64>,
+}
+
+impl RankedSlot {
+    /// How much earlier LaserStream delivered the slot than RPC (negative
+    /// when RPC was first), when both delivered it.
+    fn laserstream_advantage_ms(&self) -> Option<i64> {
+        Some(self.rpc_ms? - self.laserstream_ms?)
+    }
+}
+
+/// Race bookkeeping shared by all monitors.
+#[derive(Debug)]
+struct RaceState {
+    /// Slots not ranked yet, with each source's arrival so far.
+    pending: HashMap<u64, HashMap<Source, BlockEvent>>,
+    /// Sources still racing; a monitor that failed is retired.
+    sources: Vec<Source>,
+    /// Slots ranked between at least two sources.
+    decided: u64,
+    wins: HashMap<Source, u64>,
+    ties: u64,
+    no_shows: HashMap<Source, u64>,
+    thresholds: StatusThresholds,
+    /// The last [`DASHBOARD_WINDOW`] ranked slots.
+    recent: VecDeque<RankedSlot>,
+    /// `--dashboard`: rows are redrawn in place instead of printed.
+    dashboard: Option<TailView>,
+}
+
+impl RaceState {
+    fn new(sources: Vec<Source>, thresholds: StatusThresholds, dashboard: bool) -> Self {
+        RaceState {
+            pending: HashMap::new(),
+            sources,
+            decided: 0,
+            wins: HashMap::new(),
+            ties: 0,
+            no_shows: HashMap::new(),
+            thresholds,
+            recent: VecDeque::with_capacity(DASHBOARD_WINDOW),
+            dashboard: dashboard.then(|| TailView::new(DASHBOARD_ROWS)),
+        }
+    }
+
+    fn retire(&mut self, source: Source) {
+        self.sources.retain(|&s| s != source);
+    }
+
+    fn count(counts: &HashMap<Source, u64>, source: Source) -> u64 {
+        counts.get(&source).copied().unwrap_or(0)
+    }
+
+    /// Rank `slot` with whatever arrived, count the missing sources as
+    /// no-shows and announce it.
+    fn decide(&mut self, slot: u64) {
+        let Some(arrivals) = self.pending.remove(&slot) else {
+            return;
+        };
+        for source in &self.sources {
+            if !arrivals.contains_key(source) {
+                *self.no_shows.entry(*source).or_default() += 1;
+            }
+        }
+
+        let ranking = rank(&arrivals);
+        if let [(first, first_event), (_, second_event), ..] = ranking[..] {
+            let margin = second_event.arrival_ms - first_event.arrival_ms;
+            let winner = (margin > 0).then_some(first);
+            match winner {
+                Some(source) => *self.wins.entry(source).or_default() += 1,
+                None => self.ties += 1,
+            }
+            self.decided += 1;
+            let arrival = |source| arrivals.get(&source).map(|e: &BlockEvent| e.arrival_ms);
+            self.record_ranked(RankedSlot {
+                winner,
+                ranked_at: ranking
+                    .iter()
+                    .map(|(_, e)| e.arrival_ms)
+                    .max()
+                    .unwrap_or_default(),
+                laserstream_ms: arrival(Source::Laserstream),
+                rpc_ms: arrival(Source::Rpc),
+            });
+        }
+
+        if let Some(row) = announce_winner(slot, &ranking, &self.sources, self.thresholds) {
+            self.show(row);
+        }
+    }
+
+    fn record_ranked(&mut self, ranked: RankedSlot) {
+        if self.recent.len() == DASHBOARD_WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(ranked);
+    }
+
+    /// Print a table row, or redraw the dashboard with it.
+    fn show(&mut self, row: String) {
+        if self.dashboard.is_none() {
+            println!("{}", row);
+            return;
+        }
+        let panel = self.dashboard_panel();
+        if let Some(view) = self.dashboard.as_mut() {
+            view.push(row);
+            view.render(&panel);
+        }
+    }
+
+    fn dashboard_panel(&self) -> String {
+        let ranked = self.recent.len();
+        if ranked == 0 {
+            return "⏳ Waiting for a slot two sources deliver...".to_string();
+        }
+
+        let share = |count: usize| count as f64 / ranked as f64 * 100.0;
+        let wins = |source: Source| {
+            self.recent
+                .iter()
+                .filter(|slot| slot.winner == Some(source))
+                .count()
+        };
+        let ties = self
+            .recent
+            .iter()
+            .filter(|slot| slot.winner.is_none())
+            .count();
+        let advantages: Vec<i64> = self
+            .recent
+            .iter()
+            .filter_map(RankedSlot::laserstream_advantage_ms)
+            .collect();
+        let advantage = if advantages.is_empty() {
+            "n/a (no slot both delivered)".to_string()
+        } else {
+            format!(
+                "{:+.0}ms over {} slots",
+                advantages.iter().sum::<i64>() as f64 / advantages.len() as f64,
+                advantages.len()
+            )
+        };
+        let span_ms = match (self.recent.front(), self.recent.back()) {
+            (Some(first), Some(last)) => last.ranked_at - first.ranked_at,
+            _ => 0,
+        };
+        let slots_per_sec = if span_ms > 0 {
+            format!("{:.2}", (ranked - 1) as f64 * 1000.0 / span_ms as f64)
+        } else {
+            "n/a".to_string()
+        };
+        let per_source = |figure: &dyn Fn(Source) -> String| {
+            Source::ALL
+                .iter()
+                .map(|&source| format!("{} {}", source.name(), figure(source)))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        };
+
+        format!(
+            "📊 Last {} slots: {} | Tie {:.0}%\n\
+             ⚡ Avg LaserStream advantage over RPC: {}\n\
+             🚀 Slots/sec: {} | Raced: {} | No-shows: {}",
+            ranked,
+            per_source(&|source| format!("{:.0}%", share(wins(source)))),
+            share(ties),
+            advantage,
+            slots_per_sec,
+            self.decided,
+            per_source(&|source| Self::count(&self.no_shows, source).to_string())
+        )
+    }
+}
+
+type SharedBlocks = Arc<Mutex<RaceState>>;
+
+pub async fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    logging::init(args.log_format, args.verbose, false);
+
+    let api_key = args
+        .api_key
+        .clone()
+        .or_else(|| std::env::var("HELIUS_API_KEY").ok())
+        .unwrap_or_else(|| "9de07723-0030-4ee0-b175-6722231d5d97".to_string());
+
+    println!("🏁 Real-Time Block Detection Race");
+    if args.no_websocket {
+        println!("LaserStream vs RPC - Who gets the block first?");
+    } else {
+        println!("LaserStream vs WebSocket vs RPC - Who gets the block first?");
+    }
+    let stop = StopCondition::new(args.duration, args.blocks)?;
+    println!("Stop after: {}", stop);
+    println!("LaserStream endpoint: {}", args.endpoint);
+    println!("Time source: {}", args.time_source);
+    println!();
+
+    clock::init(&args.time_source)?;
+
+    let config = rpc::load_config(&args.config)?;
+    let thresholds = StatusThresholds::resolve(
+        args.status_thresholds,
+        config.status_thresholds,
+        StatusThresholds::BLOCK_STREAM,
+    )?;
+
+    // Get premium RPC (Helius) or fallback to first active RPC
+    let rpc = config
+        .rpcs
+        .values()
+        .find(|r| r.provider == "Helius" && r.status == "active")
+        .or_else(|| config.rpcs.values().find(|r| r.status == "active"))
+        .ok_or_else(|| anyhow::anyhow!("No active RPCs found"))?;
+
+    println!("RPC Provider: {} ({})", rpc.name, rpc.provider);
+    println!("RPC URL: {}", rpc.url);
+    let mut websocket_url = (!args.no_websocket).then(|| {
+        args.websocket_url
+            .clone()
+            .unwrap_or_else(|| rpc::websocket_url(&rpc.url, None))
+    });
+    if let Some(url) = &websocket_url {
+        println!("WebSocket URL: {} (blockSubscribe, confirmed)", url);
+    }
+    println!();
+
+    if !args.no_capability_probe {
+        let client = Client::builder().timeout(Duration::from_secs(10)).build()?;
+        let capabilities = match &websocket_url {
+            Some(url) => capabilities::probe(&client, &rpc.url, url).await,
+            None => capabilities::probe_provider(&client, &rpc.url).await,
+        };
+        capabilities.print();
+        capabilities.require(&[
+            Capability::GetSlot(Commitment::Processed),
+            Capability::GetBlockTime,
+        ])?;
+        // The WebSocket leg is optional, so race without it rather than refuse
+        let block_subscribe = Capability::BlockSubscribe(Commitment::Confirmed);
+        if websocket_url.is_some() && !capabilities.support(block_subscribe).usable() {
+            println!(
+                "⚠️  No {}, racing only LaserStream and RPC",
+                block_subscribe
+            );
+            println!();
+            websocket_url = None;
+        }
+    }
+
+    let sources = Source::ALL
+        .into_iter()
+        .filter(|&source| source != Source::Websocket || websocket_url.is_some())
+        .collect();
+    let shared_blocks: SharedBlocks = Arc::new(Mutex::new(RaceState::new(
+        sources,
+        thresholds,
+        args.dashboard,
+    )));
+    let timeouts = RaceTimeouts {
+        no_show_ms: args.no_show_timeout as i64 * 1000,
+        straggler_ms: args.straggler_timeout_ms as i64,
+    };
+    let shutdown = Shutdown::install();
+
+    let mut monitors = vec![
+        tokio::spawn(race_source(
+            Source::Laserstream,
+            shared_blocks.clone(),
+            monitor_laserstream(
+                api_key.clone(),
+                args.endpoint.clone(),
+                stop,
+                shared_blocks.clone(),
+                timeouts,
+                shutdown.clone(),
+            ),
+        )),
+        tokio::spawn(race_source(
+            Source::Rpc,
+            shared_blocks.clone(),
+            monitor_rpc(
+                rpc.clone(),
+                stop,
+                Duration::from_millis(args.call_timeout_ms),
+                shared_blocks.clone(),
+                timeouts,
+                AdaptiveInterval::new(args.min_poll_ms, args.max_poll_ms),
+                shutdown.clone(),
+            ),
+        )),
+    ];
+    if let Some(url) = websocket_url {
+        monitors.push(tokio::spawn(race_source(
+            Source::Websocket,
+            shared_blocks.clone(),
+            monitor_websocket(url, stop, shared_blocks.clone(), timeouts, shutdown.clone()),
+        )));
+    }
+
+    println!("🚀 Starting the race...");
+    println!("🏆 First to detect each new block wins!");
+    println!();
+    println!(
+        "Slot       | Winner           | LaserStream     | WebSocket       | RPC             | Margin    | Status"
+    );
+    println!("{}", "-".repeat(105));
+
+    // Wait for every monitor to complete
+    futures::future::join_all(monitors).await;
+
+    let race = shared_blocks.lock().await;
+    print_race_summary(&race, args.no_show_timeout);
+
+    Ok(())
+}
+
+/// Run one source's monitor. If it fails the race goes on without it, so
+/// its missing slots are not counted as no-shows.
+async fn race_source(
+    source: Source,
+    shared_blocks: SharedBlocks,
+    monitor: impl Future<Output = Result<()>>,
+) {
+    if let Err(e) = monitor.await {
+        println!("❌ {} dropped out of the race: {}", source.name(), e);
+        shared_blocks.lock().await.retire(source);
+    }
+}
+
+async fn monitor_laserstream(
+    api_key: String,
+    endpoint: String,
+    stop: StopCondition,
+    shared_blocks: SharedBlocks,
+    timeouts: RaceTimeouts,
+    mut shutdown: Shutdown,
+) -> Result<()> {
+    let config = LaserstreamConfig {
+        api_key,
+        endpoint: endpoint.parse()?,
+        ..Default::default()
+    };
+
+    let mut block_filters = HashMap::new();
+    block_filters.insert(
+        "all_blocks".to_string(),
+        SubscribeRequestFilterBlocks {
+            account_include: vec![],
+            include_transactions: Some(false),
+            include_accounts: Some(false),
+            include_entries: Some(false),
+        },
+    );
+
+    let request = SubscribeRequest {
+        blocks: block_filters,
+        ..Default::default()
+    };
+
+    let (stream, subscription) = subscription::subscribe(config, request);
+    futures::pin_mut!(stream);
+
+    while stop.running(shared_blocks.lock().await.decided) {
+        let next = tokio::select! {
+            _ = shutdown.wait() => break,
+            next = stream.next() => next,
+        };
+        if let Some(result) = next {
+            match result {
+                Ok(update) => {
+                    let received_time = clock::now_ms();
+                    let arrival_ms = clock::monotonic_ms();
+
+                    if let Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::Block(
+                        block,
+                    )) = update.update_oneof
+                    {
+                        let slot = block.slot;
+                        let block_time = block.block_time.map(|bt| bt.timestamp);
+                        let latency = block_time.map(|bt| received_time - (bt * 1000));
+
+                        let block_event = BlockEvent {
+                            received_time,
+                            arrival_ms,
+                            latency_ms: latency,
+                        };
+
+                        // Update shared state and check if we can announce a winner
+                        let mut race = shared_blocks.lock().await;
+                        record_event(&mut race, slot, block_event, Source::Laserstream, timeouts);
+                    }
+                }
+                Err(e) => {
+                    debug!("LaserStream error: {}", e);
+                }
+            }
+        }
+    }
+
+    subscription.cancel();
+    subscription.health().print();
+
+    Ok(())
+}
+
+async fn monitor_rpc(
+    rpc: RPCConfig,
+    stop: StopCondition,
+    call_timeout: Duration,
+    shared_blocks: SharedBlocks,
+    timeouts: RaceTimeouts,
+    mut interval: AdaptiveInterval,
+    mut shutdown: Shutdown,
+) -> Result<()> {
+    let client = Client::builder().timeout(Duration::from_secs(10)).build()?;
+
+    let mut tracker =
+        SlotTracker::new(rpc::get_latest_slot(&client, &rpc.url, Commitment::Processed).await?);
+    let mut timed_out = 0u64;
+
+    while stop.running(shared_blocks.lock().await.decided) {
+        let previous_slot = tracker.last_slot();
+        match tracker
+            .poll(|| rpc::get_latest_slot(&client, &rpc.url, Commitment::Processed))
+            .await
+        {
+            Ok(Some(current_slot)) => {
+                interval.advanced(previous_slot, current_slot);
+                if current_slot > previous_slot + 1 {
+                    debug!(
+                        "RPC    | {} | Slot jumped from {}, backfilling {} skipped-over slots",
+                        current_slot,
+                        previous_slot,
+                        current_slot - previous_slot - 1
+                    );
+                }
+
+                // Every slot since the last poll, so skipped-over slots are
+                // still raced instead of silently dropping out of the join
+                for slot in (previous_slot + 1)..=current_slot {
+                    let block_time = rpc::with_timeout(
+                        call_timeout,
+                        rpc::get_block_time(&client, &rpc.url, slot),
+                    )
+                    .await;
+                    match block_time {
+                        Ok(Some(block_time)) => {
+                            let received_time = clock::now_ms();
+                            let arrival_ms = clock::monotonic_ms();
+
+                            let latency = received_time - (block_time * 1000);
+
+                            let block_event = BlockEvent {
+                                received_time,
+                                arrival_ms,
+                                latency_ms: Some(latency),
+                            };
+
+                            // Update shared state and check if we can announce a winner
+                            let mut race = shared_blocks.lock().await;
+                            record_event(&mut race, slot, block_event, Source::Rpc, timeouts);
+                        }
+                        Ok(None) => {
+                            debug!("RPC    | {} | Block time not available yet", slot);
+                        }
+                        Err(e) if e.downcast_ref::<TimedOut>().is_some() => {
+                            timed_out += 1;
+                            debug!("RPC    | {} | Skipped: {}", slot, e);
+                        }
+                        Err(e) => {
+                            debug!("RPC    | {} | Error: {}", slot, e);
+                        }
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                debug!("RPC slot error: {}", e);
+            }
+        }
+
+        tokio::select! {
+            _ = shutdown.wait() => break,
+            _ = time::sleep(interval.next()) => {}
+        }
+    }
+
+    tracker.print_summary();
+    interval.print_summary();
+    if timed_out > 0 {
+        println!(
+            "⏱️  {} getBlockTime calls took over {}ms and their slots were skipped",
+            timed_out,
+            call_timeout.as_millis()
+        );
+    }
+
+    Ok(())
+}
+
+/// `blockSubscribe` notifications, timestamped on arrival. The subscription
+/// runs at confirmed commitment, the lowest `blockSubscribe` accepts.
+async fn monitor_websocket(
+    url: String,
+    stop: StopCondition,
+    shared_blocks: SharedBlocks,
+    timeouts: RaceTimeouts,
+    mut shutdown: Shutdown,
+) -> Result<()> {
+    let (ws_stream, _) = connect_async(&url).await?;
+    let (mut write, mut read) = ws_stream.split();
+
+    let subscription = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "blockSubscribe",
+        "params": [
+            "all",
+            {
+                "commitment": "confirmed",
+                "encoding": "json",
+                "transactionDetails": "none",
+                "rewards": false
+            }
+        ]
+    });
+    write.send(Message::Text(subscription.to_string())).await?;
+    let mut keepalive = Keepalive::new(WS_PING_INTERVAL_SECS);
+
+    while stop.running(shared_blocks.lock().await.decided) {
+        let message = tokio::select! {
+            _ = shutdown.wait() => break,
+            event = keepalive.next() => {
+                match event {
+                    KeepaliveEvent::Ping => write.send(Message::Ping(Vec::new())).await?,
+                    KeepaliveEvent::PongOverdue => {
+                        debug!("WS     | No pong within {}s", PONG_TIMEOUT.as_secs())
+                    }
+                }
+                continue;
+            }
+            message = read.next() => message,
+        };
+
+        let text = match message {
+            Some(Ok(Message::Text(text))) => text,
+            Some(Ok(Message::Pong(_))) => {
+                keepalive.pong();
+                continue;
+            }
+            Some(Ok(Message::Close(_))) | None => {
+                return Err(anyhow!("WebSocket connection closed"));
+            }
+            // tungstenite queues the pong reply itself
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => return Err(e.into()),
+        };
+        let received_time = clock::now_ms();
+        let arrival_ms = clock::monotonic_ms();
+
+        let Ok(notification) = serde_json::from_str::<Value>(&text) else {
+            continue;
+        };
+        if let Some(error) = notification.get("error") {
+            return Err(anyhow!("blockSubscribe error: {}", error));
+        }
+        let Some(result) = notification.pointer("/params/result") else {
+            continue;
+        };
+        // An inferred slot is wrong after a skip and would race the wrong block
+        let Some((slot, false)) = block::notification_slot(result) else {
+            debug!("WS     | Notification without a slot skipped");
+            continue;
+        };
+
+        let block_time = result
+            .pointer("/value/block/blockTime")
+            .and_then(Value::as_i64);
+        let block_event = BlockEvent {
+            received_time,
+            arrival_ms,
+            latency_ms: block_time.map(|bt| received_time - (bt * 1000)),
+        };
+
+        let mut race = shared_blocks.lock().await;
+        record_event(&mut race, slot, block_event, Source::Websocket, timeouts);
+    }
+
+    Ok(())
+}
+
+/// Record one source's event for `slot` and rank the slot once every
+/// racing source is in. Pending slots past their timeout are ranked with
+/// what they have; a slot only one source delivered waits for the no-show
+/// timeout, one that two or more delivered for the straggler timeout.
+fn record_event(
+    race: &mut RaceState,
+    slot: u64,
+    event: BlockEvent,
+    source: Source,
+    timeouts: RaceTimeouts,
+) {
+    let now = event.arrival_ms;
+    let arrivals = race.pending.entry(slot).or_default();
+    // A source delivering a slot twice keeps its first arrival
+    arrivals.entry(source).or_insert(event);
+    if race.sources.iter().all(|s| arrivals.contains_key(s)) {
+        race.decide(slot);
+    }
+
+    let mut expired: Vec<u64> = race
+        .pending
+        .iter()
+        .filter(|(_, arrivals)| {
+            let limit = if arrivals.len() >= 2 {
+                timeouts.straggler_ms
+            } else {
+                timeouts.no_show_ms
+            };
+            arrivals
+                .values()
+                .map(|e| e.arrival_ms)
+                .min()
+                .is_some_and(|first| now - first > limit)
+        })
+        .map(|(&slot, _)| slot)
+        .collect();
+    expired.sort();
+    for slot in expired {
+        race.decide(slot);
+    }
+}
+
+/// The arrivals of a slot, first to last on the monotonic clock. A wall
+/// clock step between two receipts must not reorder them.
+fn rank(arrivals: &HashMap<Source, BlockEvent>) -> Vec<(Source, &BlockEvent)> {
+    let mut ranking: Vec<(Source, &BlockEvent)> = Source::ALL
+        .into_iter()
+        .filter_map(|source| arrivals.get(&source).map(|event| (source, event)))
+        .collect();
+    ranking.sort_by_key(|(_, event)| event.arrival_ms);
+    ranking
+}
+
+/// The table row announcing a ranked slot. Racing sources missing from
+/// `ranking` are no-shows; retired ones are shown as `-`.
+fn announce_winner(
+    slot: u64,
+    ranking: &[(Source, &BlockEvent)],
+    sources: &[Source],
+    thresholds: StatusThresholds,
+) -> Option<String> {
+    let (winner, margin) = match ranking {
+        [] => return None,
+        [(only, _)] => (format!("🏆 {}", only.name()), "only one".to_string()),
+        [(first, first_event), (_, second_event), ..] => {
+            let diff = second_event.arrival_ms - first_event.arrival_ms;
+            if diff > 0 {
+                (format!("🏆 {}", first.name()), format!("{}ms", diff))
+            } else {
+                ("🤝 Tie".to_string(), "Same time".to_string())
+            }
+        }
+    };
+    let column = |source: Source| match ranking.iter().position(|(s, _)| *s == source) {
+        Some(place) => format!(
+            "{}ms #{}",
+            ranking[place].1.latency_ms.unwrap_or(0),
+            place + 1
+        ),
+        None if sources.contains(&source) => "no-show".to_string(),
+        None => "-".to_string(),
+    };
+
+    // The slot is rated by whichever source delivered it fastest; a no-show
+    // never counts towards the status
+    let fastest = ranking
+        .iter()
+        .map(|(_, event)| event.latency_ms.unwrap_or(0))
+        .min()
+        .unwrap_or(i64::MAX);
+    let overall_status = thresholds.label(fastest);
+
+    Some(format!(
+        "{:<10} | {:<16} | {:<15} | {:<15} | {:<15} | {:<9} | {}",
+        slot,
+        winner,
+        column(Source::Laserstream),
+        column(Source::Websocket),
+        column(Source::Rpc),
+        margin,
+        overall_status
+    ))
+}
+
+fn print_race_summary(race: &RaceState, no_show_timeout_secs: u64) {
+    println!();
+    println!("🏁 Race summary");
+    println!("Slots raced by at least two sources: {}", race.decided);
+    if race.decided > 0 {
+        let share = |count: u64| count as f64 / race.decided as f64 * 100.0;
+        let wins: Vec<String> = Source::ALL
+            .iter()
+            .map(|&source| {
+                let count = RaceState::count(&race.wins, source);
+                format!("{} {} ({:.0}%)", source.name(), count, share(count))
+            })
+            .collect();
+        println!(
+            "🏆 Wins: {} | Ties {} ({:.0}%)",
+            wins.join(" | "),
+            race.ties,
+            share(race.ties)
+        );
+    }
+    let no_shows: Vec<String> = Source::ALL
+        .iter()
+        .map(|&source| {
+            format!(
+                "{} {}",
+                source.name(),
+                RaceState::count(&race.no_shows, source)
+            )
+        })
+        .collect();
+    println!(
+        "No-shows (missing when the slot was ranked, or after {}s alone): {}",
+        no_show_timeout_secs,
+        no_shows.join(" | ")
+    );
+    if !race.pending.is_empty() {
+        println!(
+            "{} slots were still waiting on other sources when the run ended",
+            race.pending.len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLOCK_TIME_MS: i64 = 1_700_000_000_000;
+
+    fn event(received_time: i64, arrival_ms: i64) -> BlockEvent {
+        BlockEvent {
+            received_time,
+            arrival_ms,
+            latency_ms: Some(received_time - BLOCK_TIME_MS),
+        }
+    }
+
+    #[test]
+    fn wall_clock_step_does_not_flip_the_winner() {
+        let mut race = RaceState::new(
+            vec![Source::Laserstream, Source::Websocket],
+            StatusThresholds::BLOCK_PROPAGATION,
+            false,
+        );
+        let timeouts = RaceTimeouts {
+            no_show_ms: 10_000,
+            straggler_ms: 2_000,
+        };
+
+        // Laserstream is 40ms ahead on the monotonic clock, but the wall
+        // clock stepped back 500ms between the two receipts
+        record_event(
+            &mut race,
+            42,
+            event(BLOCK_TIME_MS + 900, 1_000),
+            Source::Laserstream,
+            timeouts,
+        );
+        record_event(
+            &mut race,
+            42,
+            event(BLOCK_TIME_MS + 440, 1_040),
+            Source::Websocket,
+            timeouts,
+        );
+
+        assert!(race.pending.is_empty());
+        assert_eq!(race.decided, 1);
+        assert_eq!(RaceState::count(&race.wins, Source::Laserstream), 1);
+        assert_eq!(RaceState::count(&race.wins, Source::Websocket), 0);
+        assert_eq!(race.ties, 0);
+        assert_eq!(
+            race.recent.back().map(|slot| slot.winner),
+            Some(Some(Source::Laserstream))
+        );
+    }
+
+    #[test]
+    fn rank_orders_by_arrival_not_received_time() {
+        let arrivals = HashMap::from([
+            (Source::Rpc, event(BLOCK_TIME_MS + 100, 2_300)),
+            (Source::Websocket, event(BLOCK_TIME_MS + 2_000, 2_050)),
+            (Source::Laserstream, event(BLOCK_TIME_MS + 1_900, 2_000)),
+        ]);
+
+        let order: Vec<Source> = rank(&arrivals).into_iter().map(|(s, _)| s).collect();
+        assert_eq!(
+            order,
+            vec![Source::Laserstream, Source::Websocket, Source::Rpc]
+        );
+    }
+    #[test]
+    fn dashboard_averages_laserstream_advantage_over_rpc() {
+        let mut race = RaceState::new(
+            vec![Source::Laserstream, Source::Websocket, Source::Rpc],
+            StatusThresholds::BLOCK_PROPAGATION,
+            false,
+        );
+        let timeouts = RaceTimeouts {
+            no_show_ms: 10_000,
+            straggler_ms: 2_000,
+        };
+        let arrivals = [
+            // WebSocket wins, LaserStream 30ms ahead of RPC
+            (
+                1,
+                [
+                    (Source::Websocket, 1_000),
+                    (Source::Laserstream, 1_010),
+                    (Source::Rpc, 1_040),
+                ],
+            ),
+            // RPC beats LaserStream by 10ms
+            (
+                2,
+                [
+                    (Source::Rpc, 1_400),
+                    (Source::Laserstream, 1_410),
+                    (Source::Websocket, 1_420),
+                ],
+            ),
+        ];
+        for (slot, events) in arrivals {
+            for (source, arrival_ms) in events {
+                record_event(
+                    &mut race,
+                    slot,
+                    event(BLOCK_TIME_MS, arrival_ms),
+                    source,
+                    timeouts,
+                );
+            }
+        }
+        // RPC never delivers slot 3: it does not count towards the average
+        for (source, arrival_ms) in [(Source::Laserstream, 1_800), (Source::Websocket, 1_850)] {
+            record_event(
+                &mut race,
+                3,
+                event(BLOCK_TIME_MS, arrival_ms),
+                source,
+                timeouts,
+            );
+        }
+        record_event(
+            &mut race,
+            4,
+            event(BLOCK_TIME_MS, 4_000),
+            Source::Laserstream,
+            timeouts,
+        );
+
+        assert_eq!(race.recent.len(), 3);
+        let panel = race.dashboard_panel();
+        assert!(
+            panel.contains("Avg LaserStream advantage over RPC: +10ms over 2 slots"),
+            "{}",
+            panel
+        );
+    }
+}
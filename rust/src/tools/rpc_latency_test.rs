@@ -0,0 +1,732 @@
+//! Processed-slot detection latency of an HTTP RPC (`rpc_latency_test`, `bench rpc`).
+//!
+//! With `--assert-p95` / `--assert-success-rate` the run doubles as a health
+//! probe: it ends with one PASS/FAIL line, and a failed check returns
+//! [`AssertionFailed`], which the binaries turn into
+//! [`ASSERTION_FAILED_EXIT_CODE`].
+
+use crate::block_time::{self, BlockTimeRejections};
+use crate::capabilities::{self, Capability};
+use crate::clock::{self, TimeSource};
+use crate::environment::RunEnvironment;
+use crate::hook::SampleHook;
+use crate::inject::{InjectSpec, LatencyInjector};
+use crate::logging::{self, LogFormat};
+use crate::methodology::Methodology;
+use crate::poll::{self, AdaptiveInterval, SlotTracker};
+use crate::prewarm;
+use crate::report::{BenchmarkReport, Measurement};
+use crate::rpc::{self, Commitment, RPCConfig};
+use crate::shutdown::Shutdown;
+use crate::stats::{self, HistogramBuckets, LatencyStats, Percentiles};
+use crate::status::{LatencyStatus, StatusThresholds};
+use crate::stop::StopCondition;
+use crate::storage::MeasurementStore;
+use crate::types::SlotLatency;
+use crate::warmup::{Warmup, WARMUP_LABEL};
+use anyhow::Result;
+use clap::builder::ArgPredicate;
+use clap::Parser;
+use reqwest::Client;
+use std::fmt;
+use std::time::Duration;
+use tokio::time;
+use tracing::debug;
+
+#[derive(Parser)]
+#[command(name = "rpc-latency-test")]
+#[command(about = "Measure RPC latency using processed slot detection")]
+pub struct Args {
+    #[arg(
+        long,
+        default_value = "../shared/config.json",
+        help = "Config file path"
+    )]
+    config: String,
+
+    #[arg(
+        long,
+        default_value = "2",
+        default_value_if("blocks", ArgPredicate::IsPresent, None),
+        value_name = "MINUTES",
+        help = "Test duration in minutes (no default with --blocks; with both, whichever comes first)"
+    )]
+    duration: Option<u64>,
+
+    #[arg(
+        long,
+        conflicts_with = "duration",
+        value_name = "SECS",
+        help = "Test duration in seconds instead of minutes, e.g. 30 for a health probe"
+    )]
+    duration_secs: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Stop after N measured slots (with --duration or --duration-secs, whichever comes first)"
+    )]
+    blocks: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "MS",
+        help = "Fail the run (exit code 2) unless the P95 latency is below MS"
+    )]
+    assert_p95: Option<i64>,
+
+    #[arg(
+        long,
+        value_name = "PERCENT",
+        help = "Fail the run (exit code 2) unless at least PERCENT of detected slots got a valid block time"
+    )]
+    assert_success_rate: Option<f64>,
+
+    #[arg(
+        long,
+        help = "Detailed per-slot output, plus debug diagnostics on stderr (same as RUST_LOG=solana_rpc_performance_rust=debug)"
+    )]
+    verbose: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "text",
+        help = "Format of the diagnostics on stderr; RUST_LOG sets their levels"
+    )]
+    log_format: LogFormat,
+
+    #[arg(long, help = "RPC provider to test (helius, solana, etc)")]
+    provider: Option<String>,
+
+    #[arg(long, help = "Print the methodology constants used in this run")]
+    fairness_report: bool,
+
+    #[arg(long, help = "Skip the best-effort public IP / location lookup")]
+    no_env_probe: bool,
+
+    #[arg(
+        long,
+        help = "Skip the startup check of which RPC methods and commitments the provider supports"
+    )]
+    no_capability_probe: bool,
+
+    #[arg(
+        long,
+        value_name = "EXCELLENT,GOOD,FAIR",
+        help = "Status band upper bounds in ms (overrides config, default 300,1000,3000)"
+    )]
+    status_thresholds: Option<StatusThresholds>,
+
+    #[arg(
+        long,
+        help = "Resolve DNS and open the connection with a throwaway getSlot before timing"
+    )]
+    prewarm_dns: bool,
+
+    #[arg(
+        long,
+        value_name = "CMD",
+        help = "Run CMD (via sh -c) for each sample with SLOT, LATENCY_MS, SOURCE, STATUS set"
+    )]
+    on_sample: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "CMD",
+        help = "Run CMD for each SLOW sample (at or above the fair threshold)"
+    )]
+    on_alert: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "1000",
+        help = "Minimum milliseconds between runs of each hook command"
+    )]
+    hook_interval_ms: u64,
+
+    #[arg(
+        long,
+        default_value = "clock_realtime",
+        value_name = "clock_realtime|ntp[:server]|file:<path>",
+        help = "Wall clock for latency math; ntp[:server] applies an offset measured once at startup, file:<path> the PTP/GPS offset (ms) read from that file"
+    )]
+    time_source: TimeSource,
+
+    #[arg(
+        long,
+        default_value = "0",
+        value_name = "N",
+        help = "Discard the first N measurements from the statistics (still printed live, marked warmup)"
+    )]
+    warmup: u64,
+
+    #[arg(
+        long,
+        value_name = "CSV",
+        help = "Write bucket_lower_ms,bucket_upper_ms,count of the latency distribution"
+    )]
+    histogram_out: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Append every measurement to this SQLite database (created and migrated if needed)"
+    )]
+    db: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "0,250,500,750,1000,1250,1500,2000,3000,5000",
+        value_name = "EDGES",
+        help = "Ascending bucket edges in ms for --histogram-out"
+    )]
+    histogram_buckets: HistogramBuckets,
+
+    #[arg(
+        long,
+        default_value = "50,90,95,99",
+        value_name = "LIST",
+        help = "Percentiles to report, e.g. 50,90,99,99.9"
+    )]
+    percentiles: Percentiles,
+
+    #[arg(
+        long,
+        help = "Also report avg/P95 per UTC hour of day (useful for multi-hour runs)"
+    )]
+    by_hour: bool,
+
+    #[arg(
+        long,
+        default_value_t = poll::DEFAULT_MIN_POLL_MS,
+        value_name = "MS",
+        help = "Shortest sleep between getSlot polls; the interval adapts to half the observed slot time"
+    )]
+    min_poll_ms: u64,
+
+    #[arg(
+        long,
+        default_value_t = poll::DEFAULT_MAX_POLL_MS,
+        value_name = "MS",
+        help = "Longest sleep between getSlot polls"
+    )]
+    max_poll_ms: u64,
+
+    #[arg(long, hide = true, value_name = "MS@PERCENT")]
+    inject_latency: Option<InjectSpec>,
+
+    #[arg(long, hide = true)]
+    seed: Option<u64>,
+}
+
+/// Exit status of a run whose `--assert-*` check failed; a run that could
+/// not measure at all exits with 1.
+pub const ASSERTION_FAILED_EXIT_CODE: i32 = 2;
+
+/// An `--assert-*` check failed; one reason per failed check.
+#[derive(Debug)]
+pub struct AssertionFailed(pub Vec<String>);
+
+impl fmt::Display for AssertionFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "assertion failed: {}", self.0.join(", "))
+    }
+}
+
+impl std::error::Error for AssertionFailed {}
+
+impl Args {
+    /// Starts the clock, so build it right before the measurement loop.
+    fn stop_condition(&self) -> Result<StopCondition> {
+        let duration = match self.duration_secs {
+            Some(secs) => Some(Duration::from_secs(secs)),
+            None => self
+                .duration
+                .map(|minutes| Duration::from_secs(minutes * 60)),
+        };
+        StopCondition::with_duration(duration, self.blocks)
+    }
+}
+
+/// Slots seen advancing, and how many of them got a valid block time.
+#[derive(Debug, Clone, Copy, Default)]
+struct Coverage {
+    detected: u64,
+    measured: u64,
+}
+
+impl Coverage {
+    fn success_rate(&self) -> Option<f64> {
+        (self.detected > 0).then(|| self.measured as f64 / self.detected as f64 * 100.0)
+    }
+}
+
+pub async fn run(args: Args) -> Result<BenchmarkReport, Box<dyn std::error::Error>> {
+    logging::init(args.log_format, args.verbose, false);
+    println!("⚡ RPC Slot Latency Test (Processed Commitment)");
+    println!("Stop after: {}", args.stop_condition()?);
+    println!("Time source: {}", args.time_source);
+
+    let mut injector = args
+        .inject_latency
+        .map(|spec| LatencyInjector::new(spec, args.seed));
+    if let Some(injector) = &injector {
+        println!("{}", injector.banner());
+    }
+    println!();
+
+    clock::init(&args.time_source)?;
+
+    let config = rpc::load_config(&args.config)?;
+    let thresholds = StatusThresholds::resolve(
+        args.status_thresholds,
+        config.status_thresholds,
+        StatusThresholds::RPC_POLLING,
+    )?;
+
+    // Select RPC based on provider preference
+    let rpc = if let Some(provider) = &args.provider {
+        config
+            .rpcs
+            .values()
+            .find(|r| {
+                r.provider.to_lowercase().contains(&provider.to_lowercase()) && r.status == "active"
+            })
+            .ok_or_else(|| anyhow::anyhow!("No active RPC found for provider: {}", provider))?
+    } else {
+        config
+            .rpcs
+            .values()
+            .find(|r| r.provider == "Helius" && r.status == "active")
+            .or_else(|| config.rpcs.values().find(|r| r.status == "active"))
+            .ok_or_else(|| anyhow::anyhow!("No active RPCs found"))?
+    };
+
+    println!("🌐 RPC Provider: {} ({})", rpc.name, rpc.provider);
+    println!("🔗 RPC URL: {}", rpc.url);
+    println!();
+
+    if !args.no_capability_probe {
+        let client = Client::builder().timeout(Duration::from_secs(5)).build()?;
+        let capabilities = capabilities::probe_provider(&client, &rpc.url).await;
+        capabilities.print();
+        capabilities.require(&[
+            Capability::GetSlot(Commitment::Processed),
+            Capability::GetBlockTime,
+        ])?;
+    }
+
+    let environment = RunEnvironment::capture(&rpc.url, !args.no_env_probe).await;
+
+    let (latencies, warmup_discarded, coverage) =
+        monitor_slot_latency(rpc.clone(), &args, thresholds, injector.as_mut()).await?;
+
+    print_latency_results(&latencies, thresholds, &args.percentiles);
+    if let Some(rate) = coverage.success_rate() {
+        println!(
+            "Block time success: {}/{} detected slots ({:.1}%)",
+            coverage.measured, coverage.detected, rate
+        );
+    }
+    if let Some(injector) = &injector {
+        injector.print_summary();
+    }
+
+    if args.by_hour {
+        let samples: Vec<(i64, i64)> = latencies
+            .iter()
+            .map(|l| (l.detected_time, l.latency_ms))
+            .collect();
+        println!();
+        stats::print_by_hour(&stats::by_hour(&samples));
+    }
+
+    environment.print();
+
+    if let Some(path) = &args.histogram_out {
+        let samples: Vec<i64> = latencies.iter().map(|l| l.latency_ms).collect();
+        stats::write_histogram_csv(path, &samples, &args.histogram_buckets)?;
+        println!("📊 Histogram written to {}", path);
+    }
+
+    if args.fairness_report {
+        methodology(thresholds, &args).print();
+    }
+
+    let measurements = latencies
+        .iter()
+        .map(|l| Measurement {
+            slot: l.slot,
+            received_ms: l.detected_time,
+            latency_ms: l.latency_ms,
+            created_at_ms: None,
+        })
+        .collect();
+    let report = BenchmarkReport::new(
+        "rpc_latency_test",
+        &rpc.url,
+        measurements,
+        0,
+        warmup_discarded,
+    );
+    check_assertions(&args, &report, coverage)?;
+    Ok(report)
+}
+
+/// Print the PASS/FAIL line of the `--assert-*` checks, if any were given.
+/// No samples fail a P95 check, no detected slots a success rate check.
+fn check_assertions(
+    args: &Args,
+    report: &BenchmarkReport,
+    coverage: Coverage,
+) -> Result<(), AssertionFailed> {
+    let mut passed = Vec::new();
+    let mut failed = Vec::new();
+
+    if let Some(limit) = args.assert_p95 {
+        match report.stats.as_ref().map(|stats| stats.p95_ms) {
+            Some(p95) if p95 < limit => passed.push(format!("p95 {}ms < {}ms", p95, limit)),
+            Some(p95) => failed.push(format!("p95 {}ms >= {}ms", p95, limit)),
+            None => failed.push("p95 unknown, no samples".to_string()),
+        }
+    }
+    if let Some(minimum) = args.assert_success_rate {
+        match coverage.success_rate() {
+            Some(rate) if rate >= minimum => {
+                passed.push(format!("success rate {:.1}% >= {}%", rate, minimum))
+            }
+            Some(rate) => failed.push(format!("success rate {:.1}% < {}%", rate, minimum)),
+            None => failed.push("success rate unknown, no slots detected".to_string()),
+        }
+    }
+
+    if passed.is_empty() && failed.is_empty() {
+        return Ok(());
+    }
+    println!();
+    if failed.is_empty() {
+        println!("✅ PASS: {}", passed.join(", "));
+        Ok(())
+    } else {
+        println!("❌ FAIL: {}", failed.join(", "));
+        Err(AssertionFailed(failed))
+    }
+}
+
+async fn monitor_slot_latency(
+    rpc: RPCConfig,
+    args: &Args,
+    thresholds: StatusThresholds,
+    mut injector: Option<&mut LatencyInjector>,
+) -> Result<(Vec<SlotLatency>, u64, Coverage)> {
+    let client = Client::builder().timeout(Duration::from_secs(5)).build()?;
+    let verbose = args.verbose;
+
+    let hook_interval = Duration::from_millis(args.hook_interval_ms);
+    let mut sample_hook = args
+        .on_sample
+        .clone()
+        .map(|cmd| SampleHook::new(cmd, hook_interval));
+    let mut alert_hook = args
+        .on_alert
+        .clone()
+        .map(|cmd| SampleHook::new(cmd, hook_interval));
+
+    if args.prewarm_dns {
+        prewarm::rpc(&client, &rpc.url).await.print();
+    }
+
+    // Synthetic samples must never end up in the history
+    let store = if args.db.is_some() && args.inject_latency.is_some() {
+        eprintln!("⚠️  --db ignored while --inject-latency is active");
+        None
+    } else {
+        args.db.as_deref().map(MeasurementStore::open).transpose()?
+    };
+    let mut latencies = Vec::new();
+    let mut shutdown = Shutdown::install();
+
+    println!("🚀 Starting slot latency monitoring...");
+    println!(
+        "📊 Checking new slots every half slot, {}-{}ms apart",
+        args.min_poll_ms, args.max_poll_ms
+    );
+    println!();
+
+    if !verbose {
+        println!("Slot      | Block Time   | Detected    | Latency | Status");
+        println!("{}", "-".repeat(55));
+    }
+
+    let mut warmup = Warmup::new(args.warmup);
+    let mut rejections = BlockTimeRejections::default();
+    let mut coverage = Coverage::default();
+    let mut tracker =
+        SlotTracker::new(rpc::get_latest_slot(&client, &rpc.url, Commitment::Processed).await?);
+    let mut interval = AdaptiveInterval::new(args.min_poll_ms, args.max_poll_ms);
+
+    let stop = args.stop_condition()?;
+    while stop.running(latencies.len() as u64) {
+        let previous_slot = tracker.last_slot();
+        match tracker
+            .poll(|| rpc::get_latest_slot(&client, &rpc.url, Commitment::Processed))
+            .await
+        {
+            Ok(Some(current_slot)) => {
+                interval.advanced(previous_slot, current_slot);
+                if current_slot > previous_slot + 1 {
+                    debug!(
+                        "Slot jumped {} -> {}: {} transitions likely missed",
+                        previous_slot,
+                        current_slot,
+                        current_slot - previous_slot - 1
+                    );
+                }
+
+                // New slot detected! Now check if we can get its block time
+                let detected_time = clock::now_ms();
+                coverage.detected += 1;
+
+                match rpc::retry(
+                    || rpc::get_block_time(&client, &rpc.url, current_slot),
+                    rpc::POLL_RETRY_ATTEMPTS,
+                    rpc::POLL_RETRY_BASE_DELAY,
+                )
+                .await
+                {
+                    Ok(Some(block_time)) => {
+                        if let Err(invalid) =
+                            block_time::validate_block_time(block_time, current_slot, detected_time)
+                        {
+                            rejections.record(&invalid);
+                            debug!("Slot {} | Rejected: {}", current_slot, invalid);
+                        } else {
+                            coverage.measured += 1;
+                            let mut latency_ms = detected_time - (block_time * 1000);
+                            if warmup.absorb() {
+                                let slot_latency = SlotLatency {
+                                    slot: current_slot,
+                                    block_time,
+                                    detected_time,
+                                    latency_ms,
+                                };
+                                log_slot_latency(&slot_latency, WARMUP_LABEL, verbose);
+                            } else {
+                                if let Some(injector) = injector.as_mut() {
+                                    latency_ms = injector.apply(latency_ms);
+                                }
+
+                                let slot_latency = SlotLatency {
+                                    slot: current_slot,
+                                    block_time,
+                                    detected_time,
+                                    latency_ms,
+                                };
+
+                                log_slot_latency(
+                                    &slot_latency,
+                                    thresholds.label(latency_ms),
+                                    verbose,
+                                );
+
+                                let status = thresholds.status(latency_ms);
+                                if let Some(hook) = sample_hook.as_mut() {
+                                    hook.fire("rpc", current_slot, latency_ms, status.name());
+                                }
+                                if status == LatencyStatus::Slow {
+                                    if let Some(hook) = alert_hook.as_mut() {
+                                        hook.fire("rpc", current_slot, latency_ms, status.name());
+                                    }
+                                }
+                                if let Some(store) = &store {
+                                    if let Err(e) = store.insert_slot_latency(&slot_latency) {
+                                        eprintln!("❌ Cannot store slot {}: {}", current_slot, e);
+                                    }
+                                }
+                                latencies.push(slot_latency);
+                            }
+                        }
+                    }
+                    Ok(None) => {
+                        debug!("Slot {} | Block time not available yet", current_slot);
+                    }
+                    Err(e) => {
+                        debug!("Slot {} | Error getting block time: {}", current_slot, e);
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                debug!("Error getting latest slot: {}", e);
+            }
+        }
+
+        tokio::select! {
+            _ = shutdown.wait() => break,
+            _ = time::sleep(interval.next()) => {}
+        }
+    }
+
+    tracker.print_summary();
+    interval.print_summary();
+    warmup.print_summary();
+    rejections.print();
+    if let Some(hook) = &sample_hook {
+        hook.print_summary("--on-sample");
+    }
+    if let Some(hook) = &alert_hook {
+        hook.print_summary("--on-alert");
+    }
+
+    Ok((latencies, warmup.discarded, coverage))
+}
+
+fn log_slot_latency(latency: &SlotLatency, status: &str, verbose: bool) {
+    if verbose {
+        println!("🎯 Slot {} Latency Analysis:", latency.slot);
+        println!("   Block Created: {} (Unix timestamp)", latency.block_time);
+        println!(
+            "   RPC Detected:  {} (Unix timestamp ms)",
+            latency.detected_time
+        );
+        println!("   Latency:       {}ms {}", latency.latency_ms, status);
+        println!();
+    } else {
+        println!(
+            "{:<9} | {:<12} | {:<11} | {:<7}ms | {}",
+            latency.slot,
+            latency.block_time,
+            latency.detected_time / 1000,
+            latency.latency_ms,
+            status
+        );
+    }
+}
+
+fn print_latency_results(
+    latencies: &[SlotLatency],
+    thresholds: StatusThresholds,
+    percentiles: &Percentiles,
+) {
+    if latencies.is_empty() {
+        println!("❌ No slot latency measurements collected");
+        return;
+    }
+
+    let latency_values: Vec<i64> = latencies.iter().map(|l| l.latency_ms).collect();
+    let LatencyStats {
+        count,
+        avg_ms: avg,
+        min_ms: min,
+        max_ms: max,
+        std_dev_ms,
+        jitter_ms,
+        ..
+    } = match LatencyStats::from_samples(&latency_values) {
+        Some(stats) => stats,
+        None => return,
+    };
+
+    println!();
+    println!("📊 RPC Slot Detection Latency Results");
+    println!("{}", "=".repeat(50));
+    println!("Slots measured:     {}", count);
+    println!("Average latency:    {:.1}ms", avg);
+    println!("Min latency:        {}ms", min);
+    println!("Max latency:        {}ms", max);
+    println!("Std deviation:      {:.1}ms", std_dev_ms);
+    println!("Jitter:             {:.1}ms", jitter_ms);
+    for (label, value) in percentiles.of(&latency_values) {
+        println!("{:<20}{}ms", format!("{}:", label), value);
+    }
+
+    // Performance categories
+    let excellent_count = latency_values
+        .iter()
+        .filter(|&&l| l < thresholds.excellent_ms)
+        .count();
+    let good_count = latency_values
+        .iter()
+        .filter(|&&l| l < thresholds.good_ms)
+        .count();
+    let fair_count = latency_values
+        .iter()
+        .filter(|&&l| l < thresholds.fair_ms)
+        .count();
+
+    println!();
+    println!("⚡ Performance Distribution:");
+    println!(
+        "🟢 Excellent (<{}ms): {}/{} ({:.1}%)",
+        thresholds.excellent_ms,
+        excellent_count,
+        count,
+        (excellent_count as f64 / count as f64) * 100.0
+    );
+    println!(
+        "🟡 Good (<{}ms):      {}/{} ({:.1}%)",
+        thresholds.good_ms,
+        good_count,
+        count,
+        (good_count as f64 / count as f64) * 100.0
+    );
+    println!(
+        "🟠 Fair (<{}ms):      {}/{} ({:.1}%)",
+        thresholds.fair_ms,
+        fair_count,
+        count,
+        (fair_count as f64 / count as f64) * 100.0
+    );
+
+    println!();
+    println!("🎯 Overall Performance:");
+    match thresholds.status_f64(avg) {
+        LatencyStatus::Excellent => {
+            println!(
+                "✅ EXCELLENT - Sub-{}ms average latency!",
+                thresholds.excellent_ms
+            );
+            println!("💡 Perfect for real-time trading and indexing");
+        }
+        LatencyStatus::Good => {
+            println!("🟡 GOOD - Sub-{}ms average latency", thresholds.good_ms);
+            println!("💡 Suitable for most real-time applications");
+        }
+        LatencyStatus::Fair => {
+            println!("🟠 FAIR - Sub-{}ms average latency", thresholds.fair_ms);
+            println!("💡 Acceptable for general applications");
+        }
+        LatencyStatus::Slow => {
+            println!("🔴 SLOW - >{}ms average latency", thresholds.fair_ms);
+            println!("💡 Consider faster RPC providers");
+        }
+    }
+
+    println!();
+    println!("📋 Methodology:");
+    println!("• Uses getSlot() with processed commitment for slot detection");
+    println!("• Uses getBlockTime() to get block creation timestamp");
+    println!("• Latency = slot_detection_time - block_creation_time");
+    println!("• Polling interval: half the observed slot time, shortened after skipped slots");
+}
+
+fn methodology(thresholds: StatusThresholds, args: &Args) -> Methodology {
+    Methodology {
+        tool: "rpc_latency_test",
+        source: "HTTP polling (getSlot + getBlockTime)",
+        latency_formula: "detected_time_ms - block_time * 1000",
+        time_source: args.time_source.to_string(),
+        commitment: "processed",
+        poll_interval_ms: Some(args.min_poll_ms),
+        poll_interval_max_ms: Some(args.max_poll_ms),
+        latency_filter: Some(format!(
+            "block_time > 0, at most {}ms ahead of and {}ms behind detected_time",
+            block_time::FUTURE_TOLERANCE_MS,
+            block_time::MAX_AGE_MS
+        )),
+        warmup_samples: args.warmup,
+        percentile_definition: stats::PERCENTILE_DEFINITION,
+        status_thresholds: thresholds,
+    }
+}
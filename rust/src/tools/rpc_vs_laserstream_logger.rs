@@ -0,0 +1,1528 @@
+//! Side-by-side block log of RPC and Laserstream (`rpc_vs_laserstream_logger`, `bench feed`).
+
+use crate::block;
+use crate::breakdown::{ClockSkew, LatencyBreakdown};
+use crate::capabilities::{self, Capability};
+use crate::clock::{self, TimeSource};
+use crate::comparison::{
+    self, compare_feed_latencies, write_comparison_report, ReportFormat, ScoreWeights,
+};
+use crate::feed::CommitmentFeed;
+use crate::logging::{self, LogFormat};
+use crate::ratelimit::RequestLimiter;
+use crate::report;
+use crate::rpc::{self, Commitment, RPCConfig, TimedOut, Transfer};
+use crate::shutdown::Shutdown;
+use crate::stats;
+use crate::stop::StopCondition;
+use crate::subscription;
+use crate::types::{BlockInfo, RewardLamports};
+use anyhow::Result;
+use clap::builder::ArgPredicate;
+use clap::Parser;
+use futures::{future, StreamExt};
+use helius_laserstream::{
+    grpc::{SubscribeRequest, SubscribeRequestFilterBlocks},
+    LaserstreamConfig,
+};
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::time;
+use tracing::debug;
+use yellowstone_grpc_proto::prelude::RewardType;
+
+/// block_time has one-second resolution, so an honest source's median
+/// propagation latency can't sit below zero; anything lower points at a
+/// provider reporting block_time differently (or serving cached data).
+const MIN_PLAUSIBLE_MEDIAN_MS: i64 = 0;
+/// Share of negative samples above which a source is flagged even if its median looks fine.
+const MAX_NEGATIVE_SHARE: f64 = 0.25;
+/// Share of Laserstream blocks without `created_at` above which the
+/// created_at-based network latency is reported as not applicable.
+const MAX_MISSING_CREATED_AT_SHARE: f64 = 0.1;
+/// Mismatched slots printed by the block_time and blockhash cross-checks before truncating.
+const MAX_LISTED_MISMATCHES: usize = 20;
+/// Common slots per window of the rolling RPC − Laserstream mean difference.
+const BIAS_WINDOW: usize = 20;
+/// Two-sided 95% z value for the "is the mean difference non-zero" test.
+const BIAS_Z: f64 = 1.96;
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+#[derive(Parser)]
+#[command(name = "rpc-vs-laserstream-logger")]
+#[command(about = "Log block information from both RPC and Laserstream for comparison")]
+pub struct Args {
+    #[arg(long, help = "Helius API key")]
+    api_key: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "https://laserstream-mainnet-tyo.helius-rpc.com",
+        help = "Helius Laserstream endpoint"
+    )]
+    endpoint: String,
+
+    #[arg(
+        long,
+        default_value = "../shared/config.json",
+        help = "Config file path"
+    )]
+    config: String,
+
+    #[arg(
+        long,
+        default_value = "3",
+        default_value_if("blocks", ArgPredicate::IsPresent, None),
+        value_name = "MINUTES",
+        help = "Test duration in minutes (no default with --blocks; with both, whichever comes first)"
+    )]
+    duration: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Stop each feed after it delivered N blocks (with --duration, whichever comes first)"
+    )]
+    blocks: Option<u64>,
+
+    #[arg(
+        long,
+        help = "Detailed per-block output, plus debug diagnostics on stderr (same as RUST_LOG=solana_rpc_performance_rust=debug)"
+    )]
+    verbose: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "text",
+        help = "Format of the diagnostics on stderr; RUST_LOG sets their levels"
+    )]
+    log_format: LogFormat,
+
+    #[arg(
+        long,
+        default_value = "clock_realtime",
+        value_name = "clock_realtime|ntp[:server]|file:<path>",
+        help = "Wall clock for latency math; ntp[:server] applies an offset measured once at startup, file:<path> the PTP/GPS offset (ms) read from that file"
+    )]
+    time_source: TimeSource,
+
+    #[arg(
+        long,
+        default_value = "signatures",
+        value_parser = ["signatures", "full", "accounts"],
+        help = "transactionDetails level requested from RPC getBlock"
+    )]
+    transaction_details: String,
+
+    #[arg(
+        long,
+        help = "Log every JSON-RPC request/response with ids and round trip (DEBUG)"
+    )]
+    trace_rpc: bool,
+
+    #[arg(
+        long,
+        default_value = "10000",
+        help = "Blocks retained per source for the final summary (oldest are evicted)"
+    )]
+    history_limit: usize,
+
+    #[arg(long, help = "Retain every block for the summary (unbounded memory)")]
+    keep_history: bool,
+
+    #[arg(
+        long,
+        help = "Skip the startup check of which RPC methods and commitments the provider supports"
+    )]
+    no_capability_probe: bool,
+
+    #[arg(
+        long,
+        help = "Report upstream, delivery and total latency as separate distributions"
+    )]
+    breakdown: bool,
+
+    #[arg(
+        long,
+        default_value = "off",
+        value_parser = ["off", "gzip", "compare"],
+        help = "Accept-Encoding for RPC getBlock; compare fetches every block both ways"
+    )]
+    compression: String,
+
+    #[arg(
+        long,
+        help = "Also fetch every new RPC slot at processed, confirmed and finalized and report each commitment's feed latency"
+    )]
+    compare_commitments: bool,
+
+    #[arg(
+        long,
+        default_value = "4",
+        value_name = "N",
+        help = "RPC getBlock requests in flight when several new slots arrive at once; the rate comes from the provider's rateLimit in the config"
+    )]
+    max_concurrency: usize,
+
+    #[arg(
+        long,
+        help = "Print one compact JSON object per block on stdout instead of the table, raw dumps and summary"
+    )]
+    jsonl: bool,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with = "jsonl",
+        help = "Recompute the summary from a --jsonl capture instead of monitoring (no network access)"
+    )]
+    replay: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "20",
+        value_name = "N",
+        help = "Ranges of Laserstream slots missing from RPC listed in the summary"
+    )]
+    max_listed_missed: usize,
+
+    #[arg(
+        long,
+        default_value = "350",
+        value_name = "MS",
+        help = "Give up on a getBlock after MS and skip the slot, so a stalled call cannot hold up the 400ms poll"
+    )]
+    call_timeout_ms: u64,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write per-source propagation latency stats (samples, avg, min, max, p50, p95, success rate, score), best score first"
+    )]
+    comparison_report: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "csv",
+        requires = "comparison_report",
+        help = "Format of --comparison-report"
+    )]
+    comparison_format: ReportFormat,
+
+    #[arg(
+        long,
+        default_value = "median=0.4,p95=0.3,success=0.3",
+        value_name = "WEIGHTS",
+        help = "Weights of the provider ranking score terms (median, p95, success)"
+    )]
+    score_weights: ScoreWeights,
+}
+
+/// getBlock transfers by requested encoding, for --compression.
+#[derive(Debug, Default)]
+struct TransferLog {
+    plain: Vec<Transfer>,
+    gzip: Vec<Transfer>,
+}
+
+impl TransferLog {
+    fn append(&mut self, other: TransferLog) {
+        self.plain.extend(other.plain);
+        self.gzip.extend(other.gzip);
+    }
+}
+
+/// How the RPC monitor fetches each new slot's block.
+struct BlockFetch {
+    /// Processed unless the capability probe found the provider rejects it.
+    commitment: Commitment,
+    transaction_details: String,
+    compression: String,
+    limiter: RequestLimiter,
+    /// Per attempt, so waiting for the limiter does not count against it.
+    call_timeout: Duration,
+}
+
+pub async fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(path) = &args.replay {
+        return replay(path, &args);
+    }
+
+    logging::init(args.log_format, args.verbose, args.trace_rpc);
+
+    let api_key = args
+        .api_key
+        .clone()
+        .or_else(|| std::env::var("HELIUS_API_KEY").ok())
+        .unwrap_or_else(|| "9de07723-0030-4ee0-b175-6722231d5d97".to_string());
+
+    if !args.jsonl {
+        println!("🔍 RPC vs Laserstream Block Information Logger");
+        println!("Comparing block data from both sources");
+        println!(
+            "Stop after: {}",
+            StopCondition::new(args.duration, args.blocks)?
+        );
+        println!("Laserstream endpoint: {}", args.endpoint);
+        println!("Time source: {}", args.time_source);
+        println!();
+    }
+
+    clock::init(&args.time_source)?;
+
+    let config = rpc::load_config(&args.config)?;
+
+    // Get premium RPC (Helius) or fallback to first active RPC
+    let rpc = config
+        .rpcs
+        .values()
+        .find(|r| r.provider == "Helius" && r.status == "active")
+        .or_else(|| config.rpcs.values().find(|r| r.status == "active"))
+        .ok_or_else(|| anyhow::anyhow!("No active RPCs found"))?;
+
+    let requests_per_second = rpc.requests_per_second();
+    if !args.jsonl {
+        println!("RPC Provider: {} ({})", rpc.name, rpc.provider);
+        println!("RPC URL: {}", rpc.url);
+        match requests_per_second {
+            Some(rate) => println!(
+                "RPC pacing: {} req/s, {} concurrent getBlock",
+                rate, args.max_concurrency
+            ),
+            None => println!(
+                "RPC pacing: no rateLimit in config, {} concurrent getBlock",
+                args.max_concurrency
+            ),
+        }
+        println!();
+    }
+
+    // getBlock below confirmed is commonly rejected, so fetch at the weakest
+    // commitment the provider accepts
+    let mut block_commitment = Commitment::Processed;
+    if !args.no_capability_probe {
+        let client = Client::builder().timeout(Duration::from_secs(10)).build()?;
+        let capabilities = capabilities::probe_provider(&client, &rpc.url).await;
+        if !args.jsonl {
+            capabilities.print();
+        }
+        capabilities.require(&[Capability::GetSlot(Commitment::Processed)])?;
+        block_commitment = capabilities
+            .get_block
+            .weakest_usable()
+            .unwrap_or(Commitment::Confirmed);
+        capabilities.require(&[Capability::GetBlock(block_commitment)])?;
+        if block_commitment != Commitment::Processed && !args.jsonl {
+            println!(
+                "RPC getBlock at {}: the provider rejects processed, so RPC latencies include the wait for {}",
+                block_commitment, block_commitment
+            );
+            println!();
+        }
+    }
+
+    let mut all_blocks = Vec::new();
+    let history_limit = (!args.keep_history).then_some(args.history_limit);
+    let shutdown = Shutdown::install();
+    // One clock for every feed, each counting its own blocks
+    let stop = StopCondition::new(args.duration, args.blocks)?;
+
+    // Start both monitoring tasks
+    let laserstream_handle = tokio::spawn(monitor_laserstream(
+        api_key.clone(),
+        args.endpoint.clone(),
+        stop,
+        history_limit,
+        shutdown.clone(),
+        args.verbose,
+        args.jsonl,
+    ));
+
+    let rpc_handle = tokio::spawn(monitor_rpc(
+        rpc.clone(),
+        stop,
+        BlockFetch {
+            commitment: block_commitment,
+            transaction_details: args.transaction_details.clone(),
+            compression: args.compression.clone(),
+            limiter: RequestLimiter::new(args.max_concurrency, requests_per_second),
+            call_timeout: Duration::from_millis(args.call_timeout_ms),
+        },
+        history_limit,
+        shutdown.clone(),
+        args.verbose,
+        args.jsonl,
+    ));
+
+    let commitments_handle = args
+        .compare_commitments
+        .then(|| tokio::spawn(monitor_commitments(rpc.url.clone(), stop, shutdown.clone())));
+
+    if !args.jsonl {
+        println!("🚀 Starting dual monitoring...");
+        println!("📡 Laserstream: Real-time gRPC stream");
+        println!(
+            "🌐 RPC: HTTP polling every {}ms",
+            rpc::SLOT_POLL_INTERVAL_MS
+        );
+        println!();
+        println!("Block Format:");
+        println!("SOURCE     | Slot     | Block Time | Received | Network Lat. | Propagation | Parent | Height | TXs");
+        println!("{}", "-".repeat(95));
+    }
+
+    // Wait for both to complete
+    let (laserstream_result, rpc_result) = tokio::join!(laserstream_handle, rpc_handle);
+
+    let laserstream_blocks = laserstream_result??;
+    let (rpc_blocks, transfers) = rpc_result??;
+
+    // stdout carries nothing but the JSON lines
+    if args.jsonl {
+        if let Some(handle) = commitments_handle {
+            handle.await??;
+        }
+        return Ok(());
+    }
+
+    all_blocks.extend(laserstream_blocks);
+    all_blocks.extend(rpc_blocks);
+
+    // Sort by slot for comparison
+    all_blocks.sort_by_key(|b| b.slot);
+
+    println!();
+    println!("📊 Final Summary");
+    if let Some(limit) = history_limit {
+        println!(
+            "ℹ️  Summary covers at most the last {} blocks per source (use --keep-history for all)",
+            limit
+        );
+    }
+    print_block_comparison(&all_blocks, &args);
+
+    print_compression_summary(&transfers, &args.compression);
+
+    if let Some(handle) = commitments_handle {
+        handle.await??.print();
+    }
+
+    Ok(())
+}
+
+async fn monitor_laserstream(
+    api_key: String,
+    endpoint: String,
+    stop: StopCondition,
+    history_limit: Option<usize>,
+    mut shutdown: Shutdown,
+    verbose: bool,
+    jsonl: bool,
+) -> Result<Vec<BlockInfo>> {
+    let config = LaserstreamConfig {
+        api_key,
+        endpoint: endpoint.parse()?,
+        ..Default::default()
+    };
+
+    let mut block_filters = HashMap::new();
+    block_filters.insert(
+        "all_blocks".to_string(),
+        SubscribeRequestFilterBlocks {
+            account_include: vec![],
+            include_transactions: Some(true), // Get transaction data
+            include_accounts: Some(false),
+            include_entries: Some(false),
+        },
+    );
+
+    let request = SubscribeRequest {
+        blocks: block_filters,
+        ..Default::default()
+    };
+
+    let (stream, subscription) = subscription::subscribe(config, request);
+    futures::pin_mut!(stream);
+
+    let mut blocks = VecDeque::new();
+    let mut delivered = 0u64;
+
+    while stop.running(delivered) {
+        let next = tokio::select! {
+            _ = shutdown.wait() => break,
+            next = stream.next() => next,
+        };
+        if let Some(result) = next {
+            match result {
+                Ok(update) => {
+                    let received_time = clock::now_ms();
+
+                    // Print raw Laserstream update as JSON
+                    if !jsonl {
+                        println!("🔥 LASERSTREAM RAW UPDATE:");
+                        println!("{{");
+                        println!("  \"received_at\": {},", received_time);
+                        println!("  \"filters\": {:?},", update.filters);
+                        println!("  \"created_at\": {:?},", update.created_at);
+                        println!("  \"update_type\": \"{}\"", match &update.update_oneof {
+                        Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::Block(_)) => "Block",
+                        Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::Transaction(_)) => "Transaction",
+                        Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::Account(_)) => "Account",
+                        Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::Slot(_)) => "Slot",
+                        Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::TransactionStatus(_)) => "TransactionStatus",
+                        Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::BlockMeta(_)) => "BlockMeta",
+                        Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::Entry(_)) => "Entry",
+                        Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::Ping(_)) => "Ping",
+                        Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::Pong(_)) => "Pong",
+                        None => "None"
+                    });
+                        println!("}}");
+                    }
+
+                    if let Some(helius_laserstream::grpc::subscribe_update::UpdateOneof::Block(
+                        block,
+                    )) = update.update_oneof
+                    {
+                        // Calculate both types of latency
+                        let laserstream_created_time = update
+                            .created_at
+                            .map(|ts| (ts.seconds * 1000) + (ts.nanos as i64 / 1_000_000));
+
+                        let network_latency =
+                            laserstream_created_time.map(|created| received_time - created);
+
+                        let block_time = block.block_time.map(|bt| bt.timestamp);
+                        let propagation_latency = block_time.map(|bt| received_time - (bt * 1000));
+
+                        if !jsonl {
+                            println!("📦 LASERSTREAM BLOCK DETAILS:");
+                            println!("{{");
+                            println!("  \"slot\": {},", block.slot);
+                            println!("  \"parent_slot\": {},", block.parent_slot);
+                            println!(
+                                "  \"block_height\": {:?},",
+                                block.block_height.as_ref().map(|bh| bh.block_height)
+                            );
+                            println!("  \"block_time\": {:?},", block_time);
+                            println!(
+                                "  \"laserstream_created_time\": {:?},",
+                                laserstream_created_time.map(|t| t / 1000)
+                            );
+                            println!("  \"network_latency_ms\": {:?},", network_latency);
+                            println!("  \"propagation_latency_ms\": {:?},", propagation_latency);
+                            println!("  \"transaction_count\": {},", block.transactions.len());
+                            println!("  \"blockhash\": \"{}\",", block.blockhash);
+                            println!("  \"parent_blockhash\": \"{}\",", block.parent_blockhash);
+                            println!(
+                                "  \"rewards_count\": {}",
+                                block
+                                    .rewards
+                                    .as_ref()
+                                    .map(|rewards| rewards.rewards.len())
+                                    .unwrap_or(0)
+                            );
+                            println!("}}");
+                            println!();
+                            println!();
+                        }
+
+                        let slot = block.slot;
+                        let parent_slot = block.parent_slot;
+                        let block_height =
+                            block.block_height.map(|bh| bh.block_height).unwrap_or(0);
+                        let tx_count = block.transactions.len();
+                        let rewards = block.rewards.as_ref().map(|rewards| {
+                            let entries = rewards
+                                .rewards
+                                .iter()
+                                .map(|reward| (reward.reward_type, reward.lamports));
+                            (rewards.rewards.len(), sum_rewards(entries))
+                        });
+
+                        let block_info = BlockInfo {
+                            slot,
+                            block_time,
+                            received_time,
+                            source: "LASERSTREAM".to_string(),
+                            parent_slot: Some(parent_slot),
+                            block_height: Some(block_height),
+                            transaction_count: Some(tx_count),
+                            blockhash: Some(block.blockhash.clone()),
+                            laserstream_created_time,
+                            network_latency_ms: network_latency,
+                            propagation_latency_ms: propagation_latency,
+                            reward_count: rewards.map(|(count, _)| count),
+                            total_fees_lamports: rewards.map(|(_, sums)| sums.fee),
+                            reward_lamports: rewards.map(|(_, sums)| sums),
+                        };
+
+                        // Log block information
+                        log_block_info(&block_info, verbose, jsonl);
+                        retain_block(&mut blocks, block_info, history_limit);
+                        delivered += 1;
+                    }
+                }
+                Err(e) => {
+                    eprintln!("❌ Laserstream error: {}", e);
+                }
+            }
+        }
+    }
+
+    subscription.cancel();
+    if !jsonl {
+        subscription.health().print();
+    }
+
+    Ok(blocks.into())
+}
+
+async fn monitor_rpc(
+    rpc: RPCConfig,
+    stop: StopCondition,
+    fetch: BlockFetch,
+    history_limit: Option<usize>,
+    mut shutdown: Shutdown,
+    verbose: bool,
+    jsonl: bool,
+) -> Result<(Vec<BlockInfo>, TransferLog)> {
+    let client = Client::builder().timeout(Duration::from_secs(10)).build()?;
+
+    let mut blocks = VecDeque::new();
+    let mut transfers = TransferLog::default();
+    let mut delivered = 0u64;
+
+    let current_slot = rpc::get_latest_slot(&client, &rpc.url, Commitment::Processed).await?;
+    let mut last_slot = current_slot;
+    let mut timed_out = 0u64;
+
+    while stop.running(delivered) {
+        match rpc::retry(
+            || rpc::get_latest_slot(&client, &rpc.url, Commitment::Processed),
+            rpc::POLL_RETRY_ATTEMPTS,
+            rpc::POLL_RETRY_BASE_DELAY,
+        )
+        .await
+        {
+            Ok(current_slot) => {
+                if current_slot > last_slot {
+                    // Fetched concurrently within the limiter, logged in slot order
+                    let fetches = ((last_slot + 1)..=current_slot).map(|slot| {
+                        let (client, fetch, url) = (&client, &fetch, &rpc.url);
+                        async move {
+                            let result = fetch
+                                .limiter
+                                .run(|| {
+                                    rpc::with_timeout(
+                                        fetch.call_timeout,
+                                        get_block_info(
+                                            client,
+                                            url,
+                                            slot,
+                                            fetch.commitment,
+                                            &fetch.transaction_details,
+                                            &fetch.compression,
+                                        ),
+                                    )
+                                })
+                                .await;
+                            (slot, result)
+                        }
+                    });
+                    for (slot, result) in future::join_all(fetches).await {
+                        match result {
+                            Ok((block_info, fetched)) => {
+                                transfers.append(fetched);
+                                match block_info {
+                                    Some(block_info) => {
+                                        log_block_info(&block_info, verbose, jsonl);
+                                        retain_block(&mut blocks, block_info, history_limit);
+                                        delivered += 1;
+                                    }
+                                    None => {
+                                        debug!("RPC      | {} | Block not available", slot);
+                                    }
+                                }
+                            }
+                            Err(e) if e.downcast_ref::<TimedOut>().is_some() => {
+                                timed_out += 1;
+                                debug!("RPC      | {} | Skipped: {}", slot, e);
+                            }
+                            Err(e) => {
+                                // Rate-limit retries exhausted show up here too
+                                debug!("RPC      | {} | Error: {}", slot, e);
+                            }
+                        }
+                    }
+                    last_slot = current_slot;
+                }
+            }
+            Err(e) => {
+                debug!("RPC slot error: {}", e);
+            }
+        }
+
+        tokio::select! {
+            _ = shutdown.wait() => break,
+            _ = time::sleep(Duration::from_millis(rpc::SLOT_POLL_INTERVAL_MS)) => {}
+        }
+    }
+
+    if timed_out > 0 {
+        eprintln!(
+            "⏱️  {} getBlock calls took over {}ms and their slots were skipped",
+            timed_out,
+            fetch.call_timeout.as_millis()
+        );
+    }
+    if fetch.limiter.rate_limited() > 0 {
+        eprintln!(
+            "🚦 RPC answered HTTP 429 {} times; those requests were retried with backoff",
+            fetch.limiter.rate_limited()
+        );
+    }
+
+    Ok((blocks.into(), transfers))
+}
+
+/// Fetch every new processed slot at all three commitments, retrying the
+/// commitments a slot has not reached yet.
+async fn monitor_commitments(
+    rpc_url: String,
+    stop: StopCondition,
+    mut shutdown: Shutdown,
+) -> Result<CommitmentFeed> {
+    let client = Client::builder().timeout(Duration::from_secs(10)).build()?;
+
+    let mut feed = CommitmentFeed::default();
+    let mut tracked = 0u64;
+    let mut last_slot = rpc::get_latest_slot(&client, &rpc_url, Commitment::Processed).await?;
+
+    while stop.running(tracked) {
+        match rpc::retry(
+            || rpc::get_latest_slot(&client, &rpc_url, Commitment::Processed),
+            rpc::POLL_RETRY_ATTEMPTS,
+            rpc::POLL_RETRY_BASE_DELAY,
+        )
+        .await
+        {
+            Ok(current_slot) => {
+                for slot in (last_slot + 1)..=current_slot {
+                    feed.track(&client, &rpc_url, slot).await;
+                    tracked += 1;
+                }
+                last_slot = last_slot.max(current_slot);
+            }
+            Err(e) => {
+                debug!("RPC slot error: {}", e);
+            }
+        }
+
+        if let Err(e) = feed.retry_pending(&client, &rpc_url).await {
+            debug!("Commitment retry error: {}", e);
+        }
+
+        tokio::select! {
+            _ = shutdown.wait() => break,
+            _ = time::sleep(Duration::from_millis(rpc::SLOT_POLL_INTERVAL_MS)) => {}
+        }
+    }
+
+    Ok(feed)
+}
+
+async fn get_block_info(
+    client: &Client,
+    rpc_url: &str,
+    slot: u64,
+    commitment: Commitment,
+    transaction_details: &str,
+    compression: &str,
+) -> Result<(Option<BlockInfo>, TransferLog)> {
+    let received_time = clock::now_ms();
+    let mut transfers = TransferLog::default();
+
+    let params = json!([
+        slot,
+        {
+            "encoding": "json",
+            "commitment": commitment.as_str(),
+            "maxSupportedTransactionVersion": 0,
+            "rewards": false,
+            "transactionDetails": transaction_details
+        }
+    ]);
+    let json_value = match compression {
+        "gzip" => {
+            let (value, transfer) =
+                rpc::call_measured(client, rpc_url, "getBlock", params, true).await?;
+            transfers.gzip.push(transfer);
+            value
+        }
+        "compare" => {
+            // Alternate which encoding goes first so server-side caching of
+            // the block does not consistently favour the second request
+            let gzip_first = slot & 1 == 0;
+            let (value, first) =
+                rpc::call_measured(client, rpc_url, "getBlock", params.clone(), gzip_first).await?;
+            let (_, second) =
+                rpc::call_measured(client, rpc_url, "getBlock", params, !gzip_first).await?;
+            let (gzip, plain) = if gzip_first {
+                (first, second)
+            } else {
+                (second, first)
+            };
+            transfers.gzip.push(gzip);
+            transfers.plain.push(plain);
+            value
+        }
+        _ => {
+            let (value, transfer) =
+                rpc::call_measured(client, rpc_url, "getBlock", params, false).await?;
+            transfers.plain.push(transfer);
+            value
+        }
+    };
+
+    if let Some(result) = json_value.get("result") {
+        if result.is_null() {
+            return Ok((None, transfers));
+        }
+
+        let block_time = result.get("blockTime").and_then(|v| v.as_i64());
+        let parent_slot = result.get("parentSlot").and_then(|v| v.as_u64());
+        let block_height = result.get("blockHeight").and_then(|v| v.as_u64());
+        // Top-level, so present whatever transactionDetails asked for
+        let blockhash = result
+            .get("blockhash")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+
+        // Handles both the `signatures` shape and legacy/v0 `transactions` entries
+        let tx_count = block::transaction_count(result);
+
+        let block_info = BlockInfo {
+            slot,
+            block_time,
+            received_time,
+            source: "RPC".to_string(),
+            parent_slot,
+            block_height,
+            transaction_count: tx_count,
+            blockhash,
+            laserstream_created_time: None,
+            network_latency_ms: None,
+            propagation_latency_ms: block_time.map(|bt| received_time - (bt * 1000)),
+            // Fetched with rewards: false
+            reward_count: None,
+            total_fees_lamports: None,
+            reward_lamports: None,
+        };
+        Ok((Some(block_info), transfers))
+    } else {
+        Ok((None, transfers))
+    }
+}
+
+/// Average getBlock call split into time to first byte, download, gzip
+/// decode and JSON parse, per encoding. A provider slow to respond shows a
+/// high TTFB; one returning huge blocks shows in download and parse.
+fn print_compression_summary(transfers: &TransferLog, compression: &str) {
+    if transfers.plain.is_empty() && transfers.gzip.is_empty() {
+        return;
+    }
+
+    println!();
+    if compression == "off" {
+        println!("📦 getBlock transfer timing");
+    } else {
+        println!("🗜️  getBlock transfer by encoding");
+    }
+    println!(
+        "{:<8} | {:>6} | {:>10} | {:>10} | {:>8} | {:>8} | {:>8} | {:>8} | {:>8}",
+        "Encoding", "Calls", "Wire B", "Body B", "TTFB", "Download", "Decode", "Parse", "Total"
+    );
+
+    let mut avg_total = Vec::new();
+    for (name, log) in [("identity", &transfers.plain), ("gzip", &transfers.gzip)] {
+        if log.is_empty() {
+            continue;
+        }
+        let n = log.len() as f64;
+        let avg = |f: fn(&Transfer) -> f64| log.iter().map(f).sum::<f64>() / n;
+        let total = avg(|t| t.total_ms);
+
+        println!(
+            "{:<8} | {:>6} | {:>10.0} | {:>10.0} | {:>6.1}ms | {:>6.1}ms | {:>6.1}ms | {:>6.1}ms | {:>6.1}ms",
+            name,
+            log.len(),
+            avg(|t| t.wire_bytes as f64),
+            avg(|t| t.body_bytes as f64),
+            avg(|t| t.headers_ms),
+            avg(|t| t.download_ms),
+            avg(|t| t.decode_ms),
+            avg(|t| t.parse_ms),
+            total
+        );
+        avg_total.push(total);
+    }
+
+    let uncompressed = transfers.gzip.iter().filter(|t| !t.compressed).count();
+    if uncompressed > 0 {
+        println!(
+            "⚠️  {}/{} gzip requests were answered uncompressed",
+            uncompressed,
+            transfers.gzip.len()
+        );
+    }
+    if let [plain, gzip] = avg_total[..] {
+        println!(
+            "• gzip changed average getBlock call time by {:+.1}ms ({:+.1}%)",
+            gzip - plain,
+            (gzip - plain) / plain * 100.0
+        );
+    }
+}
+
+/// `--replay`: the final summary of a run, from the blocks it printed with
+/// `--jsonl`. Every line must carry the current [`report::SCHEMA_VERSION`].
+fn replay(path: &str, args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Cannot read replay file {}: {}", path, e))?;
+
+    let mut blocks = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_number = index + 1;
+        let record: Value = serde_json::from_str(line)
+            .map_err(|e| anyhow::anyhow!("{}:{}: not JSON: {}", path, line_number, e))?;
+
+        report::check_schema_version(&record)
+            .map_err(|e| anyhow::anyhow!("{}:{}: {}", path, line_number, e))?;
+
+        let block: BlockInfo = serde_json::from_value(record)
+            .map_err(|e| anyhow::anyhow!("{}:{}: {}", path, line_number, e))?;
+        blocks.push(block);
+    }
+    blocks.sort_by_key(|b| b.slot);
+
+    println!("🔁 Replaying {} blocks from {}", blocks.len(), path);
+    println!();
+    print_block_comparison(&blocks, args);
+
+    Ok(())
+}
+
+/// Ring-buffer push: keep at most `limit` blocks, evicting the oldest.
+fn retain_block(blocks: &mut VecDeque<BlockInfo>, block: BlockInfo, limit: Option<usize>) {
+    if let Some(limit) = limit {
+        while blocks.len() >= limit.max(1) {
+            blocks.pop_front();
+        }
+    }
+    blocks.push_back(block);
+}
+
+/// One table row per block, or one compact JSON object with `--jsonl`.
+fn log_block_info(block: &BlockInfo, verbose: bool, jsonl: bool) {
+    if jsonl {
+        let mut record = json!(block);
+        record["schema_version"] = json!(report::SCHEMA_VERSION);
+        match serde_json::to_string(&record) {
+            Ok(line) => println!("{}", line),
+            Err(e) => eprintln!("❌ Cannot serialize slot {}: {}", block.slot, e),
+        }
+        return;
+    }
+
+    let propagation_latency = block
+        .propagation_latency_ms
+        .map(|l| format!("{}ms", l))
+        .unwrap_or_else(|| "N/A".to_string());
+
+    let network_latency = block
+        .network_latency_ms
+        .map(|l| format!("{}ms", l))
+        .unwrap_or_else(|| "N/A".to_string());
+
+    let block_time_str = block
+        .block_time
+        .map(|bt| format!("{}", bt))
+        .unwrap_or_else(|| "N/A".to_string());
+
+    let parent_str = block
+        .parent_slot
+        .map(|p| format!("{}", p))
+        .unwrap_or_else(|| "N/A".to_string());
+
+    let height_str = block
+        .block_height
+        .map(|h| format!("{}", h))
+        .unwrap_or_else(|| "N/A".to_string());
+
+    let tx_str = block
+        .transaction_count
+        .map(|c| format!("{}", c))
+        .unwrap_or_else(|| "N/A".to_string());
+
+    if block.source == "LASERSTREAM" {
+        println!(
+            "{:<10} | {:<8} | {:<10} | {:<8} | Net:{:<6} | Prop:{:<6} | {:<6} | {:<6} | {:<3}",
+            block.source,
+            block.slot,
+            block_time_str,
+            block.received_time / 1000,
+            network_latency,
+            propagation_latency,
+            parent_str,
+            height_str,
+            tx_str
+        );
+    } else {
+        println!(
+            "{:<10} | {:<8} | {:<10} | {:<8} | {:<13} | Prop:{:<6} | {:<6} | {:<6} | {:<3}",
+            block.source,
+            block.slot,
+            block_time_str,
+            block.received_time / 1000,
+            "N/A",
+            propagation_latency,
+            parent_str,
+            height_str,
+            tx_str
+        );
+    }
+
+    if verbose {
+        println!("  📋 Block Details:");
+        println!("     Slot: {}", block.slot);
+        println!("     Block Time: {:?}", block.block_time);
+        println!("     Received Time: {}", block.received_time);
+        println!("     Parent Slot: {:?}", block.parent_slot);
+        println!("     Block Height: {:?}", block.block_height);
+        println!("     Transaction Count: {:?}", block.transaction_count);
+        println!("     Source: {}", block.source);
+        println!();
+    }
+}
+
+fn print_block_comparison(blocks: &[BlockInfo], args: &Args) {
+    let laserstream_blocks: Vec<_> = blocks
+        .iter()
+        .filter(|b| b.source == "LASERSTREAM")
+        .collect();
+    let rpc_blocks: Vec<_> = blocks.iter().filter(|b| b.source == "RPC").collect();
+
+    println!("{}", "=".repeat(60));
+    println!("📊 Block Reception Comparison");
+    println!("{}", "=".repeat(60));
+    println!("Laserstream blocks received: {}", laserstream_blocks.len());
+    println!("RPC blocks received: {}", rpc_blocks.len());
+    println!();
+
+    if !laserstream_blocks.is_empty() {
+        let network_latencies: Vec<i64> = laserstream_blocks
+            .iter()
+            .filter_map(|b| b.network_latency_ms)
+            .collect();
+
+        let propagation_latencies: Vec<i64> = laserstream_blocks
+            .iter()
+            .filter_map(|b| b.propagation_latency_ms)
+            .collect();
+
+        // Blocks without created_at have no network latency at all; they are
+        // counted here rather than averaged in as zeros
+        let missing_created_at = laserstream_blocks.len() - network_latencies.len();
+
+        if !network_latencies.is_empty() {
+            let avg_network =
+                network_latencies.iter().sum::<i64>() as f64 / network_latencies.len() as f64;
+            println!(
+                "⚡ Laserstream Average Network Latency: {:.1}ms ({} blocks)",
+                avg_network,
+                network_latencies.len()
+            );
+
+            if let Some(skew) = ClockSkew::detect(&network_latencies) {
+                skew.print();
+            }
+        }
+
+        if missing_created_at > 0 {
+            let missing_share = missing_created_at as f64 / laserstream_blocks.len() as f64;
+            println!(
+                "ℹ️  {}/{} Laserstream blocks had no created_at and are excluded from network latency",
+                missing_created_at,
+                laserstream_blocks.len()
+            );
+            if missing_share > MAX_MISSING_CREATED_AT_SHARE {
+                println!(
+                    "⚠️  {:.0}% of blocks lack created_at - the created_at methodology may not apply to this endpoint",
+                    missing_share * 100.0
+                );
+            }
+        }
+
+        if !propagation_latencies.is_empty() {
+            let avg_propagation = propagation_latencies.iter().sum::<i64>() as f64
+                / propagation_latencies.len() as f64;
+            println!(
+                "📡 Laserstream Average Propagation Latency: {:.1}ms",
+                avg_propagation
+            );
+        }
+    }
+
+    if !rpc_blocks.is_empty() {
+        let rpc_propagation_latencies: Vec<i64> = rpc_blocks
+            .iter()
+            .filter_map(|b| b.propagation_latency_ms)
+            .collect();
+
+        if !rpc_propagation_latencies.is_empty() {
+            let avg_rpc = rpc_propagation_latencies.iter().sum::<i64>() as f64
+                / rpc_propagation_latencies.len() as f64;
+            println!("🌐 RPC Average Propagation Latency: {:.1}ms", avg_rpc);
+        }
+    }
+
+    report_slot_coverage(&laserstream_blocks, &rpc_blocks, args.max_listed_missed);
+
+    // Latency deltas only make sense for the same block, so pairing is by
+    // exact slot; coverage above accounts for the slots left unpaired
+    let rpc_by_slot: HashMap<u64, &&BlockInfo> = rpc_blocks.iter().map(|b| (b.slot, b)).collect();
+    let common_slots: Vec<_> = laserstream_blocks
+        .iter()
+        .filter_map(|ls_block| Some((ls_block, *rpc_by_slot.get(&ls_block.slot)?)))
+        .collect();
+
+    if !common_slots.is_empty() {
+        println!();
+        println!("🔄 Common Slots (Direct Comparison):");
+        println!("Slot      | Laserstream        | RPC      | Prop Diff");
+        println!("{}", "-".repeat(55));
+
+        for (ls, rpc) in common_slots.iter().take(10) {
+            let ls_network = ls
+                .network_latency_ms
+                .map(|l| l.to_string())
+                .unwrap_or_else(|| "N/A".to_string());
+            let ls_propagation = ls.propagation_latency_ms.unwrap_or(0);
+            let rpc_propagation = rpc.propagation_latency_ms.unwrap_or(0);
+            let propagation_diff = rpc_propagation - ls_propagation;
+
+            println!(
+                "{:<8} | Net:{:<4}ms Prop:{:<4}ms | Prop:{:<4}ms | {:+}ms",
+                ls.slot, ls_network, ls_propagation, rpc_propagation, propagation_diff
+            );
+        }
+    }
+
+    println!();
+    println!("🕰️  Timestamp Sanity (block_time vs local clock):");
+    let ls_propagation: Vec<i64> = laserstream_blocks
+        .iter()
+        .filter_map(|b| b.propagation_latency_ms)
+        .collect();
+    let rpc_propagation: Vec<i64> = rpc_blocks
+        .iter()
+        .filter_map(|b| b.propagation_latency_ms)
+        .collect();
+    check_timestamp_plausibility("Laserstream", &ls_propagation);
+    check_timestamp_plausibility("RPC", &rpc_propagation);
+    check_block_time_agreement(&common_slots);
+    report_systematic_bias(&common_slots);
+
+    println!();
+    println!("🔗 Blockhash Agreement:");
+    check_blockhash_agreement(&common_slots);
+
+    // Every slot either source delivered was there to be delivered
+    let expected: HashSet<u64> = laserstream_blocks
+        .iter()
+        .chain(rpc_blocks.iter())
+        .map(|b| b.slot)
+        .collect();
+    let stats = compare_feed_latencies(
+        &[("Laserstream", ls_propagation), ("RPC", rpc_propagation)],
+        expected.len(),
+        &args.score_weights,
+    );
+    if !stats.is_empty() {
+        comparison::print_ranking(&stats);
+    }
+    if let Some(path) = &args.comparison_report {
+        match write_comparison_report(&stats, args.comparison_format, path) {
+            Ok(()) => println!("📄 Comparison report written to {}", path.display()),
+            Err(e) => eprintln!("❌ Cannot write {}: {}", path.display(), e),
+        }
+    }
+
+    print_rewards_summary(&laserstream_blocks);
+
+    if args.breakdown {
+        let mut ls_breakdown = LatencyBreakdown::default();
+        for b in &laserstream_blocks {
+            ls_breakdown.record(b.block_time, b.laserstream_created_time, b.received_time);
+        }
+        ls_breakdown.print("Laserstream");
+        println!("• RPC responses carry no created_at, so only its total latency is reported");
+    }
+
+    println!();
+    println!("💡 Key Insights:");
+    println!("• Network Latency: Time from Laserstream server to client (gRPC delivery speed)");
+    println!("• Propagation Latency: Time from block creation to receipt (validator→client)");
+    println!("• RPC uses HTTP polling - only propagation latency measured");
+    println!("• Negative propagation diff = Laserstream receives blocks faster");
+}
+
+/// Lamports of reward entries given as `(reward_type, lamports)`, summed per
+/// `RewardType`.
+fn sum_rewards(entries: impl Iterator<Item = (i32, i64)>) -> RewardLamports {
+    let mut sums = RewardLamports::default();
+    for (reward_type, lamports) in entries {
+        let sum = match RewardType::try_from(reward_type) {
+            Ok(RewardType::Fee) => &mut sums.fee,
+            Ok(RewardType::Rent) => &mut sums.rent,
+            Ok(RewardType::Staking) => &mut sums.staking,
+            Ok(RewardType::Voting) => &mut sums.voting,
+            Ok(RewardType::Unspecified) | Err(_) => &mut sums.unspecified,
+        };
+        *sum += lamports;
+    }
+    sums
+}
+
+/// Per-block averages of the Laserstream rewards. Blocks that arrived
+/// without rewards are counted, not averaged in as zero.
+fn print_rewards_summary(laserstream_blocks: &[&BlockInfo]) {
+    let rewarded: Vec<(usize, RewardLamports)> = laserstream_blocks
+        .iter()
+        .filter_map(|b| Some((b.reward_count?, b.reward_lamports?)))
+        .collect();
+
+    println!();
+    println!("💰 Block Rewards (Laserstream):");
+    if rewarded.is_empty() {
+        println!("• No block carried rewards");
+        return;
+    }
+
+    let blocks = rewarded.len() as f64;
+    let avg = |lamports: fn(&RewardLamports) -> i64| {
+        rewarded.iter().map(|(_, sums)| lamports(sums)).sum::<i64>() as f64 / blocks
+    };
+    let avg_fees = avg(|sums| sums.fee);
+    println!(
+        "Blocks with rewards: {}/{}",
+        rewarded.len(),
+        laserstream_blocks.len()
+    );
+    println!(
+        "Avg reward entries per block: {:.1}",
+        rewarded.iter().map(|(count, _)| *count).sum::<usize>() as f64 / blocks
+    );
+    println!(
+        "Avg fee rewards per block: {:.0} lamports ({:.6} SOL)",
+        avg_fees,
+        avg_fees / LAMPORTS_PER_SOL
+    );
+    println!(
+        "Avg per block by type: fee {:.0} | rent {:.0} | staking {:.0} | voting {:.0} | unspecified {:.0} lamports",
+        avg_fees,
+        avg(|sums| sums.rent),
+        avg(|sums| sums.staking),
+        avg(|sums| sums.voting),
+        avg(|sums| sums.unspecified)
+    );
+}
+
+/// How many of the Laserstream slots RPC also delivered, over the slot range
+/// both sources were running for, and which ones it never fetched. A polling
+/// RPC falling behind shows up here rather than in the latency deltas.
+fn report_slot_coverage(
+    laserstream_blocks: &[&BlockInfo],
+    rpc_blocks: &[&BlockInfo],
+    max_listed: usize,
+) {
+    let bounds = |blocks: &[&BlockInfo]| {
+        Some((
+            blocks.iter().map(|b| b.slot).min()?,
+            blocks.iter().map(|b| b.slot).max()?,
+        ))
+    };
+    let (Some((ls_first, ls_last)), Some((rpc_first, rpc_last))) =
+        (bounds(laserstream_blocks), bounds(rpc_blocks))
+    else {
+        return;
+    };
+    let (first, last) = (ls_first.max(rpc_first), ls_last.min(rpc_last));
+
+    println!();
+    println!(
+        "🧩 Slot Coverage (slots {}-{}, both sources running):",
+        first, last
+    );
+    if first > last {
+        println!("• The sources never overlapped");
+        return;
+    }
+
+    let in_window = |b: &&&BlockInfo| (first..=last).contains(&b.slot);
+    let ls_slots: HashSet<u64> = laserstream_blocks
+        .iter()
+        .filter(in_window)
+        .map(|b| b.slot)
+        .collect();
+    let rpc_slots: HashSet<u64> = rpc_blocks
+        .iter()
+        .filter(in_window)
+        .map(|b| b.slot)
+        .collect();
+    let mut missed: Vec<u64> = ls_slots.difference(&rpc_slots).copied().collect();
+    missed.sort_unstable();
+    let common = ls_slots.len() - missed.len();
+    let rpc_only = rpc_slots.len() - common;
+
+    println!(
+        "RPC delivered {}/{} Laserstream slots ({:.1}%)",
+        common,
+        ls_slots.len(),
+        common as f64 / ls_slots.len().max(1) as f64 * 100.0
+    );
+    if rpc_only > 0 {
+        println!("RPC-only slots: {}", rpc_only);
+    }
+    println!("Latency deltas below use only the {} common slots", common);
+
+    if missed.is_empty() {
+        return;
+    }
+    let mut ranges: Vec<(u64, u64)> = Vec::new();
+    for slot in missed {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == slot => *end = slot,
+            _ => ranges.push((slot, slot)),
+        }
+    }
+    println!(
+        "Laserstream slots RPC never delivered ({} ranges):",
+        ranges.len()
+    );
+    for (start, end) in ranges.iter().take(max_listed) {
+        if start == end {
+            println!("   {}", start);
+        } else {
+            println!("   {}-{} ({} slots)", start, end, end - start + 1);
+        }
+    }
+    if ranges.len() > max_listed {
+        println!("   ... and {} more", ranges.len() - max_listed);
+    }
+}
+
+/// Decide whether one source is consistently ahead of the other across the
+/// run, or whether the per-slot differences are just jitter around zero.
+fn report_systematic_bias(common_slots: &[(&&BlockInfo, &&BlockInfo)]) {
+    // Positive = Laserstream received the slot earlier than RPC
+    let diffs: Vec<f64> = common_slots
+        .iter()
+        .filter_map(|(ls, rpc)| {
+            Some((rpc.propagation_latency_ms? - ls.propagation_latency_ms?) as f64)
+        })
+        .collect();
+
+    println!();
+    println!("⚖️  Systematic Bias (RPC − Laserstream per common slot):");
+
+    if diffs.len() < 2 {
+        println!("• Not enough common slots to test for bias");
+        return;
+    }
+
+    let n = diffs.len() as f64;
+    let mean = diffs.iter().sum::<f64>() / n;
+    let variance = diffs.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    let margin = BIAS_Z * (variance / n).sqrt();
+
+    if diffs.len() >= BIAS_WINDOW {
+        let rolling: Vec<f64> = diffs
+            .windows(BIAS_WINDOW)
+            .map(|w| w.iter().sum::<f64>() / BIAS_WINDOW as f64)
+            .collect();
+        let min = rolling.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = rolling.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let same_sign = rolling
+            .iter()
+            .filter(|m| m.signum() == mean.signum())
+            .count();
+        println!(
+            "• Rolling {}-slot mean: {:+.0}ms to {:+.0}ms, {}/{} windows on the overall side",
+            BIAS_WINDOW,
+            min,
+            max,
+            same_sign,
+            rolling.len()
+        );
+    }
+
+    if mean.abs() > margin {
+        let leader = if mean > 0.0 {
+            "Laserstream leads RPC"
+        } else {
+            "RPC leads Laserstream"
+        };
+        println!(
+            "✅ {} by {:.0}±{:.0} ms (systematic, 95% CI over {} slots)",
+            leader,
+            mean.abs(),
+            margin,
+            diffs.len()
+        );
+    } else {
+        println!(
+            "• No systematic difference: {:+.0}±{:.0} ms over {} slots is within jitter",
+            mean,
+            margin,
+            diffs.len()
+        );
+    }
+}
+
+/// Both sources read block_time from the same bank, so any difference for a
+/// common slot is a data bug in one of them.
+fn check_block_time_agreement(common_slots: &[(&&BlockInfo, &&BlockInfo)]) {
+    let compared: Vec<(u64, i64, i64)> = common_slots
+        .iter()
+        .filter_map(|(ls, rpc)| Some((ls.slot, ls.block_time?, rpc.block_time?)))
+        .collect();
+
+    if compared.is_empty() {
+        println!("• block_time cross-check: no common slots with block_time from both sources");
+        return;
+    }
+
+    let mismatches: Vec<_> = compared
+        .iter()
+        .filter(|(_, ls_time, rpc_time)| ls_time != rpc_time)
+        .collect();
+
+    if mismatches.is_empty() {
+        println!(
+            "✅ block_time cross-check: Laserstream and RPC agree on all {} common slots",
+            compared.len()
+        );
+        return;
+    }
+
+    println!(
+        "⚠️  block_time cross-check: {}/{} common slots disagree",
+        mismatches.len(),
+        compared.len()
+    );
+    for (slot, ls_time, rpc_time) in mismatches.iter().take(MAX_LISTED_MISMATCHES) {
+        println!(
+            "   Slot {}: Laserstream {} vs RPC {} ({:+}s)",
+            slot,
+            ls_time,
+            rpc_time,
+            ls_time - rpc_time
+        );
+    }
+    if mismatches.len() > MAX_LISTED_MISMATCHES {
+        println!(
+            "   ... and {} more",
+            mismatches.len() - MAX_LISTED_MISMATCHES
+        );
+    }
+}
+
+/// A common slot with two different blockhashes was a different block on
+/// each side: one source followed a fork the other did not, or served a
+/// stale read.
+fn check_blockhash_agreement(common_slots: &[(&&BlockInfo, &&BlockInfo)]) {
+    let compared: Vec<(u64, &str, &str)> = common_slots
+        .iter()
+        .filter_map(|(ls, rpc)| {
+            Some((ls.slot, ls.blockhash.as_deref()?, rpc.blockhash.as_deref()?))
+        })
+        .collect();
+
+    if compared.is_empty() {
+        println!("• blockhash cross-check: no common slots with a blockhash from both sources");
+        return;
+    }
+
+    let mismatches: Vec<_> = compared
+        .iter()
+        .filter(|(_, ls_hash, rpc_hash)| ls_hash != rpc_hash)
+        .collect();
+
+    if mismatches.is_empty() {
+        println!(
+            "✅ blockhash cross-check: Laserstream and RPC agree on all {} common slots",
+            compared.len()
+        );
+        return;
+    }
+
+    println!(
+        "⚠️  blockhash cross-check: {}/{} common slots disagree (fork or stale read)",
+        mismatches.len(),
+        compared.len()
+    );
+    for (slot, ls_hash, rpc_hash) in mismatches.iter().take(MAX_LISTED_MISMATCHES) {
+        println!(
+            "   Slot {}: Laserstream {} vs RPC {}",
+            slot, ls_hash, rpc_hash
+        );
+    }
+    if mismatches.len() > MAX_LISTED_MISMATCHES {
+        println!(
+            "   ... and {} more",
+            mismatches.len() - MAX_LISTED_MISMATCHES
+        );
+    }
+}
+
+/// Flag a source whose block_time-derived latencies are implausible, which
+/// usually means clock skew on its side or cached/misreported block_time.
+fn check_timestamp_plausibility(source: &str, latencies: &[i64]) {
+    let mut sorted = latencies.to_vec();
+    sorted.sort();
+    let Some(median) = stats::percentile(&sorted, 0.5) else {
+        println!("• {}: no samples", source);
+        return;
+    };
+    let negative = sorted.iter().filter(|&&l| l < 0).count();
+    let negative_share = negative as f64 / sorted.len() as f64;
+
+    if median < MIN_PLAUSIBLE_MEDIAN_MS || negative_share > MAX_NEGATIVE_SHARE {
+        println!(
+            "⚠️  {}: median {}ms, {}/{} negative ({:.1}%) - possibly clock-skewed or caching; don't trust its latency",
+            source,
+            median,
+            negative,
+            sorted.len(),
+            negative_share * 100.0
+        );
+    } else {
+        println!(
+            "✅ {}: median {}ms, {}/{} negative ({:.1}%)",
+            source,
+            median,
+            negative,
+            sorted.len(),
+            negative_share * 100.0
+        );
+    }
+}
@@ -0,0 +1,673 @@
+//! Transaction detection latency for a watched account (`signature_poll_latency`, `bench tx`).
+
+use crate::clock::{self, TimeSource};
+use crate::landing;
+use crate::logging::{self, LogFormat};
+use crate::pubkey;
+use crate::rpc::{self, Config, RPCConfig};
+use crate::shutdown::Shutdown;
+use crate::stats::{LatencyStats, Percentiles};
+use crate::submit;
+use crate::subscription;
+use anyhow::Result;
+use clap::Parser;
+use futures::StreamExt;
+use helius_laserstream::{
+    grpc::{
+        subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest,
+        SubscribeRequestFilterBlocksMeta, SubscribeRequestFilterTransactions,
+    },
+    LaserstreamConfig,
+};
+use reqwest::Client;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::time;
+use tracing::debug;
+
+#[derive(Parser)]
+#[command(name = "signature-poll-latency")]
+#[command(
+    about = "Measure detection latency of getSignaturesForAddress polling (or a Laserstream transaction subscription) for a watched account"
+)]
+pub struct Args {
+    #[arg(
+        long,
+        value_parser = pubkey::parse_pubkey,
+        required_unless_present_any = ["landing", "send"],
+        help = "Account address to watch (e.g. a DEX market)"
+    )]
+    address: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "../shared/config.json",
+        help = "Config file path"
+    )]
+    config: String,
+
+    #[arg(long, help = "RPC provider to test (helius, solana, etc)")]
+    provider: Option<String>,
+
+    #[arg(long, default_value = "2", help = "Test duration in minutes")]
+    duration: u64,
+
+    #[arg(long, default_value = "400", help = "Polling interval in milliseconds")]
+    interval_ms: u64,
+
+    #[arg(long, default_value = "25", help = "Signatures requested per poll")]
+    limit: u64,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "poll",
+        help = "Detection path: poll getSignaturesForAddress, or subscribe via Laserstream"
+    )]
+    method: Method,
+
+    #[arg(long, help = "Helius API key (--method grpc)")]
+    api_key: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "https://laserstream-mainnet-tyo.helius-rpc.com",
+        help = "Helius Laserstream endpoint (--method grpc)"
+    )]
+    endpoint: String,
+
+    #[arg(
+        long,
+        help = "Debug diagnostics on stderr (same as RUST_LOG=solana_rpc_performance_rust=debug)"
+    )]
+    verbose: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "text",
+        help = "Format of the diagnostics on stderr; RUST_LOG sets their levels"
+    )]
+    log_format: LogFormat,
+
+    #[arg(
+        long,
+        default_value = "clock_realtime",
+        value_name = "clock_realtime|ntp[:server]|file:<path>",
+        help = "Wall clock for latency math; ntp[:server] applies an offset measured once at startup, file:<path> the PTP/GPS offset (ms) read from that file"
+    )]
+    time_source: TimeSource,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Instead of watching --address, report the landing rate of the signatures in FILE (one per line, optionally `signature,priority_fee_lamports`)"
+    )]
+    landing: Option<String>,
+
+    #[arg(
+        long,
+        requires = "landing",
+        help = "Also group --landing time to confirm by priority fee; fees missing from FILE are read from the landed transactions"
+    )]
+    by_priority_fee: bool,
+
+    #[arg(
+        long,
+        default_value = "60",
+        help = "Seconds to wait for --landing signatures to finalize"
+    )]
+    landing_deadline_secs: u64,
+
+    #[arg(
+        long,
+        requires = "keypair",
+        conflicts_with = "landing",
+        help = "Instead of watching --address, submit one self-transfer signed by --keypair and time it to confirmed. Spends the transaction fee"
+    )]
+    send: bool,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        requires = "send",
+        help = "Keypair JSON file paying for --send"
+    )]
+    keypair: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "50,90,95,99",
+        value_name = "LIST",
+        help = "Percentiles to report, e.g. 50,90,99,99.9"
+    )]
+    percentiles: Percentiles,
+}
+
+impl Args {
+    /// Present unless running `--landing` or `--send`, which clap enforces.
+    fn address(&self) -> &str {
+        self.address.as_deref().unwrap_or_default()
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Method {
+    /// Poll getSignaturesForAddress every --interval-ms.
+    Poll,
+    /// Stream transactions touching the address; no polling delay or rate limit.
+    Grpc,
+}
+
+/// Streamed signatures whose slot has seen no block meta after this many
+/// slots are counted as missing a block time.
+const MAX_PENDING_SLOTS: u64 = 150;
+
+#[derive(Debug, Clone)]
+struct SignatureDetection {
+    signature: String,
+    slot: u64,
+    block_time: i64,
+    detected_time: i64,
+    latency_ms: i64,
+}
+
+#[derive(Debug, Default)]
+struct PollStats {
+    polls: u64,
+    failed_polls: u64,
+    /// Polls where every returned signature was new, so older ones may have been missed.
+    saturated_polls: u64,
+    /// New signatures that came back without a blockTime.
+    missing_block_time: u64,
+    call_times_ms: Vec<f64>,
+    /// `--method grpc` only: transaction updates received.
+    stream_updates: u64,
+    /// `--method grpc` only.
+    stream_errors: u64,
+}
+
+pub async fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    logging::init(args.log_format, args.verbose, false);
+    clock::init(&args.time_source)?;
+
+    if args.method == Method::Grpc {
+        println!("🔎 Laserstream Transaction Detection Latency");
+        println!("Address: {}", args.address());
+        println!("Duration: {} minutes", args.duration);
+        println!("Endpoint: {}", args.endpoint);
+        println!();
+
+        let api_key = args
+            .api_key
+            .clone()
+            .or_else(|| std::env::var("HELIUS_API_KEY").ok())
+            .unwrap_or_else(|| "9de07723-0030-4ee0-b175-6722231d5d97".to_string());
+
+        let (detections, stats) = monitor_stream(api_key, &args).await?;
+        print_detection_results(&detections, &stats, &args);
+        return Ok(());
+    }
+
+    if let Some(path) = &args.landing {
+        return run_landing(path, &args).await;
+    }
+    if let Some(path) = &args.keypair {
+        return run_send(path, &args).await;
+    }
+
+    println!("🔎 getSignaturesForAddress Polling Latency");
+    println!("Address: {}", args.address());
+    println!("Duration: {} minutes", args.duration);
+    println!(
+        "Poll interval: {}ms (limit {})",
+        args.interval_ms, args.limit
+    );
+    println!();
+
+    let config = rpc::load_config(&args.config)?;
+    let rpc = select_rpc(&config, args.provider.as_deref())?;
+
+    println!("🌐 RPC Provider: {} ({})", rpc.name, rpc.provider);
+    println!("🔗 RPC URL: {}", rpc.url);
+    println!();
+
+    let (detections, stats) = monitor_signatures(rpc.clone(), &args).await?;
+
+    print_detection_results(&detections, &stats, &args);
+
+    Ok(())
+}
+
+fn select_rpc<'a>(config: &'a Config, provider: Option<&str>) -> Result<&'a RPCConfig> {
+    if let Some(provider) = provider {
+        config
+            .rpcs
+            .values()
+            .find(|r| {
+                r.provider.to_lowercase().contains(&provider.to_lowercase()) && r.status == "active"
+            })
+            .ok_or_else(|| anyhow::anyhow!("No active RPC found for provider: {}", provider))
+    } else {
+        config
+            .rpcs
+            .values()
+            .find(|r| r.provider == "Helius" && r.status == "active")
+            .or_else(|| config.rpcs.values().find(|r| r.status == "active"))
+            .ok_or_else(|| anyhow::anyhow!("No active RPCs found"))
+    }
+}
+
+/// `--landing`: final status of already-submitted transactions.
+async fn run_landing(path: &str, args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let mut submitted = Vec::new();
+    for line in std::fs::read_to_string(path)?.lines().map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+        let (signature, fee) =
+            match line.split_once(',') {
+                Some((signature, fee)) => (
+                    signature.trim(),
+                    Some(fee.trim().parse::<u64>().map_err(|e| {
+                        anyhow::anyhow!("Invalid priority fee in '{}': {}", line, e)
+                    })?),
+                ),
+                None => (line, None),
+            };
+        submitted.push(landing::Submitted {
+            signature: signature.to_string(),
+            priority_fee_lamports: fee,
+        });
+    }
+
+    let config = rpc::load_config(&args.config)?;
+    let rpc = select_rpc(&config, args.provider.as_deref())?;
+
+    println!("🛬 Transaction Landing Rate");
+    println!("Signatures: {} (from {})", submitted.len(), path);
+    println!("Deadline: {}s", args.landing_deadline_secs);
+    println!("🌐 RPC Provider: {} ({})", rpc.name, rpc.provider);
+    println!("🔗 RPC URL: {}", rpc.url);
+
+    let client = Client::builder().timeout(Duration::from_secs(5)).build()?;
+    let deadline = Duration::from_secs(args.landing_deadline_secs);
+    if args.by_priority_fee {
+        let (stats, buckets) =
+            landing::benchmark_fee_landing(&client, &rpc.url, submitted, deadline).await?;
+        stats.print();
+        landing::print_fee_buckets(&buckets);
+    } else {
+        let signatures = submitted.into_iter().map(|s| s.signature).collect();
+        let stats =
+            landing::benchmark_landing_rate(&client, &rpc.url, signatures, deadline).await?;
+        stats.print();
+    }
+
+    Ok(())
+}
+
+/// `--send`: round trip of a transaction submitted here.
+async fn run_send(keypair_path: &str, args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let keypair = solana_sdk::signature::read_keypair_file(keypair_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read keypair '{}': {}", keypair_path, e))?;
+
+    let config = rpc::load_config(&args.config)?;
+    let rpc = select_rpc(&config, args.provider.as_deref())?;
+
+    println!("🚀 Transaction Round Trip");
+    println!("Payer: {}", solana_sdk::signer::Signer::pubkey(&keypair));
+    println!("Deadline: {}s", args.landing_deadline_secs);
+    println!("🌐 RPC Provider: {} ({})", rpc.name, rpc.provider);
+    println!("🔗 RPC URL: {}", rpc.url);
+
+    let deadline = Duration::from_secs(args.landing_deadline_secs);
+    let speed = submit::submit_and_time(&keypair, &rpc.url, deadline).await?;
+    speed.print();
+
+    Ok(())
+}
+
+async fn monitor_signatures(
+    rpc: RPCConfig,
+    args: &Args,
+) -> Result<(Vec<SignatureDetection>, PollStats)> {
+    let client = Client::builder().timeout(Duration::from_secs(5)).build()?;
+
+    let mut detections = Vec::new();
+    let mut stats = PollStats::default();
+    let start_time = SystemTime::now();
+    let duration = Duration::from_secs(args.duration * 60);
+    let mut shutdown = Shutdown::install();
+
+    // Everything already on-chain at start is history, not a detection
+    let mut seen: HashSet<String> = get_signatures(&client, &rpc.url, args.address(), args.limit)
+        .await?
+        .into_iter()
+        .map(|s| s.signature)
+        .collect();
+
+    println!(
+        "🚀 Watching for new signatures ({} already known)...",
+        seen.len()
+    );
+    println!();
+    println!("Slot       | Signature    | Block Time   | Latency   ");
+    println!("{}", "-".repeat(55));
+
+    while start_time.elapsed()? < duration {
+        let call_start = Instant::now();
+        let result = get_signatures(&client, &rpc.url, args.address(), args.limit).await;
+        let detected_time = clock::now_ms();
+        stats.polls += 1;
+
+        match result {
+            Ok(entries) => {
+                stats
+                    .call_times_ms
+                    .push(call_start.elapsed().as_secs_f64() * 1000.0);
+
+                let returned = entries.len();
+                let mut new_count = 0;
+
+                // Oldest first so rows print in chain order
+                for entry in entries.into_iter().rev() {
+                    if !seen.insert(entry.signature.clone()) {
+                        continue;
+                    }
+                    new_count += 1;
+
+                    match entry.block_time {
+                        Some(block_time) => {
+                            let detection = SignatureDetection {
+                                signature: entry.signature,
+                                slot: entry.slot,
+                                block_time,
+                                detected_time,
+                                latency_ms: detected_time - (block_time * 1000),
+                            };
+                            log_detection(&detection);
+                            detections.push(detection);
+                        }
+                        None => stats.missing_block_time += 1,
+                    }
+                }
+
+                if returned > 0 && new_count == returned && stats.polls > 1 {
+                    stats.saturated_polls += 1;
+                    debug!("All {} returned signatures were new - raise --limit or lower --interval-ms", returned);
+                }
+            }
+            Err(e) => {
+                stats.failed_polls += 1;
+                debug!("getSignaturesForAddress error: {}", e);
+            }
+        }
+
+        tokio::select! {
+            _ = shutdown.wait() => break,
+            _ = time::sleep(Duration::from_millis(args.interval_ms)) => {}
+        }
+    }
+
+    Ok((detections, stats))
+}
+
+/// Same detections as [`monitor_signatures`], timed at the moment the
+/// confirmed transaction arrives on the stream. Block times come from block
+/// meta updates on the same subscription, which may arrive on either side of
+/// the transactions.
+async fn monitor_stream(
+    api_key: String,
+    args: &Args,
+) -> Result<(Vec<SignatureDetection>, PollStats)> {
+    let config = LaserstreamConfig {
+        api_key,
+        endpoint: args.endpoint.parse()?,
+        ..Default::default()
+    };
+
+    let mut transaction_filters = HashMap::new();
+    transaction_filters.insert(
+        "watched_address".to_string(),
+        SubscribeRequestFilterTransactions {
+            vote: Some(false),
+            failed: None,
+            signature: None,
+            account_include: vec![args.address().to_string()],
+            account_exclude: vec![],
+            account_required: vec![],
+        },
+    );
+    let mut blocks_meta_filters = HashMap::new();
+    blocks_meta_filters.insert(
+        "block_times".to_string(),
+        SubscribeRequestFilterBlocksMeta {},
+    );
+
+    // Confirmed, to match what getSignaturesForAddress can see
+    let request = SubscribeRequest {
+        transactions: transaction_filters,
+        blocks_meta: blocks_meta_filters,
+        commitment: Some(CommitmentLevel::Confirmed as i32),
+        ..Default::default()
+    };
+
+    let (stream, subscription) = subscription::subscribe(config, request);
+    futures::pin_mut!(stream);
+
+    let mut detections = Vec::new();
+    let mut stats = PollStats::default();
+    let mut seen = HashSet::new();
+    let mut block_times: HashMap<u64, i64> = HashMap::new();
+    // slot -> (signature, detected_time) waiting for the slot's block time
+    let mut pending: HashMap<u64, Vec<(String, i64)>> = HashMap::new();
+    let start_time = SystemTime::now();
+    let duration = Duration::from_secs(args.duration * 60);
+    let mut shutdown = Shutdown::install();
+
+    println!("🚀 Watching for new signatures...");
+    println!();
+    println!("Slot       | Signature    | Block Time   | Latency   ");
+    println!("{}", "-".repeat(55));
+
+    while start_time.elapsed()? < duration {
+        let next = tokio::select! {
+            _ = shutdown.wait() => break,
+            next = stream.next() => next,
+        };
+        let Some(result) = next else {
+            break;
+        };
+        let detected_time = clock::now_ms();
+
+        match result {
+            Ok(update) => match update.update_oneof {
+                Some(UpdateOneof::Transaction(tx_update)) => {
+                    let Some(info) = tx_update.transaction else {
+                        continue;
+                    };
+                    stats.stream_updates += 1;
+                    let signature = bs58::encode(&info.signature).into_string();
+                    if !seen.insert(signature.clone()) {
+                        continue;
+                    }
+
+                    match block_times.get(&tx_update.slot) {
+                        Some(&block_time) => {
+                            let detection = SignatureDetection {
+                                signature,
+                                slot: tx_update.slot,
+                                block_time,
+                                detected_time,
+                                latency_ms: detected_time - (block_time * 1000),
+                            };
+                            log_detection(&detection);
+                            detections.push(detection);
+                        }
+                        None => pending
+                            .entry(tx_update.slot)
+                            .or_default()
+                            .push((signature, detected_time)),
+                    }
+                }
+                Some(UpdateOneof::BlockMeta(meta)) => {
+                    let slot = meta.slot;
+                    let Some(block_time) = meta.block_time.map(|bt| bt.timestamp) else {
+                        stats.missing_block_time +=
+                            pending.remove(&slot).map_or(0, |txs| txs.len() as u64);
+                        continue;
+                    };
+
+                    block_times.insert(slot, block_time);
+                    block_times.retain(|&known, _| known + MAX_PENDING_SLOTS >= slot);
+
+                    for (signature, detected_time) in pending.remove(&slot).unwrap_or_default() {
+                        let detection = SignatureDetection {
+                            signature,
+                            slot,
+                            block_time,
+                            detected_time,
+                            latency_ms: detected_time - (block_time * 1000),
+                        };
+                        log_detection(&detection);
+                        detections.push(detection);
+                    }
+                    pending.retain(|&waiting, txs| {
+                        let keep = waiting + MAX_PENDING_SLOTS >= slot;
+                        if !keep {
+                            stats.missing_block_time += txs.len() as u64;
+                        }
+                        keep
+                    });
+                }
+                _ => {}
+            },
+            Err(e) => {
+                stats.stream_errors += 1;
+                debug!("Laserstream error: {}", e);
+            }
+        }
+    }
+
+    subscription.cancel();
+    subscription.health().print();
+    stats.missing_block_time += pending.values().map(|txs| txs.len() as u64).sum::<u64>();
+
+    Ok((detections, stats))
+}
+
+struct SignatureEntry {
+    signature: String,
+    slot: u64,
+    block_time: Option<i64>,
+}
+
+async fn get_signatures(
+    client: &Client,
+    rpc_url: &str,
+    address: &str,
+    limit: u64,
+) -> Result<Vec<SignatureEntry>> {
+    // getSignaturesForAddress does not accept processed commitment
+    let json_value = rpc::call(
+        client,
+        rpc_url,
+        "getSignaturesForAddress",
+        json!([address, {"limit": limit, "commitment": "confirmed"}]),
+    )
+    .await?;
+
+    if let Some(error) = json_value.get("error") {
+        return Err(anyhow::anyhow!("getSignaturesForAddress error: {}", error));
+    }
+
+    let entries = json_value
+        .get("result")
+        .and_then(|r| r.as_array())
+        .ok_or_else(|| anyhow::anyhow!("Failed to get signatures"))?;
+
+    Ok(entries
+        .iter()
+        .filter_map(|e| {
+            Some(SignatureEntry {
+                signature: e.get("signature")?.as_str()?.to_string(),
+                slot: e.get("slot")?.as_u64()?,
+                block_time: e.get("blockTime").and_then(|bt| bt.as_i64()),
+            })
+        })
+        .collect())
+}
+
+fn log_detection(detection: &SignatureDetection) {
+    let short_sig: String = detection.signature.chars().take(12).collect();
+    println!(
+        "{:<10} | {:<12} | {:<12} | {:<7}ms",
+        detection.slot, short_sig, detection.block_time, detection.latency_ms
+    );
+}
+
+fn print_detection_results(detections: &[SignatureDetection], stats: &PollStats, args: &Args) {
+    println!();
+    println!("📊 Signature Detection Latency Results");
+    println!("{}", "=".repeat(50));
+    if args.method == Method::Grpc {
+        println!("Stream updates:       {}", stats.stream_updates);
+        println!("Stream errors:        {}", stats.stream_errors);
+    } else {
+        println!("Polls:                {}", stats.polls);
+        println!("Failed polls:         {}", stats.failed_polls);
+        println!("Saturated polls:      {}", stats.saturated_polls);
+    }
+    println!("New signatures:       {}", detections.len());
+    println!("Missing blockTime:    {}", stats.missing_block_time);
+
+    let latencies: Vec<i64> = detections.iter().map(|d| d.latency_ms).collect();
+    if let Some(stats) = LatencyStats::from_samples(&latencies) {
+        println!();
+        println!("⏱️  Detection latency (block_time → detected):");
+        println!("Average:              {:.1}ms", stats.avg_ms);
+        println!("Min:                  {}ms", stats.min_ms);
+        println!("Max:                  {}ms", stats.max_ms);
+        println!("Std deviation:        {:.1}ms", stats.std_dev_ms);
+        println!("Jitter:               {:.1}ms", stats.jitter_ms);
+        for (label, value) in args.percentiles.of(&latencies) {
+            println!("{:<22}{}ms", format!("{}:", label), value);
+        }
+    } else {
+        println!();
+        println!("❌ No new signatures detected for {}", args.address());
+    }
+
+    if !stats.call_times_ms.is_empty() {
+        let avg_call = stats.call_times_ms.iter().sum::<f64>() / stats.call_times_ms.len() as f64;
+        let calls_per_detection = if detections.is_empty() {
+            stats.polls as f64
+        } else {
+            stats.polls as f64 / detections.len() as f64
+        };
+
+        println!();
+        println!("🌐 Polling overhead:");
+        println!("Avg call round trip:  {:.1}ms", avg_call);
+        println!(
+            "Expected poll delay:  {:.1}ms (half the {}ms interval + call time)",
+            args.interval_ms as f64 / 2.0 + avg_call,
+            args.interval_ms
+        );
+        println!("Calls per detection:  {:.2}", calls_per_detection);
+    }
+
+    println!();
+    println!("📋 Methodology:");
+    if args.method == Method::Grpc {
+        println!("• Subscribes to confirmed non-vote transactions touching the watched address");
+        println!("• A signature is detected when its transaction update arrives on the stream");
+        println!("• block_time comes from block meta updates on the same subscription");
+    } else {
+        println!("• Polls getSignaturesForAddress (confirmed) for the watched address");
+        println!("• A signature is detected the first time it appears in a poll");
+    }
+    println!("• Latency = detection_time - block_time (block_time has 1s resolution)");
+}
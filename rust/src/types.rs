@@ -0,0 +1,95 @@
+//! Per-block records of the measurement tools, shared so they can be dumped,
+//! stored and consumed outside the binaries.
+//!
+//! All times are unix milliseconds except `block_time`, which is unix
+//! seconds as returned by the validator. Field names are part of the
+//! `--jsonl` format and must stay stable.
+
+use serde::{Deserialize, Serialize};
+
+/// A block as seen by one source of `rpc_vs_laserstream_logger`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockInfo {
+    pub slot: u64,
+    /// Unix seconds; `None` when the source did not report it.
+    pub block_time: Option<i64>,
+    /// Unix ms at which the block was received.
+    pub received_time: i64,
+    /// `"LASERSTREAM"` or `"RPC"`.
+    pub source: String,
+    pub parent_slot: Option<u64>,
+    pub block_height: Option<u64>,
+    pub transaction_count: Option<usize>,
+    /// Base58 hash of the block. Two sources disagreeing on it for the same
+    /// slot saw different forks, or one served a stale block.
+    pub blockhash: Option<String>,
+    /// Unix ms at which Laserstream processed the block (its `created_at`);
+    /// Laserstream only.
+    pub laserstream_created_time: Option<i64>,
+    /// `received_time - laserstream_created_time` in ms: Laserstream
+    /// delivery speed.
+    pub network_latency_ms: Option<i64>,
+    /// `received_time - block_time * 1000` in ms: block creation to receipt.
+    pub propagation_latency_ms: Option<i64>,
+    /// Entries in the block's rewards; `None` when the source sent none
+    /// (RPC blocks are fetched without rewards).
+    pub reward_count: Option<usize>,
+    /// Lamports of the block's `Fee` rewards, the leader's share of the
+    /// transaction fees.
+    pub total_fees_lamports: Option<i64>,
+    /// Reward lamports summed per reward type.
+    pub reward_lamports: Option<RewardLamports>,
+}
+
+/// Lamports credited by one block's rewards, per reward type. Negative
+/// entries (e.g. rent debits) are summed as reported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardLamports {
+    pub fee: i64,
+    pub rent: i64,
+    pub staking: i64,
+    pub voting: i64,
+    /// Rewards without a known type.
+    pub unspecified: i64,
+}
+
+/// One source's arrival of a slot in `racing_comparison`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockEvent {
+    /// Unix ms at which the slot was received.
+    pub received_time: i64,
+    /// [`clock::monotonic_ms`](crate::clock::monotonic_ms) at receipt. Arrivals
+    /// from different sources are ordered and diffed on this, since
+    /// `received_time` can jump with an NTP adjustment.
+    pub arrival_ms: i64,
+    /// `received_time - block_time * 1000` in ms, when the block time is
+    /// known.
+    pub latency_ms: Option<i64>,
+}
+
+/// One block accepted by `laserstream_benchmark`.
+#[derive(Debug, Clone)]
+pub struct BlockLatencyData {
+    pub slot: u64,
+    /// Unix ms.
+    pub received_ms: i64,
+    /// `received_ms - block_time * 1000`, block_time being unix seconds.
+    pub propagation_latency_ms: i64,
+    /// Unix ms of the update's `created_at`, when it carried one.
+    pub created_at_ms: Option<i64>,
+    /// Executed transactions, reported by the block even without
+    /// `include_transactions`.
+    pub transaction_count: u64,
+}
+
+/// One new slot detected by `rpc_latency_test`.
+#[derive(Debug, Clone)]
+pub struct SlotLatency {
+    pub slot: u64,
+    /// Unix seconds, as returned by getBlockTime.
+    pub block_time: i64,
+    /// Unix ms.
+    pub detected_time: i64,
+    /// `detected_time - block_time * 1000`.
+    pub latency_ms: i64,
+}
@@ -0,0 +1,43 @@
+//! Cold-start exclusion for `--warmup <N>`.
+//!
+//! The first measurements after connecting carry the TLS handshake and
+//! stream spin-up. They are still printed live, marked as warmup, but never
+//! reach the samples the statistics are computed from.
+
+pub const WARMUP_LABEL: &str = "⏳ WARMUP";
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Warmup {
+    remaining: u64,
+    /// Measurements discarded so far.
+    pub discarded: u64,
+}
+
+impl Warmup {
+    pub fn new(samples: u64) -> Self {
+        Warmup {
+            remaining: samples,
+            discarded: 0,
+        }
+    }
+
+    /// Count one measurement; true while it falls in the warmup and must
+    /// be kept out of the statistics.
+    pub fn absorb(&mut self) -> bool {
+        if self.remaining == 0 {
+            return false;
+        }
+        self.remaining -= 1;
+        self.discarded += 1;
+        true
+    }
+
+    pub fn print_summary(&self) {
+        if self.discarded > 0 {
+            println!(
+                "⏳ {} warmup measurements excluded from the statistics",
+                self.discarded
+            );
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! The RPC latency path against a local mock RPC server, so it can run in CI
+//! without a provider.
+//!
+//! The mock answers `getSlot` with a slot that advances on every call and
+//! `getBlock` / `getBlockTime` with a canned block time. The clock is pinned
+//! through a `file:` time source whose offset puts "now" at [`FIXED_NOW_MS`],
+//! so the expected latency is known up front.
+
+use clap::Parser;
+use reqwest::Client;
+use serde_json::{json, Value};
+use solana_rpc_performance_rust::rpc::{self, Commitment};
+use solana_rpc_performance_rust::{run_websocket_benchmark, WebsocketBenchConfig};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+const FIRST_SLOT: u64 = 250_000_000;
+/// Unix seconds returned for every block.
+const BLOCK_TIME: i64 = 1_700_000_000;
+/// What the pinned clock reads when the run starts: 650ms after the block.
+const FIXED_NOW_MS: i64 = BLOCK_TIME * 1000 + 650;
+/// Wall time the run itself may add on top of [`FIXED_NOW_MS`].
+const RUN_SLACK_MS: i64 = 2_000;
+
+/// Start the mock on a free local port and return its URL.
+async fn serve_mock_rpc() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let slot = Arc::new(AtomicU64::new(FIRST_SLOT));
+
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(serve_connection(stream, slot.clone()));
+        }
+    });
+
+    url
+}
+
+/// Answer keep-alive HTTP/1.1 JSON-RPC requests until the client hangs up.
+async fn serve_connection(stream: TcpStream, slot: Arc<AtomicU64>) {
+    let mut reader = BufReader::new(stream);
+    loop {
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).await.unwrap_or(0) == 0 {
+                return;
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).await.unwrap();
+        let request: Value = serde_json::from_slice(&body).unwrap();
+        let response = respond(&request, &slot).to_string();
+
+        let head = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+            response.len()
+        );
+        let stream = reader.get_mut();
+        stream.write_all(head.as_bytes()).await.unwrap();
+        stream.write_all(response.as_bytes()).await.unwrap();
+    }
+}
+
+/// Canned JSON-RPC response envelope for `request`.
+fn respond(request: &Value, slot: &AtomicU64) -> Value {
+    let id = request["id"].clone();
+    let requested_slot = request["params"][0].as_u64();
+    let produced = |requested: u64| requested <= slot.load(Ordering::SeqCst);
+
+    match request["method"].as_str() {
+        Some("getSlot") => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "result": slot.fetch_add(1, Ordering::SeqCst)
+        }),
+        Some("getBlockTime") if requested_slot.is_some_and(produced) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "result": BLOCK_TIME
+        }),
+        Some("getBlock") if requested_slot.is_some_and(produced) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "result": {
+                "blockHeight": requested_slot.unwrap() - 20_000_000,
+                "blockTime": BLOCK_TIME,
+                "blockhash": "5Tx8F3jgSHx21CbtjwmdaKPLM5tWmreWAnPrbqHomSJF",
+                "parentSlot": requested_slot.unwrap() - 1,
+                "previousBlockhash": "4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T",
+                "signatures": []
+            }
+        }),
+        Some("getBlockTime") | Some("getBlock") => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {
+                "code": -32004,
+                "message": format!("Block not available for slot {}", requested_slot.unwrap_or(0))
+            }
+        }),
+        _ => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {"code": -32601, "message": "Method not found"}
+        }),
+    }
+}
+
+/// Write a `file:` time source offset that pins the clock to
+/// [`FIXED_NOW_MS`] as of now.
+fn pin_clock() -> std::path::PathBuf {
+    let realtime_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    let path = std::env::temp_dir().join(format!("mock-rpc-offset-{}", std::process::id()));
+    std::fs::write(
+        &path,
+        format!("# pinned clock\n{}\n", FIXED_NOW_MS - realtime_ms),
+    )
+    .unwrap();
+    path
+}
+
+#[tokio::test]
+async fn rpc_calls_read_the_mock() {
+    let url = serve_mock_rpc().await;
+    let client = Client::new();
+
+    let slot = rpc::get_latest_slot(&client, &url, Commitment::Processed)
+        .await
+        .unwrap();
+    assert_eq!(slot, FIRST_SLOT);
+    assert_eq!(
+        rpc::get_latest_slot(&client, &url, Commitment::Processed)
+            .await
+            .unwrap(),
+        FIRST_SLOT + 1
+    );
+
+    assert_eq!(
+        rpc::get_block_time(&client, &url, slot).await.unwrap(),
+        Some(BLOCK_TIME)
+    );
+    // Not produced yet: the JSON-RPC error means "not available", not a failure
+    assert_eq!(
+        rpc::get_block_time(&client, &url, slot + 100)
+            .await
+            .unwrap(),
+        None
+    );
+
+    let block = rpc::call(&client, &url, "getBlock", json!([slot]))
+        .await
+        .unwrap();
+    assert_eq!(block["result"]["blockTime"], BLOCK_TIME);
+    assert_eq!(block["result"]["parentSlot"], slot - 1);
+}
+
+#[tokio::test]
+async fn rpc_method_latency_against_a_pinned_clock() {
+    let url = serve_mock_rpc().await;
+    let offset_path = pin_clock();
+    let time_source = format!("file:{}", offset_path.display());
+    let config = WebsocketBenchConfig::parse_from([
+        "latency-calculator",
+        "--method",
+        "rpc",
+        "--endpoint",
+        url.as_str(),
+        "--target-slot",
+        "--time-source",
+        time_source.as_str(),
+    ]);
+
+    let report = run_websocket_benchmark(config).await.unwrap();
+    std::fs::remove_file(&offset_path).unwrap();
+
+    assert_eq!(report.measurements.len(), 1);
+    let measurement = &report.measurements[0];
+    // The first getSlot sets the baseline, the next one is the new slot
+    assert_eq!(measurement.slot, FIRST_SLOT + 1);
+    let expected_ms = FIXED_NOW_MS - BLOCK_TIME * 1000;
+    assert!(
+        (expected_ms..expected_ms + RUN_SLACK_MS).contains(&measurement.latency_ms),
+        "latency {}ms, expected {}ms plus at most {}ms of run time",
+        measurement.latency_ms,
+        expected_ms,
+        RUN_SLACK_MS
+    );
+    assert_eq!(
+        measurement.latency_ms,
+        measurement.received_ms - BLOCK_TIME * 1000
+    );
+}